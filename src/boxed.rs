@@ -0,0 +1,235 @@
+use crate::width::{display_width, WidthCounter};
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// The characters used to draw a box.
+struct BoxChars {
+    horizontal: char,
+    vertical: char,
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+}
+
+const UNICODE: BoxChars = BoxChars {
+    horizontal: '─',
+    vertical: '│',
+    top_left: '┌',
+    top_right: '┐',
+    bottom_left: '└',
+    bottom_right: '┘',
+};
+
+const ASCII: BoxChars = BoxChars {
+    horizontal: '-',
+    vertical: '|',
+    top_left: '+',
+    top_right: '+',
+    bottom_left: '+',
+    bottom_right: '+',
+};
+
+/// Draw a box around each line of a value's output. The box is as wide as the widest line, and
+/// narrower lines are padded to match. Lines are separated by `\n`, without a trailing line
+/// break.
+///
+/// The value is formatted twice, first to measure its lines and then to write them, so a value
+/// which can only be formatted once, such as a [join](crate::join()), panics unless it is wrapped in
+/// [reusable](crate::reusable). Lines are measured using [crate::width], so styled text is boxed
+/// according to its visible width.
+/// ```rust
+/// use fmttools::boxed;
+///
+/// let output = boxed("Disk almost full\n92% used").title("Warning").to_string();
+/// assert_eq!(output, "\
+/// ┌─ Warning ────────┐
+/// │ Disk almost full │
+/// │ 92% used         │
+/// └──────────────────┘");
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn boxed<T>(value: T) -> Boxed<'static, T> {
+    Boxed {
+        value,
+        title: None,
+        padding: 1,
+        ascii: false,
+    }
+}
+
+pub struct Boxed<'a, T> {
+    value: T,
+    title: Option<&'a str>,
+    padding: usize,
+    ascii: bool,
+}
+
+impl<'a, T> Boxed<'a, T> {
+    /// Write a title in the top border of the box. The box is widened if needed to fit the title.
+    #[inline]
+    pub fn title(self, title: &str) -> Boxed<'_, T> {
+        Boxed {
+            value: self.value,
+            title: Some(title),
+            padding: self.padding,
+            ascii: self.ascii,
+        }
+    }
+
+    /// Set the number of spaces between each side of the box and the lines inside it. Defaults
+    /// to 1.
+    #[inline]
+    pub fn padding(self, padding: usize) -> Self {
+        Boxed { padding, ..self }
+    }
+
+    /// If true, draw the box using ASCII characters instead of box-drawing characters.
+    /// ```rust
+    /// use fmttools::boxed;
+    ///
+    /// let output = boxed("ab\nabcd").ascii(true).padding(0);
+    /// assert_eq!("+----+\n|ab  |\n|abcd|\n+----+", output.to_string());
+    /// ```
+    #[inline]
+    pub fn ascii(self, ascii: bool) -> Self {
+        Boxed { ascii, ..self }
+    }
+}
+
+impl<T: Display> Display for Boxed<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let chars = if self.ascii { &ASCII } else { &UNICODE };
+
+        let mut measure = MeasureLines {
+            line: WidthCounter::new(),
+            max: 0,
+        };
+        write!(measure, "{}", self.value)?;
+        let mut inner = measure.line.width().max(measure.max) + 2 * self.padding;
+
+        // The title is surrounded by spaces and at least one horizontal line on each side
+        let title_width = self.title.map(display_width).map(|width| width + 3);
+        if let Some(title_width) = title_width {
+            inner = inner.max(title_width + 1);
+        }
+        let width = inner - 2 * self.padding;
+
+        f.write_char(chars.top_left)?;
+        match self.title {
+            Some(title) => {
+                f.write_char(chars.horizontal)?;
+                write!(f, " {} ", title)?;
+                write_repeated(f, chars.horizontal, inner - title_width.unwrap_or(0))?;
+            }
+            None => write_repeated(f, chars.horizontal, inner)?,
+        }
+        f.write_char(chars.top_right)?;
+        f.write_char('\n')?;
+
+        let mut writer = BoxWriter {
+            dst: &mut *f,
+            chars,
+            width,
+            padding: self.padding,
+            line: WidthCounter::new(),
+        };
+        writer.start_line()?;
+        write!(writer, "{}", self.value)?;
+        writer.end_line()?;
+
+        f.write_char('\n')?;
+        f.write_char(chars.bottom_left)?;
+        write_repeated(f, chars.horizontal, inner)?;
+        f.write_char(chars.bottom_right)
+    }
+}
+
+/// Finds the width of the widest line.
+struct MeasureLines {
+    line: WidthCounter,
+    max: usize,
+}
+
+impl Write for MeasureLines {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.line.write_str(first)?;
+        }
+
+        for line in lines {
+            self.max = self.max.max(self.line.width());
+            self.line = WidthCounter::new();
+            self.line.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the sides of the box around each line.
+struct BoxWriter<'c, W> {
+    dst: W,
+    chars: &'c BoxChars,
+    /// The width of the widest line
+    width: usize,
+    padding: usize,
+    /// Measures the width of the current line
+    line: WidthCounter,
+}
+
+impl<W: Write> BoxWriter<'_, W> {
+    fn start_line(&mut self) -> fmt::Result {
+        self.dst.write_char(self.chars.vertical)?;
+        write_repeated(&mut self.dst, ' ', self.padding)
+    }
+
+    fn end_line(&mut self) -> fmt::Result {
+        let space = self.width.saturating_sub(self.line.width()) + self.padding;
+        write_repeated(&mut self.dst, ' ', space)?;
+        self.dst.write_char(self.chars.vertical)?;
+        self.line = WidthCounter::new();
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for BoxWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.line.write_str(first)?;
+            self.dst.write_str(first)?;
+        }
+
+        for line in lines {
+            self.end_line()?;
+            self.dst.write_char('\n')?;
+            self.start_line()?;
+            self.line.write_str(line)?;
+            self.dst.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::boxed;
+
+    #[test]
+    fn title_wider_than_lines() {
+        let value = format_args!("{}\n{}", 1, "");
+        let output = boxed(value).title("long title").padding(2).ascii(true);
+        assert_eq!(
+            output.to_string(),
+            "+- long title -+\n|  1           |\n|              |\n+--------------+"
+        );
+        assert_eq!(boxed("").to_string(), "┌──┐\n│  │\n└──┘");
+    }
+}
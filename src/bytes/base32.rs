@@ -0,0 +1,114 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// The alphabet used by [base32].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Base32Alphabet {
+    /// The standard alphabet from RFC 4648, using `A-Z` followed by `2-7`.
+    Standard,
+    /// The extended hex alphabet from RFC 4648, using `0-9` followed by `A-V`. Unlike the standard
+    /// alphabet, this preserves the sort order of the encoded bytes.
+    Hex,
+}
+
+impl Base32Alphabet {
+    #[inline]
+    fn symbols(self) -> &'static [u8; 32] {
+        match self {
+            Base32Alphabet::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            Base32Alphabet::Hex => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+        }
+    }
+}
+
+/// Encode bytes as base32 using the standard alphabet with padding. No allocation is performed as
+/// part of this operation.
+/// ```rust
+/// use fmttools::bytes::{base32, Base32Alphabet};
+///
+/// assert_eq!("NBSWY3DP", format!("{}", base32(b"hello")));
+/// assert_eq!("MZXW6===", format!("{}", base32(b"foo")));
+/// assert_eq!("CPNMU", format!("{}", base32(b"foo").alphabet(Base32Alphabet::Hex).padding(false)));
+/// ```
+#[inline]
+pub fn base32(bytes: &[u8]) -> Base32<'_> {
+    Base32 {
+        bytes,
+        alphabet: Base32Alphabet::Standard,
+        padding: true,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Base32<'a> {
+    bytes: &'a [u8],
+    alphabet: Base32Alphabet,
+    padding: bool,
+}
+
+impl Base32<'_> {
+    /// Set the alphabet used to encode the bytes.
+    #[inline]
+    pub fn alphabet(self, alphabet: Base32Alphabet) -> Self {
+        Base32 { alphabet, ..self }
+    }
+
+    /// Set if `=` is written to pad the output to a multiple of 8 characters.
+    #[inline]
+    pub fn padding(self, padding: bool) -> Self {
+        Base32 { padding, ..self }
+    }
+}
+
+impl Display for Base32<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let symbols = self.alphabet.symbols();
+
+        for chunk in self.bytes.chunks(5) {
+            let mut bits = 0u64;
+            for (index, &byte) in chunk.iter().enumerate() {
+                bits |= u64::from(byte) << (32 - 8 * index);
+            }
+
+            // Number of characters needed to hold every bit of the chunk
+            let used = (chunk.len() * 8).div_ceil(5);
+            for index in 0..used {
+                let symbol = (bits >> (35 - 5 * index)) & 0x1f;
+                f.write_char(char::from(symbols[symbol as usize]))?;
+            }
+
+            if self.padding {
+                for _ in used..8 {
+                    f.write_char('=')?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base32, Base32Alphabet};
+
+    #[test]
+    fn rfc_4648_test_vectors() {
+        let vectors = [
+            ("", "", ""),
+            ("f", "MY======", "CO======"),
+            ("fo", "MZXQ====", "CPNG===="),
+            ("foo", "MZXW6===", "CPNMU==="),
+            ("foob", "MZXW6YQ=", "CPNMUOG="),
+            ("fooba", "MZXW6YTB", "CPNMUOJ1"),
+            ("foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+        ];
+
+        for (input, standard, hex) in vectors.iter() {
+            let encoded = base32(input.as_bytes());
+            assert_eq!(format!("{}", encoded), *standard);
+            let encoded = encoded.alphabet(Base32Alphabet::Hex);
+            assert_eq!(format!("{}", encoded), *hex);
+        }
+    }
+}
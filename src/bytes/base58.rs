@@ -0,0 +1,137 @@
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+const SYMBOLS: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The longest input which is encoded using a stack buffer.
+const MAX_STACK_INPUT: usize = 128;
+
+/// Encode bytes as base58 using the Bitcoin alphabet. Each leading zero byte is written as `1`.
+/// ```rust
+/// use fmttools::bytes::base58;
+///
+/// assert_eq!("StV1DL6CwTryKyV", format!("{}", base58(b"hello world")));
+/// assert_eq!("11233QC4", format!("{}", base58(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd])));
+/// ```
+///
+/// ## Note
+/// Base58 is not a block encoding, so the digits of the whole input must be computed before any of
+/// them can be written. Inputs of up to 128 bytes, not counting leading zeros, are encoded using a
+/// buffer on the stack. Longer inputs require allocating a buffer, so they are only supported when
+/// the `alloc` feature is enabled. Otherwise, formatting longer inputs fails without writing any
+/// output.
+#[inline]
+pub fn base58(bytes: &[u8]) -> Base58<'_> {
+    Base58 { bytes }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Base58<'a> {
+    bytes: &'a [u8],
+}
+
+/// Upper bound on the number of base58 digits needed to encode a number of bytes (log 256 / log 58
+/// is roughly 1.37).
+#[inline]
+const fn max_digits(bytes: usize) -> usize {
+    bytes * 138 / 100 + 1
+}
+
+/// Convert big endian bytes into little endian base58 digits, returning the number of digits used.
+fn to_digits(bytes: &[u8], digits: &mut [u8]) -> usize {
+    let mut length = 0;
+
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits[..length] {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+
+        while carry > 0 {
+            digits[length] = (carry % 58) as u8;
+            length += 1;
+            carry /= 58;
+        }
+    }
+
+    length
+}
+
+impl Display for Base58<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let zeros = self.bytes.iter().take_while(|&&byte| byte == 0).count();
+        let remaining = &self.bytes[zeros..];
+
+        if remaining.len() <= MAX_STACK_INPUT {
+            let mut buffer = [0u8; max_digits(MAX_STACK_INPUT)];
+            return write_base58(f, zeros, remaining, &mut buffer);
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            let mut buffer = alloc::vec![0u8; max_digits(remaining.len())];
+            write_base58(f, zeros, remaining, &mut buffer)
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        Err(fmt::Error)
+    }
+}
+
+/// Write the leading zeros followed by the digits of the remaining bytes, using the given buffer
+/// to hold the digits.
+fn write_base58(
+    f: &mut Formatter<'_>,
+    zeros: usize,
+    bytes: &[u8],
+    buffer: &mut [u8],
+) -> fmt::Result {
+    write_repeated(f, '1', zeros)?;
+
+    let length = to_digits(bytes, buffer);
+    for &digit in buffer[..length].iter().rev() {
+        f.write_char(char::from(SYMBOLS[usize::from(digit)]))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::base58;
+    #[cfg(not(feature = "alloc"))]
+    use std::fmt::Write;
+
+    #[test]
+    fn leading_zeros() {
+        assert_eq!(format!("{}", base58(&[])), "");
+        assert_eq!(format!("{}", base58(&[0, 0])), "11");
+        assert_eq!(format!("{}", base58(&[0, 1])), "12");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn long_input() {
+        // Exceeds the size of the stack buffer
+        let bytes = [0xffu8; 200];
+        let out = format!("{}", base58(&bytes));
+        assert_eq!(out.len(), 274);
+        assert!(out.bytes().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn long_input_requires_alloc() {
+        let mut zeros = [0u8; 200];
+        zeros[199] = 1;
+        assert_eq!(format!("{}", base58(&zeros)).len(), 200);
+
+        let mut out = String::new();
+        let result = write!(out, "{}", base58(&[0xffu8; 129]));
+        assert!(result.is_err());
+        assert_eq!(out, "");
+    }
+}
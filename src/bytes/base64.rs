@@ -0,0 +1,119 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// The alphabet used by [base64].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Base64Alphabet {
+    /// The standard alphabet from RFC 4648, using `+` and `/`.
+    Standard,
+    /// The URL and filename safe alphabet from RFC 4648, using `-` and `_`.
+    UrlSafe,
+}
+
+impl Base64Alphabet {
+    #[inline]
+    fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Base64Alphabet::Standard => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/"
+            }
+            Base64Alphabet::UrlSafe => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+        }
+    }
+}
+
+/// Encode bytes as base64 using the standard alphabet with padding. No allocation is performed as
+/// part of this operation.
+/// ```rust
+/// use fmttools::bytes::{base64, Base64Alphabet};
+///
+/// assert_eq!("aGVsbG8=", format!("{}", base64(b"hello")));
+/// assert_eq!("aGVsbG8", format!("{}", base64(b"hello").padding(false)));
+///
+/// let token = base64(&[0xfb, 0xff]).alphabet(Base64Alphabet::UrlSafe);
+/// assert_eq!("-_8=", format!("{}", token));
+/// ```
+#[inline]
+pub fn base64(bytes: &[u8]) -> Base64<'_> {
+    Base64 {
+        bytes,
+        alphabet: Base64Alphabet::Standard,
+        padding: true,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Base64<'a> {
+    bytes: &'a [u8],
+    alphabet: Base64Alphabet,
+    padding: bool,
+}
+
+impl Base64<'_> {
+    /// Set the alphabet used to encode the bytes.
+    #[inline]
+    pub fn alphabet(self, alphabet: Base64Alphabet) -> Self {
+        Base64 { alphabet, ..self }
+    }
+
+    /// Set if `=` is written to pad the output to a multiple of 4 characters.
+    #[inline]
+    pub fn padding(self, padding: bool) -> Self {
+        Base64 { padding, ..self }
+    }
+}
+
+impl Display for Base64<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let symbols = self.alphabet.symbols();
+
+        for chunk in self.bytes.chunks(3) {
+            let mut group = [0u8; 3];
+            group[..chunk.len()].copy_from_slice(chunk);
+            let bits = u32::from(group[0]) << 16 | u32::from(group[1]) << 8 | u32::from(group[2]);
+
+            // Each input byte contributes to one more output character
+            for index in 0..=chunk.len() {
+                let symbol = (bits >> (18 - 6 * index)) & 0x3f;
+                f.write_char(char::from(symbols[symbol as usize]))?;
+            }
+
+            if self.padding {
+                for _ in chunk.len()..3 {
+                    f.write_char('=')?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::base64;
+
+    #[test]
+    fn rfc_4648_test_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+
+        for (input, expected) in vectors.iter() {
+            assert_eq!(format!("{}", base64(input.as_bytes())), *expected);
+        }
+    }
+
+    #[test]
+    fn unpadded() {
+        assert_eq!(format!("{}", base64(b"f").padding(false)), "Zg");
+    }
+}
@@ -0,0 +1,182 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+const LOWER_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const UPPER_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Write a byte as two hex digits.
+#[inline]
+pub(crate) fn write_hex_byte<W: Write + ?Sized>(
+    out: &mut W,
+    byte: u8,
+    uppercase: bool,
+) -> fmt::Result {
+    let digits = if uppercase {
+        UPPER_DIGITS
+    } else {
+        LOWER_DIGITS
+    };
+    out.write_char(char::from(digits[usize::from(byte >> 4)]))?;
+    out.write_char(char::from(digits[usize::from(byte & 0xf)]))
+}
+
+/// Encode bytes as hex, with two lowercase digits per byte. No allocation is performed as part of
+/// this operation.
+/// ```rust
+/// use fmttools::bytes::hex;
+///
+/// let digest = [0xde, 0xad, 0xbe, 0xef];
+/// assert_eq!("deadbeef", format!("{}", hex(&digest)));
+/// assert_eq!("DEADBEEF", format!("{}", hex(&digest).uppercase(true)));
+/// ```
+#[inline]
+pub fn hex(bytes: &[u8]) -> Hex<'_> {
+    Hex {
+        bytes,
+        uppercase: false,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Hex<'a> {
+    bytes: &'a [u8],
+    uppercase: bool,
+}
+
+impl Hex<'_> {
+    /// Use uppercase hex digits.
+    #[inline]
+    pub fn uppercase(self, uppercase: bool) -> Self {
+        Hex { uppercase, ..self }
+    }
+}
+
+impl Display for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for &byte in self.bytes {
+            write_hex_byte(f, byte, self.uppercase)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encode bytes as hex, inserting a separator between every `group_size` bytes. A group size of
+/// zero disables grouping. This is commonly used for MAC addresses and key fingerprints.
+/// ```rust
+/// use fmttools::bytes::hex_grouped;
+///
+/// let mac = [0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+/// assert_eq!("aa:bb:cc:dd:ee:ff", format!("{}", hex_grouped(&mac, 1, ':')));
+/// assert_eq!("AABB CCDD EEFF", format!("{}", hex_grouped(&mac, 2, ' ').uppercase(true)));
+/// ```
+#[inline]
+pub fn hex_grouped(bytes: &[u8], group_size: usize, separator: char) -> HexGrouped<'_> {
+    HexGrouped {
+        bytes,
+        group_size,
+        separator,
+        uppercase: false,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HexGrouped<'a> {
+    bytes: &'a [u8],
+    group_size: usize,
+    separator: char,
+    uppercase: bool,
+}
+
+impl HexGrouped<'_> {
+    /// Use uppercase hex digits.
+    #[inline]
+    pub fn uppercase(self, uppercase: bool) -> Self {
+        HexGrouped { uppercase, ..self }
+    }
+}
+
+impl Display for HexGrouped<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, &byte) in self.bytes.iter().enumerate() {
+            if index > 0 && self.group_size > 0 && index % self.group_size == 0 {
+                f.write_char(self.separator)?;
+            }
+
+            write_hex_byte(f, byte, self.uppercase)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Encode bytes as binary, with eight digits per byte. Bytes are separated by a space by default.
+/// ```rust
+/// use fmttools::bytes::bits;
+///
+/// assert_eq!("00000001 10100101", format!("{}", bits(&[0x01, 0xa5])));
+/// assert_eq!("0000000110100101", format!("{}", bits(&[0x01, 0xa5]).separator("")));
+/// ```
+#[inline]
+pub fn bits(bytes: &[u8]) -> Bits<'_, 'static> {
+    Bits {
+        bytes,
+        separator: " ",
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Bits<'a, 'b> {
+    bytes: &'a [u8],
+    separator: &'b str,
+}
+
+impl<'a> Bits<'a, '_> {
+    /// Set the string written between bytes.
+    #[inline]
+    pub fn separator(self, separator: &str) -> Bits<'a, '_> {
+        Bits {
+            bytes: self.bytes,
+            separator,
+        }
+    }
+}
+
+impl Display for Bits<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, byte) in self.bytes.iter().enumerate() {
+            if index > 0 {
+                f.write_str(self.separator)?;
+            }
+
+            write!(f, "{:08b}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bits, hex, hex_grouped};
+
+    #[test]
+    fn hex_all_bytes() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let expected: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        assert_eq!(format!("{}", hex(&bytes)), expected);
+    }
+
+    #[test]
+    fn partial_last_group() {
+        let out = format!("{}", hex_grouped(&[1, 2, 3, 4, 5], 2, '-'));
+        assert_eq!(out, "0102-0304-05");
+        assert_eq!(format!("{}", hex_grouped(&[1, 2], 0, '-')), "0102");
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(format!("{}", hex(&[])), "");
+        assert_eq!(format!("{}", bits(&[])), "");
+    }
+}
@@ -0,0 +1,139 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::hex::write_hex_byte;
+
+/// Format bytes in the style of `xxd`. Each line contains the offset of its first byte, the bytes
+/// in hex (in groups of two) and the printable ASCII characters of those bytes. Other bytes are
+/// shown as `.` in the ASCII column. Lines are separated by `\n` and no trailing line break is
+/// written.
+/// ```rust
+/// use fmttools::bytes::hexdump;
+///
+/// let data = b"Hello, world!\n\x00\x01\x02";
+/// let expected = "\
+/// 00000000: 4865 6c6c 6f2c 2077 6f72 6c64 210a 0001  Hello, world!...
+/// 00000010: 02                                       .";
+/// assert_eq!(expected, format!("{}", hexdump(data)));
+///
+/// let expected = "00000100: 4865 6C6C 6F    Hello";
+/// let dump = hexdump(&data[..5]).bytes_per_line(6).uppercase(true).offset(0x100);
+/// assert_eq!(expected, format!("{}", dump));
+/// ```
+#[inline]
+pub fn hexdump(bytes: &[u8]) -> HexDump<'_> {
+    HexDump {
+        bytes,
+        bytes_per_line: 16,
+        uppercase: false,
+        offset: 0,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HexDump<'a> {
+    bytes: &'a [u8],
+    bytes_per_line: usize,
+    uppercase: bool,
+    offset: u64,
+}
+
+impl HexDump<'_> {
+    /// Set the number of bytes shown on each line. Defaults to 16.
+    ///
+    /// ## Panics
+    /// Panics if `bytes_per_line` is zero.
+    #[inline]
+    #[track_caller]
+    pub fn bytes_per_line(self, bytes_per_line: usize) -> Self {
+        assert!(bytes_per_line > 0, "bytes per line must be non-zero");
+        HexDump {
+            bytes_per_line,
+            ..self
+        }
+    }
+
+    /// Use uppercase hex digits for both the offsets and bytes.
+    #[inline]
+    pub fn uppercase(self, uppercase: bool) -> Self {
+        HexDump { uppercase, ..self }
+    }
+
+    /// Set the offset shown for the first byte. This is useful when dumping a slice taken from
+    /// the middle of a larger buffer.
+    #[inline]
+    pub fn offset(self, offset: u64) -> Self {
+        HexDump { offset, ..self }
+    }
+
+    /// Write a line of the dump. The hex column is padded to the width of a full line.
+    fn write_line(&self, f: &mut Formatter<'_>, offset: u64, line: &[u8]) -> fmt::Result {
+        if self.uppercase {
+            write!(f, "{:08X}:", offset)?;
+        } else {
+            write!(f, "{:08x}:", offset)?;
+        }
+
+        for index in 0..self.bytes_per_line {
+            if index % 2 == 0 {
+                f.write_char(' ')?;
+            }
+
+            match line.get(index) {
+                Some(&byte) => write_hex_byte(f, byte, self.uppercase)?,
+                None => f.write_str("  ")?,
+            }
+        }
+
+        f.write_str("  ")?;
+        for &byte in line {
+            match byte {
+                b' '..=b'~' => f.write_char(char::from(byte))?,
+                _ => f.write_char('.')?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut offset = self.offset;
+        for (index, line) in self.bytes.chunks(self.bytes_per_line).enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+
+            self.write_line(f, offset, line)?;
+            offset = offset.wrapping_add(line.len() as u64);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::hexdump;
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(format!("{}", hexdump(&[])), "");
+    }
+
+    #[test]
+    fn odd_bytes_per_line() {
+        let out = format!("{}", hexdump(b"abcdefg").bytes_per_line(3));
+        assert_eq!(
+            out,
+            "00000000: 6162 63  abc\n00000003: 6465 66  def\n00000006: 67       g"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_bytes_per_line() {
+        let _ = hexdump(b"abc").bytes_per_line(0);
+    }
+}
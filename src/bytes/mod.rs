@@ -0,0 +1,20 @@
+//! Adapters for displaying raw bytes.
+mod base32;
+mod base58;
+mod base64;
+mod hex;
+mod hexdump;
+mod quoted_printable;
+mod utf8;
+mod uuid;
+
+pub use base32::{base32, Base32, Base32Alphabet};
+pub use base58::{base58, Base58};
+pub use base64::{base64, Base64, Base64Alphabet};
+pub use hex::{bits, hex, hex_grouped, Bits, Hex, HexGrouped};
+pub use hexdump::{hexdump, HexDump};
+pub use quoted_printable::{
+    quoted_printable, quoted_printable_bytes, QuotedPrintable, QuotedPrintableBytes,
+};
+pub use utf8::{utf8_lossy, Utf8Lossy};
+pub use uuid::{uuid_hyphenated, uuid_simple, Uuid};
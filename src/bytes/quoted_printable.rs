@@ -0,0 +1,197 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::hex::write_hex_byte;
+
+/// The longest line written before the `=` of a soft line break.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Encode the formatted output of a value using the quoted-printable encoding from RFC 2045. Line
+/// breaks (`\n` or `\r\n`) in the output are written as `\r\n`, and longer lines are split with
+/// soft line breaks so no line exceeds 76 characters. No allocation is performed as part of this
+/// operation.
+/// ```rust
+/// use fmttools::bytes::quoted_printable;
+///
+/// let body = "Caf\u{e9} = caf\u{e9} \nBye";
+/// assert_eq!("Caf=C3=A9 =3D caf=C3=A9=20\r\nBye", format!("{}", quoted_printable(body)));
+/// ```
+#[inline]
+pub fn quoted_printable<T>(value: T) -> QuotedPrintable<T> {
+    QuotedPrintable(value)
+}
+
+pub struct QuotedPrintable<T>(T);
+
+impl<T: Display> Display for QuotedPrintable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = QuotedPrintableWriter::new(f, true);
+        write!(writer, "{}", self.0)?;
+        writer.finish()
+    }
+}
+
+/// Encode binary data using the quoted-printable encoding from RFC 2045. Unlike
+/// [quoted_printable], line breaks are encoded (as `=0D` and `=0A`) so the original bytes are
+/// preserved exactly.
+/// ```rust
+/// use fmttools::bytes::quoted_printable_bytes;
+///
+/// assert_eq!("a=0D=0A=FF", format!("{}", quoted_printable_bytes(b"a\r\n\xff")));
+/// ```
+#[inline]
+pub fn quoted_printable_bytes(bytes: &[u8]) -> QuotedPrintableBytes<'_> {
+    QuotedPrintableBytes(bytes)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct QuotedPrintableBytes<'a>(&'a [u8]);
+
+impl Display for QuotedPrintableBytes<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = QuotedPrintableWriter::new(f, false);
+        for &byte in self.0 {
+            writer.write_byte(byte)?;
+        }
+        writer.finish()
+    }
+}
+
+struct QuotedPrintableWriter<W> {
+    dst: W,
+    /// If line breaks are written as hard line breaks instead of being encoded
+    text: bool,
+    line_length: usize,
+    /// Whitespace is withheld since it must be encoded if it comes at the end of a line
+    pending_whitespace: Option<u8>,
+    /// A `\r` is withheld until it is known whether it is part of a `\r\n` line break
+    pending_cr: bool,
+}
+
+impl<W: Write> QuotedPrintableWriter<W> {
+    fn new(dst: W, text: bool) -> Self {
+        QuotedPrintableWriter {
+            dst,
+            text,
+            line_length: 0,
+            pending_whitespace: None,
+            pending_cr: false,
+        }
+    }
+
+    /// Start a new line with a soft line break if there is not enough room for the next token.
+    fn reserve(&mut self, length: usize) -> fmt::Result {
+        if self.line_length + length > MAX_LINE_LENGTH {
+            self.dst.write_str("=\r\n")?;
+            self.line_length = 0;
+        }
+
+        self.line_length += length;
+        Ok(())
+    }
+
+    fn write_literal(&mut self, byte: u8) -> fmt::Result {
+        self.reserve(1)?;
+        self.dst.write_char(char::from(byte))
+    }
+
+    fn write_encoded(&mut self, byte: u8) -> fmt::Result {
+        self.reserve(3)?;
+        self.dst.write_char('=')?;
+        write_hex_byte(&mut self.dst, byte, true)
+    }
+
+    /// Write any withheld whitespace. It must be encoded if it is the last character of a line.
+    fn flush_whitespace(&mut self, end_of_line: bool) -> fmt::Result {
+        match self.pending_whitespace.take() {
+            Some(byte) if end_of_line => self.write_encoded(byte),
+            Some(byte) => self.write_literal(byte),
+            None => Ok(()),
+        }
+    }
+
+    fn write_byte(&mut self, byte: u8) -> fmt::Result {
+        if self.pending_cr {
+            self.pending_cr = false;
+            if byte == b'\n' {
+                return self.write_line_break();
+            }
+
+            self.flush_whitespace(false)?;
+            self.write_encoded(b'\r')?;
+        }
+
+        match byte {
+            b'\r' if self.text => self.pending_cr = true,
+            b'\n' if self.text => self.write_line_break()?,
+            b' ' | b'\t' => {
+                self.flush_whitespace(false)?;
+                self.pending_whitespace = Some(byte);
+            }
+            b'!'..=b'~' if byte != b'=' => {
+                self.flush_whitespace(false)?;
+                self.write_literal(byte)?;
+            }
+            _ => {
+                self.flush_whitespace(false)?;
+                self.write_encoded(byte)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_line_break(&mut self) -> fmt::Result {
+        self.flush_whitespace(true)?;
+        self.dst.write_str("\r\n")?;
+        self.line_length = 0;
+        Ok(())
+    }
+
+    /// Write anything still withheld once the output has ended.
+    fn finish(&mut self) -> fmt::Result {
+        if self.pending_cr {
+            self.pending_cr = false;
+            self.flush_whitespace(false)?;
+            self.write_encoded(b'\r')?;
+        }
+
+        self.flush_whitespace(true)
+    }
+}
+
+impl<W: Write> Write for QuotedPrintableWriter<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        s.bytes().try_for_each(|byte| self.write_byte(byte))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quoted_printable, quoted_printable_bytes};
+
+    #[test]
+    fn soft_line_breaks() {
+        let out = format!("{}", quoted_printable("x".repeat(80)));
+        assert_eq!(out, format!("{}=\r\n{}", "x".repeat(75), "x".repeat(5)));
+
+        // Encoded bytes are never split across lines
+        let out = format!("{}", quoted_printable(format_args!("{}=", "x".repeat(74))));
+        assert_eq!(out, format!("{}=\r\n=3D", "x".repeat(74)));
+    }
+
+    #[test]
+    fn line_breaks_reset_length() {
+        let line = "y".repeat(70);
+        let out = format!("{}", quoted_printable(format_args!("{}\r\n{}", line, line)));
+        assert_eq!(out, format!("{}\r\n{}", line, line));
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        assert_eq!(format!("{}", quoted_printable("a \t")), "a =09");
+        assert_eq!(format!("{}", quoted_printable("a\r")), "a=0D");
+        assert_eq!(format!("{}", quoted_printable_bytes(b"a \n")), "a =0A");
+    }
+}
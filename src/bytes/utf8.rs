@@ -0,0 +1,66 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+use core::str;
+
+/// Display bytes as UTF-8, replacing each invalid sequence with U+FFFD (`�`). This produces the same
+/// output as [String::from_utf8_lossy], but without allocating.
+/// ```rust
+/// use fmttools::bytes::utf8_lossy;
+///
+/// let payload = b"Hello \xF0\x90\x80World\xff!";
+/// assert_eq!("Hello �World�!", format!("{}", utf8_lossy(payload)));
+/// ```
+#[inline]
+pub fn utf8_lossy(bytes: &[u8]) -> Utf8Lossy<'_> {
+    Utf8Lossy { bytes }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Utf8Lossy<'a> {
+    bytes: &'a [u8],
+}
+
+impl Display for Utf8Lossy<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.bytes;
+
+        loop {
+            match str::from_utf8(remaining) {
+                Ok(valid) => return f.write_str(valid),
+                Err(error) => {
+                    let (valid, after_valid) = remaining.split_at(error.valid_up_to());
+                    // Only valid UTF-8 can be before the error
+                    f.write_str(str::from_utf8(valid).map_err(|_| fmt::Error)?)?;
+                    f.write_char(char::REPLACEMENT_CHARACTER)?;
+
+                    // An unknown error length means the input ended partway through a character
+                    match error.error_len() {
+                        Some(length) => remaining = &after_valid[length..],
+                        None => return Ok(()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::utf8_lossy;
+
+    #[test]
+    fn matches_std() {
+        let inputs: [&[u8]; 5] = [
+            b"",
+            b"plain ascii",
+            b"\xe2\x82",
+            b"\xc0\x80\xed\xa0\x80",
+            "caf\u{e9}".as_bytes(),
+        ];
+
+        for input in inputs.iter() {
+            let expected = String::from_utf8_lossy(input);
+            assert_eq!(format!("{}", utf8_lossy(input)), expected);
+        }
+    }
+}
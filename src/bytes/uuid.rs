@@ -0,0 +1,85 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::hex::write_hex_byte;
+
+/// Format 16 bytes as a UUID in the standard hyphenated form (ex:
+/// `67e55044-10b1-426f-9247-bb680e5fe0c8`).
+/// ```rust
+/// use fmttools::bytes::uuid_hyphenated;
+///
+/// let id = [
+///     0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f,
+///     0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8,
+/// ];
+/// assert_eq!("67e55044-10b1-426f-9247-bb680e5fe0c8", format!("{}", uuid_hyphenated(&id)));
+/// assert_eq!("67E55044-10B1-426F-9247-BB680E5FE0C8", format!("{}", uuid_hyphenated(&id).uppercase(true)));
+/// ```
+#[inline]
+pub fn uuid_hyphenated(bytes: &[u8; 16]) -> Uuid<'_> {
+    Uuid {
+        bytes,
+        hyphenated: true,
+        uppercase: false,
+    }
+}
+
+/// Format 16 bytes as a UUID without hyphens (ex: `67e5504410b1426f9247bb680e5fe0c8`).
+/// ```rust
+/// use fmttools::bytes::uuid_simple;
+///
+/// let id = [
+///     0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f,
+///     0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8,
+/// ];
+/// assert_eq!("67e5504410b1426f9247bb680e5fe0c8", format!("{}", uuid_simple(&id)));
+/// ```
+#[inline]
+pub fn uuid_simple(bytes: &[u8; 16]) -> Uuid<'_> {
+    Uuid {
+        bytes,
+        hyphenated: false,
+        uppercase: false,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Uuid<'a> {
+    bytes: &'a [u8; 16],
+    hyphenated: bool,
+    uppercase: bool,
+}
+
+impl Uuid<'_> {
+    /// Use uppercase hex digits.
+    #[inline]
+    pub fn uppercase(self, uppercase: bool) -> Self {
+        Uuid { uppercase, ..self }
+    }
+}
+
+impl Display for Uuid<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, &byte) in self.bytes.iter().enumerate() {
+            if self.hyphenated && matches!(index, 4 | 6 | 8 | 10) {
+                f.write_char('-')?;
+            }
+
+            write_hex_byte(f, byte, self.uppercase)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{uuid_hyphenated, uuid_simple};
+
+    #[test]
+    fn nil_and_max() {
+        let nil = format!("{}", uuid_hyphenated(&[0; 16]));
+        assert_eq!(nil, "00000000-0000-0000-0000-000000000000");
+        assert_eq!(format!("{}", uuid_simple(&[0xff; 16])), "f".repeat(32));
+    }
+}
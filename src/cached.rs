@@ -0,0 +1,172 @@
+use core::cell::Cell;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// A [Display] adapter which formats its value into an internal buffer of `N` bytes the first time
+/// it is displayed and replays the buffered text afterwards. This allows values which are
+/// expensive to format, or which can only be formatted once, to be displayed repeatedly.
+///
+/// The value is always formatted without any formatting options, and options such as the width and
+/// precision are then applied to the cached text as they would be for a string.
+///
+/// If the output does not fit in `N` bytes, the buffered text is written followed by the rest of
+/// the output as it is produced, so the value is still only formatted once for that display.
+/// Formatting options are not applied to output which does not fit, and the value is formatted
+/// again every time it is displayed afterwards. Values which can only be formatted once should
+/// use an `N` large enough to hold their output.
+/// ```rust
+/// use fmttools::{join, Cached};
+///
+/// // A join over an iterator can normally only be formatted once
+/// let cached = Cached::<_, 16>::new(join(1..=4, ", "));
+/// assert_eq!("1, 2, 3, 4", cached.to_string());
+/// assert_eq!("1, 2, 3, 4", cached.to_string());
+/// assert_eq!("1, 2, 3, 4  ", format!("{:<12}", cached));
+/// ```
+pub struct Cached<T, const N: usize> {
+    value: T,
+    cache: Cell<Cache<N>>,
+}
+
+#[derive(Copy, Clone)]
+enum Cache<const N: usize> {
+    Empty,
+    Rendered([u8; N], usize),
+    Overflow,
+}
+
+impl<T, const N: usize> Cached<T, N> {
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Cached {
+            value,
+            cache: Cell::new(Cache::Empty),
+        }
+    }
+
+    /// Unwrap this adapter, returning the inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Display, const N: usize> Display for Cached<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (buf, len) = match self.cache.get() {
+            Cache::Rendered(buf, len) => (buf, len),
+            Cache::Overflow => return write!(f, "{}", self.value),
+            Cache::Empty => {
+                let mut writer = SpillWriter {
+                    buf: [0u8; N],
+                    len: 0,
+                    dst: &mut *f,
+                    spilled: false,
+                };
+                write!(writer, "{}", self.value)?;
+
+                if writer.spilled {
+                    // The output was already written directly, so format the value from now on
+                    self.cache.set(Cache::Overflow);
+                    return Ok(());
+                }
+
+                self.cache.set(Cache::Rendered(writer.buf, writer.len));
+                (writer.buf, writer.len)
+            }
+        };
+
+        // The buffer was filled from a `&str`, so it always holds valid UTF-8
+        f.pad(core::str::from_utf8(&buf[..len]).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Buffers output until it exceeds `N` bytes, at which point the buffered text and any further
+/// output are written to the destination instead.
+struct SpillWriter<W, const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    dst: W,
+    spilled: bool,
+}
+
+impl<W: Write, const N: usize> SpillWriter<W, N> {
+    fn spill(&mut self) -> fmt::Result {
+        self.spilled = true;
+        // Only whole strings are copied into the buffer, so it always holds valid UTF-8
+        let buffered = core::str::from_utf8(&self.buf[..self.len]).map_err(|_| fmt::Error)?;
+        self.dst.write_str(buffered)
+    }
+}
+
+impl<W: Write, const N: usize> Write for SpillWriter<W, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.spilled {
+            if let Some(dst) = self.buf.get_mut(self.len..self.len + s.len()) {
+                dst.copy_from_slice(s.as_bytes());
+                self.len += s.len();
+                return Ok(());
+            }
+
+            self.spill()?;
+        }
+
+        self.dst.write_str(s)
+    }
+}
+
+impl<T: Debug, const N: usize> Debug for Cached<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Cached").field(&self.value).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cached;
+    use crate::join;
+    use std::cell::Cell;
+    use std::fmt::{self, Display, Formatter, Write};
+
+    struct CountCalls<'a>(&'a Cell<usize>, &'a str);
+
+    impl Display for CountCalls<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            f.pad(self.1)
+        }
+    }
+
+    #[test]
+    fn formats_once_when_output_fits() {
+        let calls = Cell::new(0);
+        let cached = Cached::<_, 8>::new(CountCalls(&calls, "héllo"));
+        assert_eq!("héllo", cached.to_string());
+        assert_eq!("héllo", cached.to_string());
+        assert_eq!(1, calls.get());
+
+        assert_eq!("héllo  ", format!("{:<7}", cached));
+        assert_eq!("hé", format!("{:.2}", cached));
+        assert_eq!(1, calls.get());
+    }
+
+    #[test]
+    fn overflow_falls_back_to_value() {
+        let calls = Cell::new(0);
+        let cached = Cached::<_, 4>::new(CountCalls(&calls, "héllo"));
+        assert_eq!("héllo", cached.to_string());
+        assert_eq!(1, calls.get());
+        assert_eq!("héllo", cached.to_string());
+        assert_eq!(2, calls.get());
+    }
+
+    #[test]
+    fn overflow_formats_single_use_value_once() {
+        let cached = Cached::<_, 4>::new(join(1..=4, ", "));
+        assert_eq!("1, 2, 3, 4", cached.to_string());
+
+        let mut out = String::new();
+        write!(out, "[{}]", Cached::<_, 2>::new(join(["ab", "c"], "-"))).unwrap();
+        assert_eq!("[ab-c]", out);
+    }
+}
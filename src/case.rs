@@ -0,0 +1,148 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Convert the output of a value to uppercase, as with [str::to_uppercase]. No allocation is
+/// performed as part of this operation.
+/// ```rust
+/// use fmttools::case::uppercase;
+///
+/// assert_eq!("ERROR: DISK FULL", format!("{}", uppercase(format_args!("error: {}", "disk full"))));
+/// assert_eq!("STRASSE", format!("{}", uppercase("straße")));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn uppercase<T>(value: T) -> Uppercase<T> {
+    Uppercase { value }
+}
+
+/// Convert the output of a value to lowercase, as with [str::to_lowercase]. Unlike
+/// [str::to_lowercase], a final sigma is not given special treatment since the end of a word is
+/// not known while streaming. No allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::case::lowercase;
+///
+/// assert_eq!("content-type", format!("{}", lowercase("Content-Type")));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn lowercase<T>(value: T) -> Lowercase<T> {
+    Lowercase { value }
+}
+
+pub struct Uppercase<T> {
+    value: T,
+}
+
+impl<T: Debug> Debug for Uppercase<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            CaseWriter {
+                dst: f,
+                upper: true
+            },
+            "{:?}",
+            self.value
+        )
+    }
+}
+
+impl<T: Display> Display for Uppercase<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            CaseWriter {
+                dst: f,
+                upper: true
+            },
+            "{}",
+            self.value
+        )
+    }
+}
+
+pub struct Lowercase<T> {
+    value: T,
+}
+
+impl<T: Debug> Debug for Lowercase<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            CaseWriter {
+                dst: f,
+                upper: false
+            },
+            "{:?}",
+            self.value
+        )
+    }
+}
+
+impl<T: Display> Display for Lowercase<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            CaseWriter {
+                dst: f,
+                upper: false
+            },
+            "{}",
+            self.value
+        )
+    }
+}
+
+struct CaseWriter<W> {
+    dst: W,
+    upper: bool,
+}
+
+impl<W: Write> Write for CaseWriter<W> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.upper {
+            write_mapped(&mut self.dst, s, char::to_uppercase)
+        } else {
+            write_mapped(&mut self.dst, s, char::to_lowercase)
+        }
+    }
+}
+
+/// Write a string with each character replaced by its mapping, writing unchanged runs of
+/// characters directly.
+fn write_mapped<W, I>(dst: &mut W, s: &str, mapping: fn(char) -> I) -> fmt::Result
+where
+    W: Write,
+    I: ExactSizeIterator<Item = char>,
+{
+    let mut flushed = 0;
+
+    for (index, c) in s.char_indices() {
+        let mut mapped = mapping(c);
+        if mapped.len() == 1 && mapping(c).next() == Some(c) {
+            continue;
+        }
+
+        dst.write_str(&s[flushed..index])?;
+        mapped.try_for_each(|x| dst.write_char(x))?;
+        flushed = index + c.len_utf8();
+    }
+
+    dst.write_str(&s[flushed..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lowercase, uppercase};
+
+    #[test]
+    fn debug_and_non_ascii() {
+        assert_eq!("\"ÀB\\N\"", format!("{:?}", uppercase("àb\n")));
+        assert_eq!("ǆ i̇", format!("{}", lowercase("ǅ İ")));
+    }
+}
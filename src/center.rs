@@ -0,0 +1,132 @@
+use crate::width::WidthCounter;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+use core::str;
+
+/// The number of bytes of a single line which are held back while it is measured. Longer lines
+/// are written without padding.
+const LINE_BUFFER: usize = 256;
+
+/// Center each line of a value's output within `width` columns. Lines are padded on the left
+/// only, so no trailing whitespace is added, and lines which are already wider than `width` are
+/// written unchanged. Lines are measured using [crate::width].
+///
+/// Each line is held in an internal buffer while it is measured. Lines longer than 256 bytes are
+/// written without padding.
+/// ```rust
+/// use fmttools::center_block;
+///
+/// let banner = "*****\nfmttools\n*****";
+/// assert_eq!(
+///     "   *****\n  fmttools\n   *****",
+///     format!("{}", center_block(banner, 12))
+/// );
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn center_block<T>(value: T, width: usize) -> CenterBlock<T> {
+    CenterBlock { value, width }
+}
+
+pub struct CenterBlock<T> {
+    value: T,
+    width: usize,
+}
+
+impl<T: Display> Display for CenterBlock<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = CenterWriter {
+            dst: f,
+            width: self.width,
+            measure: WidthCounter::new(),
+            buf: [0; LINE_BUFFER],
+            len: 0,
+            streaming: false,
+        };
+
+        write!(writer, "{}", self.value)?;
+        writer.flush_line()
+    }
+}
+
+/// Holds back each line until its end is found, then writes it with padding.
+struct CenterWriter<W> {
+    dst: W,
+    width: usize,
+    /// Measures the width of the current line
+    measure: WidthCounter,
+    buf: [u8; LINE_BUFFER],
+    len: usize,
+    /// If the current line did not fit in the buffer, so the rest of it is being written directly
+    streaming: bool,
+}
+
+impl<W: Write> CenterWriter<W> {
+    /// Write the buffered line, padded if it is narrower than the width.
+    fn flush_line(&mut self) -> fmt::Result {
+        if !self.streaming && self.len > 0 {
+            let padding = self.width.saturating_sub(self.measure.width()) / 2;
+            write_repeated(&mut self.dst, ' ', padding)?;
+        }
+
+        // Only whole characters are ever added to the buffer
+        let line = str::from_utf8(&self.buf[..self.len]).map_err(|_| fmt::Error)?;
+        self.dst.write_str(line)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for CenterWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        let mut line = lines.next().unwrap_or("");
+
+        loop {
+            if self.streaming {
+                self.dst.write_str(line)?;
+            } else if self.len + line.len() <= LINE_BUFFER {
+                self.buf[self.len..self.len + line.len()].copy_from_slice(line.as_bytes());
+                self.len += line.len();
+                self.measure.write_str(line)?;
+            } else {
+                // The line is too long to hold back, so write it without padding
+                self.streaming = true;
+                self.flush_line()?;
+                self.dst.write_str(line)?;
+            }
+
+            line = match lines.next() {
+                Some(next) => next,
+                None => return Ok(()),
+            };
+
+            self.flush_line()?;
+            self.dst.write_char('\n')?;
+            self.measure = WidthCounter::new();
+            self.streaming = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{center_block, LINE_BUFFER};
+
+    #[test]
+    fn lines_split_across_writes() {
+        let value = format_args!("{}{}{}", "a", "b\n\nabcd", "ef\n");
+        assert_eq!("  ab\n\nabcdef\n", format!("{}", center_block(value, 6)));
+
+        let long = "x".repeat(LINE_BUFFER + 1);
+        let value = format_args!("{}\n{}", long, "ab");
+        let expected = format!("{}\n{}ab", long, " ".repeat(LINE_BUFFER / 2));
+        assert_eq!(
+            expected,
+            format!("{}", center_block(value, LINE_BUFFER + 2))
+        );
+    }
+}
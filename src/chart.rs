@@ -0,0 +1,320 @@
+//! Small charts drawn using Unicode block characters.
+//!
+//! ```rust
+//! use fmttools::chart::{hbar, sparkline};
+//!
+//! let latency = [12.0, 15.0, 11.0, 30.0, 22.0, 12.0];
+//! assert_eq!("▁▂▁█▅▁", sparkline(latency.iter().copied()).to_string());
+//!
+//! let get = format!("{:<4} {} {}", "GET", hbar(120.0, 120.0, 10), 120);
+//! let post = format!("{:<4} {} {}", "POST", hbar(45.0, 120.0, 10), 45);
+//! assert_eq!("GET  ██████████ 120", get);
+//! assert_eq!("POST ███▊       45", post);
+//! ```
+use crate::math;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Blocks of increasing height, used by [sparkline].
+const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Blocks of increasing width from one to seven eighths of a column, used by [hbar].
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// A number which can be drawn by [sparkline]. This is implemented for every primitive integer and
+/// float, including those such as [u64] which can not be losslessly converted with [Into<f64>].
+pub trait AsF64: Copy {
+    /// Convert the value to the nearest [f64].
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_as_f64 {
+    ($($t:ty),*) => {
+        $(impl AsF64 for $t {
+            #[inline]
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_as_f64!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T: AsF64> AsF64 for &T {
+    #[inline]
+    fn as_f64(self) -> f64 {
+        (*self).as_f64()
+    }
+}
+
+/// Draw a sequence of numbers as a sparkline, with one block character per value whose height is
+/// proportional to the value. By default, blocks are scaled between the smallest and largest
+/// values, so the values are iterated twice and must be [Clone]. NaN values are drawn as spaces.
+/// ```rust
+/// use fmttools::chart::sparkline;
+///
+/// assert_eq!("▁▂▃▄▅▆▇█", sparkline(1..=8).to_string());
+/// assert_eq!("▁▁▁", sparkline(vec![5; 3]).to_string());
+///
+/// let bytes_sent: [u64; 4] = [1024, 4096, 2048, 8192];
+/// assert_eq!("▁▄▂█", sparkline(&bytes_sent).to_string());
+/// ```
+#[inline]
+pub fn sparkline<I>(values: I) -> Sparkline<I> {
+    Sparkline {
+        values,
+        bounds: None,
+    }
+}
+
+pub struct Sparkline<I> {
+    values: I,
+    bounds: Option<(f64, f64)>,
+}
+
+impl<I> Sparkline<I> {
+    /// Scale blocks between the given bounds instead of the smallest and largest values. Values
+    /// outside of the bounds are drawn as the lowest or highest block.
+    /// ```rust
+    /// use fmttools::chart::sparkline;
+    ///
+    /// let cpu = [5.0, 50.0, 100.0, 120.0];
+    /// assert_eq!("▁▅██", sparkline(cpu.iter().copied()).bounds(0.0, 100.0).to_string());
+    /// ```
+    #[inline]
+    pub fn bounds(self, min: f64, max: f64) -> Self {
+        Sparkline {
+            bounds: Some((min, max)),
+            ..self
+        }
+    }
+}
+
+impl<I> Display for Sparkline<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: AsF64,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (min, max) = match self.bounds {
+            Some(bounds) => bounds,
+            None => self
+                .values
+                .clone()
+                .into_iter()
+                .map(AsF64::as_f64)
+                .filter(|value: &f64| !value.is_nan())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                    (min.min(value), max.max(value))
+                }),
+        };
+
+        let top = (LEVELS.len() - 1) as f64;
+        for value in self.values.clone() {
+            let value = value.as_f64();
+            if value.is_nan() {
+                f.write_char(' ')?;
+                continue;
+            }
+
+            let scaled = if max > min {
+                math::round((value - min) / (max - min) * top)
+            } else {
+                0.0
+            };
+            f.write_char(LEVELS[scaled.max(0.0).min(top) as usize])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Draw a horizontal bar `width` columns wide, filled in proportion to `value / max`. The end of
+/// the bar is drawn in eighths of a column and the rest of the width is padded with spaces, so
+/// bars drawn with the same width line up. Values are clamped between 0 and `max`.
+/// ```rust
+/// use fmttools::chart::hbar;
+///
+/// assert_eq!("█████     ", hbar(50.0, 100.0, 10).to_string());
+/// assert_eq!("██▌ ", hbar(2.5, 4.0, 4).to_string());
+/// ```
+#[inline]
+pub fn hbar(value: f64, max: f64, width: usize) -> HBar {
+    HBar { value, max, width }
+}
+
+pub struct HBar {
+    value: f64,
+    max: f64,
+    width: usize,
+}
+
+impl Display for HBar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ratio = if self.max > 0.0 && !self.value.is_nan() {
+            (self.value / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let eighths = math::round(ratio * self.width as f64 * 8.0) as usize;
+        let full = eighths / 8;
+        write_repeated(f, '█', full)?;
+
+        let mut written = full;
+        let partial = eighths % 8;
+        if partial > 0 {
+            f.write_char(EIGHTHS[partial - 1])?;
+            written += 1;
+        }
+
+        for _ in written..self.width {
+            f.write_char(' ')?;
+        }
+        Ok(())
+    }
+}
+
+/// Draw a progress bar such as `[####......] 42%`, with the bar `width` columns wide between the
+/// brackets. Only the text of the bar is written, so it can be embedded in any status line or log
+/// message. A `total` of 0 is treated as complete, and `completed` is clamped to `total`.
+/// ```rust
+/// use fmttools::chart::progress;
+///
+/// assert_eq!("[####......] 42%", progress(42, 100, 10).to_string());
+///
+/// let bar = progress(3, 5, 5).chars('=', ' ').head('>');
+/// assert_eq!("[==>  ] 3/5", bar.percent(false).fraction(true).to_string());
+/// ```
+#[inline]
+pub fn progress(completed: u64, total: u64, width: usize) -> Progress {
+    Progress {
+        completed,
+        total,
+        width,
+        fill: '#',
+        empty: '.',
+        head: None,
+        percent: true,
+        fraction: false,
+    }
+}
+
+pub struct Progress {
+    completed: u64,
+    total: u64,
+    width: usize,
+    fill: char,
+    empty: char,
+    head: Option<char>,
+    percent: bool,
+    fraction: bool,
+}
+
+impl Progress {
+    /// Set the characters used for the completed and remaining parts of the bar. Defaults to `#`
+    /// and `.`.
+    #[inline]
+    pub fn chars(self, fill: char, empty: char) -> Self {
+        Progress {
+            fill,
+            empty,
+            ..self
+        }
+    }
+
+    /// Draw the last column of the completed part of the bar using the given character, as in
+    /// `[===>  ]`. The head is not drawn once progress is complete.
+    #[inline]
+    pub fn head(self, head: char) -> Self {
+        Progress {
+            head: Some(head),
+            ..self
+        }
+    }
+
+    /// If true, write the percentage completed after the bar. Defaults to true.
+    #[inline]
+    pub fn percent(self, percent: bool) -> Self {
+        Progress { percent, ..self }
+    }
+
+    /// If true, write the number completed out of the total after the bar, as in `42/100`.
+    #[inline]
+    pub fn fraction(self, fraction: bool) -> Self {
+        Progress { fraction, ..self }
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let completed = self.completed.min(self.total);
+        let (filled, percent) = match self.total {
+            0 => (self.width, 100),
+            total => {
+                let filled = completed as u128 * self.width as u128 / total as u128;
+                (filled as usize, completed as u128 * 100 / total as u128)
+            }
+        };
+
+        f.write_char('[')?;
+        for column in 0..self.width {
+            let c = match self.head {
+                Some(head) if column + 1 == filled && filled < self.width => head,
+                _ if column < filled => self.fill,
+                _ => self.empty,
+            };
+            f.write_char(c)?;
+        }
+        f.write_char(']')?;
+
+        if self.percent {
+            write!(f, " {}%", percent)?;
+        }
+        if self.fraction {
+            write!(f, " {}/{}", completed, self.total)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hbar, progress, sparkline};
+
+    #[test]
+    fn sparkline_wide_integers() {
+        let deltas: [i64; 3] = [-10, 0, 10];
+        assert_eq!("▁▅█", sparkline(deltas.iter().copied()).to_string());
+        assert_eq!("▁█", sparkline(vec![0usize, usize::MAX]).to_string());
+    }
+
+    #[test]
+    fn sparkline_nan_and_empty() {
+        let values = [f64::NAN, 0.0, 1.0];
+        assert_eq!(" ▁█", sparkline(values.iter().copied()).to_string());
+        assert_eq!("", sparkline(Vec::<f64>::new()).to_string());
+    }
+
+    #[test]
+    fn hbar_clamps_and_rounds() {
+        assert_eq!("███", hbar(7.0, 5.0, 3).to_string());
+        assert_eq!("   ", hbar(-1.0, 5.0, 3).to_string());
+        assert_eq!("▏  ", hbar(0.4, 10.0, 3).to_string());
+        assert_eq!("", hbar(1.0, 1.0, 0).to_string());
+    }
+
+    #[test]
+    fn progress_edges() {
+        assert_eq!("[] 0% 0/3", progress(0, 3, 0).fraction(true).to_string());
+        assert_eq!("[###] 100%", progress(9, 3, 3).to_string());
+        assert_eq!("[###] 100%", progress(0, 0, 3).head('>').to_string());
+        assert_eq!(
+            "[>..]",
+            progress(1, 3, 3).head('>').percent(false).to_string()
+        );
+        assert_eq!("[##.] 99%", progress(99, 100, 3).to_string());
+    }
+}
@@ -0,0 +1,287 @@
+//! Align delimited columns across rows, in the style of elastic tabstops.
+//!
+//! Each row is formatted and split at every occurrence of a delimiter. Cells are then padded so
+//! that each column starts at the same position in every row. Like [crate::table], the rows are
+//! rendered in two passes, so each row is formatted twice.
+//!
+//! ```rust
+//! use fmttools::columns;
+//!
+//! let pods = [("web-7d4b9", "Running", 0), ("db-0", "CrashLoopBackOff", 12)];
+//! let mut rows = vec!["NAME\tSTATUS\tRESTARTS".to_string()];
+//! for (name, status, restarts) in &pods {
+//!     rows.push(format!("{}\t{}\t{}", name, status, restarts));
+//! }
+//!
+//! assert_eq!(format!("{}", columns(&rows, "\t")), "\
+//! NAME       STATUS            RESTARTS
+//! web-7d4b9  Running           0
+//! db-0       CrashLoopBackOff  12");
+//! ```
+use crate::width::WidthCounter;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Align the cells of rows split at each occurrence of `delimiter`. Rows are separated by `\n`,
+/// without a trailing line break. See [crate::columns] for more information.
+///
+/// The rows are iterated twice, so they must be [Clone]. Only cells which are followed by a
+/// delimiter affect the width of their column, so a row without any delimiters does not widen
+/// the first column. An empty delimiter does not split rows.
+/// ```rust
+/// use fmttools::columns;
+///
+/// let rows = [format_args!("{}|{}", "a", 1), format_args!("{}|{}", "bcd", 2)];
+/// assert_eq!("a    1\nbcd  2", format!("{}", columns(&rows, "|")));
+/// ```
+#[inline]
+pub fn columns<'a, R>(rows: R, delimiter: &'a str) -> Columns<'a, R> {
+    Columns::new(rows, delimiter)
+}
+
+/// Rows of delimited cells created by [columns]. Up to `N` columns are aligned, and any further
+/// columns are separated by the gap without alignment.
+pub struct Columns<'a, R, const N: usize = 16> {
+    rows: R,
+    delimiter: &'a str,
+    gap: usize,
+}
+
+impl<'a, R, const N: usize> Columns<'a, R, N> {
+    /// Create columns which align up to `N` columns.
+    #[inline]
+    pub fn new(rows: R, delimiter: &'a str) -> Self {
+        Columns {
+            rows,
+            delimiter,
+            gap: 2,
+        }
+    }
+
+    /// Set the minimum number of spaces between columns. Defaults to 2.
+    #[inline]
+    pub fn gap(self, gap: usize) -> Self {
+        Columns { gap, ..self }
+    }
+}
+
+impl<R, const N: usize> Display for Columns<'_, R, N>
+where
+    R: IntoIterator + Clone,
+    R::Item: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut widths = [0; N];
+        for row in self.rows.clone() {
+            let mut splitter = Splitter::new(
+                self.delimiter,
+                MeasureCells {
+                    widths: &mut widths,
+                    column: 0,
+                    counter: WidthCounter::new(),
+                },
+            );
+            write!(splitter, "{}", row)?;
+            splitter.finish()?;
+        }
+
+        for (index, row) in self.rows.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+
+            let mut splitter = Splitter::new(
+                self.delimiter,
+                WriteCells {
+                    dst: &mut *f,
+                    widths: &widths,
+                    gap: self.gap,
+                    column: 0,
+                    counter: WidthCounter::new(),
+                    pending: 0,
+                },
+            );
+            write!(splitter, "{}", row)?;
+            splitter.finish()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Receives the text of each cell in a row from a [Splitter].
+trait Cells {
+    fn text(&mut self, s: &str) -> fmt::Result;
+
+    /// The current cell was ended by a delimiter.
+    fn end_cell(&mut self) -> fmt::Result;
+}
+
+/// Splits written text at a delimiter, which may be broken across multiple writes.
+struct Splitter<'a, C> {
+    delimiter: &'a str,
+    /// The number of bytes at the end of the previous write which match the start of the delimiter
+    matched: usize,
+    cells: C,
+}
+
+impl<'a, C: Cells> Splitter<'a, C> {
+    #[inline]
+    fn new(delimiter: &'a str, cells: C) -> Self {
+        Splitter {
+            delimiter,
+            matched: 0,
+            cells,
+        }
+    }
+
+    /// Write any partial match left at the end of the row as text.
+    fn finish(&mut self) -> fmt::Result {
+        let matched = self.matched;
+        self.matched = 0;
+        self.cells.text(&self.delimiter[..matched])
+    }
+}
+
+impl<C: Cells> Write for Splitter<'_, C> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        let delimiter = self.delimiter;
+        if delimiter.is_empty() {
+            return self.cells.text(s);
+        }
+
+        while !s.is_empty() {
+            if self.matched > 0 {
+                let len = s.len().min(delimiter.len() - self.matched);
+                if s.as_bytes()[..len] == delimiter.as_bytes()[self.matched..self.matched + len] {
+                    self.matched += len;
+                    s = &s[len..];
+                    if self.matched == delimiter.len() {
+                        self.matched = 0;
+                        self.cells.end_cell()?;
+                    }
+                    continue;
+                }
+
+                // The partial match failed, so the first character is text and the remainder of
+                // the partial match must be searched again
+                let matched = self.matched;
+                let first = delimiter.chars().next().map_or(0, char::len_utf8);
+                self.matched = 0;
+                self.cells.text(&delimiter[..first])?;
+                self.write_str(&delimiter[first..matched])?;
+                continue;
+            }
+
+            if let Some(index) = s.find(delimiter) {
+                self.cells.text(&s[..index])?;
+                self.cells.end_cell()?;
+                s = &s[index + delimiter.len()..];
+                continue;
+            }
+
+            // Withhold the longest end of the text which could be the start of a delimiter
+            let partial = (1..delimiter.len().min(s.len() + 1))
+                .rev()
+                .find(|&len| delimiter.is_char_boundary(len) && s.ends_with(&delimiter[..len]))
+                .unwrap_or(0);
+            self.matched = partial;
+            return self.cells.text(&s[..s.len() - partial]);
+        }
+
+        Ok(())
+    }
+}
+
+struct MeasureCells<'w, const N: usize> {
+    widths: &'w mut [usize; N],
+    column: usize,
+    counter: WidthCounter,
+}
+
+impl<const N: usize> Cells for MeasureCells<'_, N> {
+    #[inline]
+    fn text(&mut self, s: &str) -> fmt::Result {
+        self.counter.write_str(s)
+    }
+
+    #[inline]
+    fn end_cell(&mut self) -> fmt::Result {
+        if let Some(width) = self.widths.get_mut(self.column) {
+            *width = (*width).max(self.counter.width());
+        }
+        self.column += 1;
+        self.counter = WidthCounter::new();
+        Ok(())
+    }
+}
+
+struct WriteCells<'w, W, const N: usize> {
+    dst: W,
+    widths: &'w [usize; N],
+    gap: usize,
+    column: usize,
+    counter: WidthCounter,
+    /// Padding which has not been written yet, so rows do not end in whitespace
+    pending: usize,
+}
+
+impl<W: Write, const N: usize> Cells for WriteCells<'_, W, N> {
+    fn text(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        write_repeated(&mut self.dst, ' ', self.pending)?;
+        self.pending = 0;
+
+        self.counter.write_str(s)?;
+        self.dst.write_str(s)
+    }
+
+    #[inline]
+    fn end_cell(&mut self) -> fmt::Result {
+        let width = self.widths.get(self.column).copied().unwrap_or(0);
+        self.pending += width.saturating_sub(self.counter.width()) + self.gap;
+        self.column += 1;
+        self.counter = WidthCounter::new();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{columns, Columns};
+
+    #[test]
+    fn delimiter_split_across_writes() {
+        let rows = [
+            format_args!("{}{}{}", "a:", ":b", "::c"),
+            format_args!("{}{}", "ddd:", ":e:f"),
+        ];
+        assert_eq!(
+            format!("{}", columns(&rows, "::").gap(1)),
+            "a   b c\nddd e:f"
+        );
+    }
+
+    #[test]
+    fn trailing_cells_and_limit() {
+        let rows = ["a,bb,", "ccc,d,e", "no delimiter here"];
+        assert_eq!(
+            format!("{}", columns(&rows, ",")),
+            "a    bb\nccc  d   e\nno delimiter here"
+        );
+        assert_eq!(
+            format!("{}", Columns::<_, 1>::new(&rows, ",")),
+            "a    bb\nccc  d  e\nno delimiter here"
+        );
+    }
+
+    #[test]
+    fn self_overlapping_delimiter() {
+        let rows = [format_args!("{}{}", "xaa", "abyaab")];
+        assert_eq!(format!("{}", columns(&rows, "aab").gap(1)), "xa y");
+    }
+}
@@ -0,0 +1,120 @@
+//! Write records as comma separated values following [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180).
+//!
+//! Fields are written using their [Display] output. Fields which contain a comma, a double quote,
+//! or a line break are surrounded by double quotes, with any double quotes inside them doubled.
+//! Each field is formatted twice, first to check if it needs quotes and then to write it. Fields
+//! which can only be formatted once, such as a [join](crate::join()), panic on the second pass and
+//! must be wrapped in [reusable](crate::reusable).
+//!
+//! ```rust
+//! use fmttools::csv::csv_rows;
+//!
+//! let records = [["name", "quote"], ["Ada", "\"Hello,\" she said"]];
+//! assert_eq!(
+//!     format!("{}", csv_rows(&records)),
+//!     "name,quote\r\nAda,\"\"\"Hello,\"\" she said\"\r\n"
+//! );
+//! ```
+use crate::escape::{quoted_with, EscapeStyle};
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Write the fields of a single record separated by commas, quoting fields where needed. No line
+/// break is written after the record. The fields are iterated each time the record is formatted,
+/// so they must be [Clone], and each field is formatted twice as described in [crate::csv].
+/// ```rust
+/// use fmttools::csv::csv_row;
+///
+/// assert_eq!("1,2.5,\"a,b\",", format!("{}", csv_row(&["1", "2.5", "a,b", ""])));
+/// ```
+#[inline]
+pub fn csv_row<I>(fields: I) -> CsvRow<I> {
+    CsvRow { fields }
+}
+
+pub struct CsvRow<I> {
+    fields: I,
+}
+
+impl<I> Display for CsvRow<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, field) in self.fields.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char(',')?;
+            }
+
+            let mut check = NeedsQuotes(false);
+            write!(check, "{}", field)?;
+            if check.0 {
+                write!(f, "{}", quoted_with(&field, '"', EscapeStyle::Doubled))?;
+            } else {
+                write!(f, "{}", field)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write an iterator of records, each followed by a CRLF line break. See [csv_row] for how each
+/// record is written. The records are iterated each time they are formatted, so they must be
+/// [Clone].
+/// ```rust
+/// use fmttools::csv::csv_rows;
+///
+/// let records = vec![vec![1, 2], vec![3]];
+/// assert_eq!("1,2\r\n3\r\n", format!("{}", csv_rows(&records)));
+/// ```
+#[inline]
+pub fn csv_rows<R>(records: R) -> CsvRows<R> {
+    CsvRows { records }
+}
+
+pub struct CsvRows<R> {
+    records: R,
+}
+
+impl<R> Display for CsvRows<R>
+where
+    R: IntoIterator + Clone,
+    R::Item: IntoIterator + Clone,
+    <R::Item as IntoIterator>::Item: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for record in self.records.clone() {
+            write!(f, "{}\r\n", csv_row(record))?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks if written text contains any characters which require a field to be quoted.
+struct NeedsQuotes(bool);
+
+impl Write for NeedsQuotes {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 |= s.contains([',', '"', '\r', '\n']);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::csv_row;
+
+    #[test]
+    fn quoting_split_across_writes() {
+        let fields = [
+            format_args!("{}{}", "a", "\"b"),
+            format_args!("{}{}", "line\r", "\n"),
+            format_args!("{}", 'c'),
+        ];
+        assert_eq!("\"a\"\"b\",\"line\r\n\",c", format!("{}", csv_row(&fields)));
+        assert_eq!("", format!("{}", csv_row(Vec::<&str>::new())));
+    }
+}
@@ -0,0 +1,789 @@
+//! Adapters which rewrite the output of [Debug] implementations.
+use crate::style::{colors_enabled, Style, RESET};
+use crate::writer::write_repeated;
+#[cfg(all(feature = "std", feature = "alloc"))]
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+#[cfg(all(feature = "std", feature = "alloc"))]
+use std::collections::{HashMap, HashSet};
+
+/// Re-indent the `{:?}` output of a value in the style of `{:#?}`. This is useful for values whose
+/// [Debug] implementation ignores the alternate flag. No allocation is performed as part of this
+/// operation.
+///
+/// Brackets, braces, and parentheses each start a new indented level, and commas between them end
+/// a line. Empty groups such as `[]` are left on one line. Text within string and character
+/// literals is written unchanged.
+/// ```rust
+/// use fmttools::debug::pretty_debug;
+///
+/// #[derive(Debug)]
+/// struct Point {
+///     x: i32,
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let point = Point { x: 1, tags: vec!["a, b", "{c}"] };
+/// let expected = [
+///     "Point {",
+///     "    x: 1,",
+///     "    tags: [",
+///     "        \"a, b\",",
+///     "        \"{c}\",",
+///     "    ],",
+///     "}",
+/// ];
+/// assert_eq!(expected.join("\n"), format!("{}", pretty_debug(&point)));
+/// ```
+#[inline]
+pub fn pretty_debug<T>(value: T) -> PrettyDebug<T> {
+    PrettyDebug { value }
+}
+
+pub struct PrettyDebug<T> {
+    value: T,
+}
+
+impl<T: Debug> PrettyDebug<T> {
+    #[inline]
+    fn fmt_impl(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = PrettyWriter {
+            dst: f,
+            tokens: Tokenizer::default(),
+            depth: 0,
+            spaces: 0,
+            line_break: false,
+            open: false,
+        };
+        write!(writer, "{:?}", self.value)
+    }
+}
+
+impl<T: Debug> Debug for PrettyDebug<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+impl<T: Debug> Display for PrettyDebug<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+/// Collapse the `{:#?}` output of a value onto a single line. Line breaks and the indentation after
+/// them are replaced with a single space, or removed next to brackets and parentheses, and the
+/// trailing commas `{:#?}` writes before closing brackets are dropped. No allocation is performed as
+/// part of this operation.
+///
+/// For the standard library's [Debug] helpers this gives the same output as `{:?}`, but it also
+/// works on hand-written implementations which only write multi-line output.
+/// ```rust
+/// use fmttools::debug::compact_debug;
+///
+/// #[derive(Debug)]
+/// struct Point {
+///     x: i32,
+///     tags: Vec<&'static str>,
+/// }
+///
+/// let point = Point { x: 1, tags: vec!["a", "b"] };
+/// assert_eq!(r#"Point { x: 1, tags: ["a", "b"] }"#, format!("{}", compact_debug(&point)));
+/// assert_eq!("first second", format!("{}", compact_debug(format_args!("first\n    second\n"))));
+/// ```
+#[inline]
+pub fn compact_debug<T>(value: T) -> CompactDebug<T> {
+    CompactDebug { value }
+}
+
+pub struct CompactDebug<T> {
+    value: T,
+}
+
+impl<T: Debug> CompactDebug<T> {
+    #[inline]
+    fn fmt_impl(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = CompactWriter {
+            dst: f,
+            tokens: Tokenizer::default(),
+            line_break: false,
+            comma: false,
+            after_open: false,
+            previous: [' '; 2],
+            spaced: 0,
+        };
+        write!(writer, "{:#?}", self.value)?;
+
+        if writer.comma {
+            writer.dst.write_char(',')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for CompactDebug<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+impl<T: Debug> Display for CompactDebug<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+/// Color the `{:?}` output of a value for display in a terminal. String and character literals,
+/// numbers, identifiers, and punctuation are each written with their own [Style], which can be
+/// changed using the builder methods. No allocation is performed as part of this operation.
+///
+/// If colors are disabled (see [colors_enabled]), the output is written without any styling.
+/// ```rust
+/// use fmttools::debug::highlight_debug;
+/// use fmttools::style::set_colors_enabled;
+///
+/// set_colors_enabled(true);
+/// assert_eq!(
+///     "\x1b[34mSome\x1b[0m\x1b[2m(\x1b[0m\x1b[32m\"a\"\x1b[0m\x1b[2m)\x1b[0m",
+///     format!("{}", highlight_debug(Some("a")))
+/// );
+///
+/// set_colors_enabled(false);
+/// assert_eq!("Some(\"a\")", format!("{}", highlight_debug(Some("a"))));
+/// ```
+#[inline]
+pub fn highlight_debug<T>(value: T) -> HighlightDebug<T> {
+    highlight_debug_if(colors_enabled(), value)
+}
+
+/// Color the `{:?}` output of a value only if `enabled` is true. See [highlight_debug].
+/// ```rust
+/// use fmttools::debug::highlight_debug_if;
+/// use fmttools::style::Style;
+///
+/// let value = (Some(12), "ok");
+/// let highlighted = highlight_debug_if(true, value)
+///     .identifiers(Style::new().bold())
+///     .punctuation(Style::new());
+/// assert_eq!(
+///     "(\x1b[1mSome\x1b[0m(\x1b[33m12\x1b[0m), \x1b[32m\"ok\"\x1b[0m)",
+///     format!("{}", highlighted)
+/// );
+/// assert_eq!("(Some(12), \"ok\")", format!("{}", highlight_debug_if(false, value)));
+/// ```
+#[inline]
+pub const fn highlight_debug_if<T>(enabled: bool, value: T) -> HighlightDebug<T> {
+    HighlightDebug {
+        value,
+        enabled,
+        styles: [
+            Style::new().green(),
+            Style::new().yellow(),
+            Style::new().blue(),
+            Style::new().dimmed(),
+        ],
+    }
+}
+
+pub struct HighlightDebug<T> {
+    value: T,
+    enabled: bool,
+    /// Styles indexed by [Highlight]
+    styles: [Style; 4],
+}
+
+impl<T> HighlightDebug<T> {
+    /// Set the style of string and character literals, including their quotes. Defaults to green.
+    #[inline]
+    pub fn strings(mut self, style: Style) -> Self {
+        self.styles[Highlight::String as usize] = style;
+        self
+    }
+
+    /// Set the style of numbers. Defaults to yellow.
+    #[inline]
+    pub fn numbers(mut self, style: Style) -> Self {
+        self.styles[Highlight::Number as usize] = style;
+        self
+    }
+
+    /// Set the style of identifiers, such as type names, field names, and enum variants. Defaults
+    /// to blue.
+    #[inline]
+    pub fn identifiers(mut self, style: Style) -> Self {
+        self.styles[Highlight::Identifier as usize] = style;
+        self
+    }
+
+    /// Set the style of punctuation, such as brackets, commas, and colons. Defaults to dimmed.
+    #[inline]
+    pub fn punctuation(mut self, style: Style) -> Self {
+        self.styles[Highlight::Punctuation as usize] = style;
+        self
+    }
+}
+
+impl<T: Debug> HighlightDebug<T> {
+    #[inline]
+    fn fmt_impl(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.enabled {
+            return write!(f, "{:?}", self.value);
+        }
+
+        let mut writer = HighlightWriter {
+            dst: f,
+            styles: &self.styles,
+            tokens: Tokenizer::default(),
+            current: None,
+            previous: ' ',
+        };
+        write!(writer, "{:?}", self.value)?;
+        writer.set_highlight(None)
+    }
+}
+
+impl<T: Debug> Debug for HighlightDebug<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+impl<T: Debug> Display for HighlightDebug<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+/// Write the [Debug] output of a [HashMap] or [HashSet] with its entries sorted by key, so the
+/// output does not depend on the hash iteration order. The output uses the same syntax as the
+/// collection's own [Debug] implementation, including for `{:#?}`. This requires allocating a
+/// buffer to hold references to the entries.
+/// ```rust
+/// use std::collections::{HashMap, HashSet};
+/// use fmttools::debug::sorted_debug;
+///
+/// let ports: HashMap<_, _> = [("https", 443), ("http", 80), ("ssh", 22)].iter().copied().collect();
+/// assert_eq!(
+///     r#"{"http": 80, "https": 443, "ssh": 22}"#,
+///     format!("{:?}", sorted_debug(&ports))
+/// );
+///
+/// let primes: HashSet<_> = [7, 2, 5, 3].iter().copied().collect();
+/// assert_eq!("{2, 3, 5, 7}", format!("{:?}", sorted_debug(&primes)));
+/// ```
+#[cfg(all(feature = "std", feature = "alloc"))]
+#[inline]
+pub fn sorted_debug<C>(collection: C) -> SortedDebug<C> {
+    SortedDebug { collection }
+}
+
+#[cfg(all(feature = "std", feature = "alloc"))]
+pub struct SortedDebug<C> {
+    collection: C,
+}
+
+#[cfg(all(feature = "std", feature = "alloc"))]
+impl<K: Ord + Debug, V: Debug, S> Debug for SortedDebug<&HashMap<K, V, S>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<(&K, &V)> = self.collection.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "alloc"))]
+impl<T: Ord + Debug, S> Debug for SortedDebug<&HashSet<T, S>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<&T> = self.collection.iter().collect();
+        entries.sort_unstable();
+        f.debug_set().entries(entries).finish()
+    }
+}
+
+#[cfg(all(feature = "std", feature = "alloc"))]
+impl<C> Display for SortedDebug<C>
+where
+    Self: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+/// The role of a character in `{:?}` output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Token {
+    Open,
+    Close,
+    Comma,
+    Space,
+    /// Part of a string or character literal, including its quotes
+    Literal,
+    Other,
+}
+
+/// Classifies the characters of `{:?}` output, tracking string and character literals across
+/// writes.
+#[derive(Default)]
+pub(crate) struct Tokenizer {
+    quote: Option<char>,
+    escaped: bool,
+    char_literal: CharLiteral,
+    /// If the last character outside of a literal was part of a word
+    after_word: bool,
+}
+
+/// The position within a character literal such as `'a'` or `'\u{1f600}'`.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+enum CharLiteral {
+    /// After the opening quote
+    #[default]
+    Start,
+    /// After a backslash
+    Escape,
+    /// Within a `\u{...}` escape
+    Unicode,
+    /// After the character, where only the closing quote may follow
+    End,
+}
+
+impl Tokenizer {
+    /// Returns true if the last character was part of a string or character literal, other than
+    /// its closing quote.
+    #[inline]
+    pub(crate) fn in_literal(&self) -> bool {
+        self.quote.is_some()
+    }
+
+    pub(crate) fn next(&mut self, c: char) -> Token {
+        match self.quote {
+            Some('\'') => {
+                if self.next_char_literal(c) {
+                    return Token::Literal;
+                }
+
+                // Not a character literal after all (ex: `'tis`), so classify the character as usual
+                self.quote = None;
+            }
+            Some(quote) => {
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == '\\' {
+                    self.escaped = true;
+                } else if c == quote {
+                    self.quote = None;
+                }
+                return Token::Literal;
+            }
+            None => {}
+        }
+
+        let after_word = self.after_word;
+        self.after_word = c.is_alphanumeric() || c == '_';
+        match c {
+            '(' | '[' | '{' => Token::Open,
+            ')' | ']' | '}' => Token::Close,
+            ',' => Token::Comma,
+            ' ' => Token::Space,
+            '"' => {
+                self.quote = Some(c);
+                Token::Literal
+            }
+            // An apostrophe within a word (ex: `don't`) does not start a character literal
+            '\'' if !after_word => {
+                self.quote = Some(c);
+                self.char_literal = CharLiteral::Start;
+                Token::Literal
+            }
+            _ => Token::Other,
+        }
+    }
+
+    /// Advance through a character literal, which holds at most one (possibly escaped) character.
+    /// Returns false if the character can not be part of the literal.
+    fn next_char_literal(&mut self, c: char) -> bool {
+        self.char_literal = match (self.char_literal, c) {
+            (CharLiteral::Start, '\\') => CharLiteral::Escape,
+            (CharLiteral::Start, _) => CharLiteral::End,
+            (CharLiteral::Escape, 'u') => CharLiteral::Unicode,
+            (CharLiteral::Escape, _) => CharLiteral::End,
+            (CharLiteral::Unicode, '}') => CharLiteral::End,
+            (CharLiteral::Unicode, c) if c == '{' || c.is_ascii_hexdigit() => CharLiteral::Unicode,
+            (CharLiteral::End, '\'') => {
+                self.quote = None;
+                return true;
+            }
+            (CharLiteral::Unicode, _) | (CharLiteral::End, _) => return false,
+        };
+        true
+    }
+}
+
+struct PrettyWriter<W> {
+    dst: W,
+    tokens: Tokenizer,
+    depth: usize,
+    /// Spaces which have been held back in case they come before a closing bracket
+    spaces: usize,
+    /// If a line break is needed before the next character
+    line_break: bool,
+    /// If the last character was an opening bracket
+    open: bool,
+}
+
+impl<W: Write> PrettyWriter<W> {
+    fn new_line(&mut self, depth: usize) -> fmt::Result {
+        self.dst.write_char('\n')?;
+        for _ in 0..depth {
+            self.dst.write_str("    ")?;
+        }
+        Ok(())
+    }
+
+    /// Write anything held back before a character which starts or continues an item.
+    fn flush_pending(&mut self) -> fmt::Result {
+        if self.line_break || self.open {
+            self.line_break = false;
+            self.open = false;
+            self.spaces = 0;
+            return self.new_line(self.depth);
+        }
+
+        write_repeated(&mut self.dst, ' ', self.spaces)?;
+        self.spaces = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for PrettyWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            let token = self.tokens.next(c);
+            let pending = self.spaces > 0 || self.line_break || self.open;
+            let plain = matches!(token, Token::Literal | Token::Other)
+                || (self.depth == 0 && token != Token::Open);
+            if plain && !pending {
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            flushed = index + c.len_utf8();
+
+            match token {
+                _ if plain => {
+                    self.flush_pending()?;
+                    self.dst.write_char(c)?;
+                }
+                Token::Open => {
+                    self.flush_pending()?;
+                    self.dst.write_char(c)?;
+                    self.depth += 1;
+                    self.open = true;
+                }
+                Token::Close => {
+                    self.depth -= 1;
+                    self.spaces = 0;
+                    if !self.open {
+                        if !self.line_break {
+                            self.dst.write_char(',')?;
+                        }
+                        self.new_line(self.depth)?;
+                    }
+                    self.open = false;
+                    self.line_break = false;
+                    self.dst.write_char(c)?;
+                }
+                Token::Comma => {
+                    self.spaces = 0;
+                    self.open = false;
+                    self.dst.write_char(',')?;
+                    self.line_break = true;
+                }
+                Token::Space => self.spaces += 1,
+                Token::Literal | Token::Other => unreachable!(),
+            }
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+struct CompactWriter<W> {
+    dst: W,
+    tokens: Tokenizer,
+    /// If a line break has been skipped since the last character
+    line_break: bool,
+    /// If a comma has been held back in case it comes before a closing bracket
+    comma: bool,
+    /// If the last character written was an opening bracket
+    after_open: bool,
+    /// The last two characters of the input, most recent first
+    previous: [char; 2],
+    /// A stack of bits recording which open brackets are written with spaces inside, as with
+    /// `Name { .. }`. The least significant bit is the innermost bracket.
+    spaced: u128,
+}
+
+impl<W> CompactWriter<W> {
+    /// Update the stack of open brackets after a character is read.
+    fn track_brackets(&mut self, token: Token, c: char) {
+        let [last, before_last] = self.previous;
+        self.previous = [c, last];
+
+        match token {
+            Token::Open => {
+                let named = last == ' ' && (before_last.is_alphanumeric() || before_last == '_');
+                self.spaced = self.spaced << 1 | u128::from(c == '{' && named);
+            }
+            Token::Close => self.spaced >>= 1,
+            _ => {}
+        }
+    }
+}
+
+impl<W: Write> Write for CompactWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            let token = self.tokens.next(c);
+            let line_break = token != Token::Literal && c == '\n';
+            if !self.line_break && !self.comma && token != Token::Comma && !line_break {
+                self.after_open = token == Token::Open;
+                self.track_brackets(token, c);
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            flushed = index + c.len_utf8();
+
+            if line_break {
+                self.line_break = true;
+                continue;
+            } else if self.line_break && (token == Token::Space || c == '\t') {
+                continue;
+            }
+
+            let closing = token == Token::Close;
+            if self.comma && !(self.line_break && closing) {
+                self.dst.write_char(',')?;
+            }
+            self.comma = false;
+
+            if self.line_break {
+                // Only brackets written as `Name { .. }` keep a space on the inside
+                let inside = self.after_open || closing;
+                if !inside || self.spaced & 1 == 1 {
+                    self.dst.write_char(' ')?;
+                }
+                self.line_break = false;
+            }
+
+            if token == Token::Comma {
+                self.comma = true;
+            } else {
+                self.dst.write_char(c)?;
+            }
+            self.after_open = token == Token::Open;
+            self.track_brackets(token, c);
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+/// The kinds of text colored by [HighlightDebug].
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Highlight {
+    String,
+    Number,
+    Identifier,
+    Punctuation,
+}
+
+struct HighlightWriter<'a, W> {
+    dst: W,
+    styles: &'a [Style; 4],
+    tokens: Tokenizer,
+    /// The highlight of the last character written, or `None` for whitespace
+    current: Option<Highlight>,
+    previous: char,
+}
+
+impl<W: Write> HighlightWriter<'_, W> {
+    /// Decide the highlight of the next character.
+    fn classify(&mut self, c: char) -> Option<Highlight> {
+        let previous = self.previous;
+        self.previous = c;
+
+        if self.tokens.next(c) == Token::Literal {
+            return Some(Highlight::String);
+        }
+
+        let word = c.is_alphanumeric() || c == '_';
+        match self.current {
+            Some(Highlight::Identifier) if word => Some(Highlight::Identifier),
+            Some(Highlight::Number) if word => Some(Highlight::Number),
+            Some(Highlight::Number) if c == '.' && previous.is_ascii_digit() => {
+                Some(Highlight::Number)
+            }
+            _ if c.is_ascii_digit() => Some(Highlight::Number),
+            _ if word => Some(Highlight::Identifier),
+            _ if c.is_whitespace() => None,
+            _ => Some(Highlight::Punctuation),
+        }
+    }
+
+    fn set_highlight(&mut self, highlight: Option<Highlight>) -> fmt::Result {
+        if let Some(current) = self.current {
+            if !self.styles[current as usize].is_plain() {
+                self.dst.write_str(RESET)?;
+            }
+        }
+
+        if let Some(highlight) = highlight {
+            self.styles[highlight as usize].write_prefix(&mut self.dst)?;
+        }
+        self.current = highlight;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for HighlightWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            let highlight = self.classify(c);
+            if highlight != self.current {
+                self.dst.write_str(&s[flushed..index])?;
+                flushed = index;
+                self.set_highlight(highlight)?;
+            }
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compact_debug, highlight_debug_if, pretty_debug};
+    use crate::fmt_fn::fmt_fn;
+    use crate::style::Style;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[derive(Debug)]
+    struct Unit;
+
+    #[test]
+    fn matches_alternate_debug() {
+        let line = fmt_fn(|f| {
+            f.debug_struct("Line")
+                .field("from", &(-1, Some('\'')))
+                .field("label", &"a\\\"}")
+                .field("empty", &BTreeSet::<u8>::new())
+                .finish()
+        });
+
+        let mut map = BTreeMap::new();
+        map.insert("k", vec![(Unit, ())]);
+        map.insert("empty", Vec::new());
+
+        assert_eq!(format!("{:#?}", map), format!("{}", pretty_debug(&map)));
+        assert_eq!(format!("{:#?}", line), format!("{}", pretty_debug(&line)));
+        assert_eq!("Unit", format!("{:?}", pretty_debug(Unit)));
+        assert_eq!("a, b)", format!("{}", pretty_debug(format_args!("a, b)"))));
+    }
+
+    #[test]
+    fn apostrophes_outside_char_literals() {
+        let value = format_args!("E {{ msg: don't, x: [1, 2] }}");
+        let expected = "E {\n    msg: don't,\n    x: [\n        1,\n        2,\n    ],\n}";
+        assert_eq!(expected, format!("{}", pretty_debug(value)));
+
+        let value = format_args!("E {{ msg: 'tis, x: ['\\u{{1f600}}', '\\''] }}");
+        let expected = "E { msg: 'tis, x: ['\\u{1f600}', '\\''] }";
+        assert_eq!(expected, format!("{}", compact_debug(pretty_debug(value))));
+    }
+
+    #[test]
+    fn inverse_of_pretty_debug() {
+        let mut map = BTreeMap::new();
+        map.insert("k\n", vec![(Unit, Some('\''))]);
+        map.insert("empty", Vec::new());
+
+        let point = fmt_fn(|f| {
+            f.debug_struct("Point")
+                .field("x", &map)
+                .field("y", &[Unit])
+                .finish()
+        });
+
+        assert_eq!(format!("{:?}", map), format!("{}", compact_debug(&map)));
+        assert_eq!(format!("{:?}", point), format!("{}", compact_debug(&point)));
+        assert_eq!(
+            format!("{:?}", map),
+            format!("{}", compact_debug(pretty_debug(&map)))
+        );
+        assert_eq!(
+            "[a, b,",
+            format!("{:?}", compact_debug(format_args!("[a,\n b,")))
+        );
+        assert_eq!(
+            "a (b)",
+            format!("{}", compact_debug(format_args!("a\n(\n\tb\n)\n")))
+        );
+    }
+
+    #[test]
+    fn highlight_classes() {
+        let value = (-1.5, '"', "a\"b", [0x1f; 0], Unit);
+        let out = highlight_debug_if(true, value)
+            .strings(Style::new().red())
+            .numbers(Style::new().green())
+            .identifiers(Style::new().blue())
+            .punctuation(Style::new())
+            .to_string();
+        assert_eq!(
+            concat!(
+                "(-\x1b[32m1.5\x1b[0m, \x1b[31m'\"'\x1b[0m, \x1b[31m\"a\\\"b\"\x1b[0m, [], ",
+                "\x1b[34mUnit\x1b[0m)"
+            ),
+            out
+        );
+    }
+
+    #[cfg(all(feature = "std", feature = "alloc"))]
+    #[test]
+    fn sorted_matches_btree_debug() {
+        use super::sorted_debug;
+        use std::collections::{BTreeSet, HashMap, HashSet};
+
+        let map: HashMap<_, _> = (0..20).map(|x| (x * 7 % 20, Some(x))).collect();
+        let ordered: BTreeMap<_, _> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        assert_eq!(
+            format!("{:#?}", ordered),
+            format!("{:#?}", sorted_debug(&map))
+        );
+        assert_eq!(format!("{:?}", ordered), sorted_debug(&map).to_string());
+
+        let set: HashSet<_> = ["b", "c", "a"].iter().copied().collect();
+        let ordered: BTreeSet<_> = set.iter().copied().collect();
+        assert_eq!(
+            format!("{:#?}", ordered),
+            format!("{:#?}", sorted_debug(&set))
+        );
+        assert_eq!("{}", format!("{:?}", sorted_debug(&HashSet::<u8>::new())));
+    }
+}
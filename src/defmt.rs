@@ -0,0 +1,85 @@
+//! Log adapters over [defmt](::defmt) on embedded targets. Requires the `defmt` feature.
+//!
+//! Adapters implement [Format](::defmt::Format) by writing their [Display] output through
+//! [Display2Format](::defmt::Display2Format), so they are formatted on the device and sent as a
+//! string. Targets without `std` should disable the default features:
+//! ```toml
+//! fmttools = { version = "0.2", default-features = false, features = ["defmt"] }
+//! ```
+//! ```rust,no_run
+//! use fmttools::join;
+//!
+//! fn log_readings(readings: &[u16]) {
+//!     defmt::info!("readings: {}", join(readings, ", "));
+//! }
+//! ```
+use ::defmt::{Display2Format, Format, Formatter};
+use core::fmt::Display;
+
+macro_rules! impl_format {
+    ($([$($generics:tt)*] $t:ty),* $(,)?) => {
+        $(impl<$($generics)*> Format for $t
+        where
+            Self: Display,
+        {
+            #[inline]
+            fn format(&self, f: Formatter<'_>) {
+                ::defmt::write!(f, "{}", Display2Format(self))
+            }
+        })*
+    };
+}
+
+impl_format!(
+    [F] crate::fmt_fn::FmtFn<F>,
+    [I] crate::join::Join<'_, I>,
+    [I, F, S] crate::join::JoinFmt<I, F, S>,
+    [I, F, S] crate::join::JoinFmtAll<I, F, S>,
+    [I] crate::join::JoinIntegers<'_, I>,
+    [T] crate::join::Reusable<T>,
+    [T, P] crate::replace::Replace<'_, T, P>,
+);
+
+impl_format!(
+    [] crate::num::ByteSize,
+    [T] crate::num::Currency<'_, T>,
+    [] crate::num::Engineering,
+    [T] crate::num::FixedPoint<T>,
+    [T] crate::num::FloatShortest<T>,
+    [T] crate::num::Grouped<T>,
+    [] crate::num::GroupedFloat,
+    [T] crate::num::Ordinal<T>,
+    [] crate::num::PerSecond,
+    [] crate::num::Percent,
+    [T] crate::num::Radix<'_, T>,
+    [] crate::num::Rate,
+    [T] crate::num::Roman<T>,
+    [T] crate::num::RustLiteral<'_, T>,
+    [] crate::num::Si<'_>,
+    [] crate::num::SigFigs,
+    [T] crate::num::TrimZeros<T>,
+);
+
+#[cfg(feature = "locale")]
+impl_format!([I] crate::locale::JoinLocale<'_, I>);
+
+#[cfg(test)]
+mod tests {
+    use crate::fmt_fn::fmt_fn;
+    use crate::join::{join, join_integers};
+    use crate::num::ordinal;
+    use crate::replace::replace;
+    use ::defmt::Format;
+
+    fn assert_format<T: Format>(_: &T) {}
+
+    #[test]
+    pub fn adapters_implement_format() {
+        let values = [1, 2, 3];
+        assert_format(&join(&values, ", "));
+        assert_format(&join_integers(values, ", "));
+        assert_format(&replace("abc", "b", "x"));
+        assert_format(&fmt_fn(|f| f.write_str("abc")));
+        assert_format(&ordinal(3));
+    }
+}
@@ -0,0 +1,76 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+
+/// One of two values, which may have different types. Formatting is delegated to whichever value
+/// is present, including any format string arguments. This allows the branches of a conditional
+/// to produce different adapter types without boxing.
+/// ```rust
+/// use fmttools::{join, Either};
+///
+/// fn list(values: &[u32]) -> Either<&'static str, impl std::fmt::Display + '_> {
+///     if values.is_empty() {
+///         Either::Left("(empty)")
+///     } else {
+///         Either::Right(join(values, ", "))
+///     }
+/// }
+///
+/// assert_eq!("(empty)", list(&[]).to_string());
+/// assert_eq!("1, 2", list(&[1, 2]).to_string());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// Select `a` if `cond` is true, otherwise `b`. See [Either].
+/// ```rust
+/// use fmttools::either;
+///
+/// let count = 3;
+/// assert_eq!("found 3", format!("found {}", either(count == 0, "nothing", count)));
+/// assert_eq!("found nothing", format!("found {}", either(0 == 0, "nothing", count)));
+/// ```
+#[inline]
+pub fn either<L, R>(cond: bool, a: L, b: R) -> Either<L, R> {
+    if cond {
+        Either::Left(a)
+    } else {
+        Either::Right(b)
+    }
+}
+
+impl<L: Display, R: Display> Display for Either<L, R> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Either::Left(value) => value.fmt(f),
+            Either::Right(value) => value.fmt(f),
+        }
+    }
+}
+
+impl<L: Debug, R: Debug> Debug for Either<L, R> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Either::Left(value) => value.fmt(f),
+            Either::Right(value) => value.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::either;
+
+    #[test]
+    fn delegates_format_arguments() {
+        assert_eq!(
+            "  1.50",
+            format!("{:>6.2}", either::<_, &str>(true, 1.5, "x"))
+        );
+        assert_eq!("\"x\"", format!("{:?}", either::<f64, _>(false, 1.5, "x")));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::escape::shell_quote;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Write key-value pairs as `KEY=value` lines which can be sourced by a POSIX shell. Values are
+/// quoted using [shell_quote], while keys are written unchanged. Lines are separated by `\n`,
+/// without a trailing line break. The pairs are iterated each time they are formatted, so they
+/// must be [Clone].
+/// ```rust
+/// use fmttools::env::env_block;
+///
+/// let vars = [("RUST_LOG", "debug"), ("GREETING", "hello world")];
+/// assert_eq!(
+///     "RUST_LOG=debug\nGREETING='hello world'",
+///     format!("{}", env_block(vars.iter().copied()))
+/// );
+/// assert_eq!(
+///     "export RUST_LOG=debug\nexport GREETING='hello world'",
+///     format!("{}", env_block(vars.iter().copied()).export(true))
+/// );
+/// ```
+#[inline]
+pub fn env_block<I>(pairs: I) -> EnvBlock<I> {
+    EnvBlock {
+        pairs,
+        export: false,
+    }
+}
+
+pub struct EnvBlock<I> {
+    pairs: I,
+    export: bool,
+}
+
+impl<I> EnvBlock<I> {
+    /// If true, start each line with `export ` so the variables are passed to child processes.
+    #[inline]
+    pub fn export(self, export: bool) -> Self {
+        EnvBlock { export, ..self }
+    }
+}
+
+impl<I, K, V> Display for EnvBlock<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, (key, value)) in self.pairs.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+            if self.export {
+                f.write_str("export ")?;
+            }
+            write!(f, "{}={}", key, shell_quote(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::env_block;
+
+    #[test]
+    fn empty_and_multi_line_values() {
+        let vars = [("EMPTY", ""), ("LINES", "a\nb")];
+        assert_eq!(
+            "EMPTY=''\nLINES='a\nb'",
+            format!("{}", env_block(vars.iter().copied()))
+        );
+    }
+}
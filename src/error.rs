@@ -0,0 +1,292 @@
+//! Formatting for chains of [std::error::Error] sources.
+use crate::fmt_fn::fmt_fn;
+use crate::map::Continuation;
+use crate::writer::{fmt_eq, measure};
+use core::error::Error;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Write an error followed by each of its [sources](Error::source), separated by `": "`. This is
+/// the loop most binaries write by hand when reporting an error to the user.
+/// ```rust
+/// use std::fmt;
+/// use std::error::Error;
+/// use fmttools::error::error_chain;
+///
+/// #[derive(Debug)]
+/// struct ConfigError(std::io::Error);
+///
+/// impl fmt::Display for ConfigError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("failed to load config")
+///     }
+/// }
+///
+/// impl Error for ConfigError {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// let err = ConfigError(std::io::Error::new(std::io::ErrorKind::NotFound, "no such file"));
+/// assert_eq!("failed to load config: no such file", format!("{}", error_chain(&err)));
+/// assert_eq!("failed to load config\n  no such file", format!("{}", error_chain(&err).separator("\n  ")));
+/// ```
+#[inline]
+pub fn error_chain<E: Error + ?Sized>(error: &E) -> ErrorChain<'_, E> {
+    ErrorChain {
+        error,
+        separator: ": ",
+        dedup: false,
+    }
+}
+
+pub struct ErrorChain<'a, E: ?Sized> {
+    error: &'a E,
+    separator: &'a str,
+    dedup: bool,
+}
+
+impl<'a, E: ?Sized> ErrorChain<'a, E> {
+    /// Set the separator written between errors. Defaults to `": "`.
+    #[inline]
+    pub fn separator(self, separator: &'a str) -> Self {
+        ErrorChain { separator, ..self }
+    }
+
+    /// Skip a source whose message is the same as, or already included at the end of, the
+    /// message before it. This is common for errors which include their source in their own
+    /// message. Defaults to false.
+    /// ```rust
+    /// use std::fmt;
+    /// use std::error::Error;
+    /// use fmttools::error::error_chain;
+    ///
+    /// #[derive(Debug)]
+    /// struct WriteError(std::io::Error);
+    ///
+    /// impl fmt::Display for WriteError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "write failed: {}", self.0)
+    ///     }
+    /// }
+    ///
+    /// impl Error for WriteError {
+    ///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+    ///         Some(&self.0)
+    ///     }
+    /// }
+    ///
+    /// let err = WriteError(std::io::Error::new(std::io::ErrorKind::Other, "disk full"));
+    /// assert_eq!("write failed: disk full: disk full", format!("{}", error_chain(&err)));
+    /// assert_eq!("write failed: disk full", format!("{}", error_chain(&err).dedup(true)));
+    /// ```
+    #[inline]
+    pub fn dedup(self, dedup: bool) -> Self {
+        ErrorChain { dedup, ..self }
+    }
+}
+
+impl<E: Error + ?Sized> Display for ErrorChain<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+
+        let mut previous: &dyn Display = &self.error;
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            if !self.dedup || !ends_with(previous, error) {
+                f.write_str(self.separator)?;
+                write!(f, "{}", error)?;
+            }
+
+            previous = error;
+            source = error.source();
+        }
+
+        Ok(())
+    }
+}
+
+/// Write an error and its [sources](Error::source) as a multi-line report, in the style of
+/// [anyhow](https://docs.rs/anyhow). Each source is numbered on its own line, and the lines of
+/// multi-line messages are indented to line up with the first.
+/// ```rust
+/// use std::fmt;
+/// use std::error::Error;
+/// use fmttools::error::error_report;
+///
+/// #[derive(Debug)]
+/// struct Context(&'static str, Option<Box<Context>>);
+///
+/// impl fmt::Display for Context {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str(self.0)
+///     }
+/// }
+///
+/// impl Error for Context {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         self.1.as_deref().map(|x| x as _)
+///     }
+/// }
+///
+/// let root = Context("permission denied\n(os error 13)", None);
+/// let middle = Context("failed to open cache.db", Some(Box::new(root)));
+/// let err = Context("failed to start server", Some(Box::new(middle)));
+///
+/// let expected = [
+///     "Error: failed to start server",
+///     "Caused by:",
+///     "  0: failed to open cache.db",
+///     "  1: permission denied",
+///     "     (os error 13)",
+/// ];
+/// assert_eq!(expected.join("\n"), format!("{}", error_report(&err)));
+/// ```
+#[inline]
+pub fn error_report<E: Error + ?Sized>(error: &E) -> ErrorReport<'_, E> {
+    ErrorReport { error }
+}
+
+pub struct ErrorReport<'a, E: ?Sized> {
+    error: &'a E,
+}
+
+impl<E: Error + ?Sized> Display for ErrorReport<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Error: ")?;
+        let mut out = Continuation {
+            dst: &mut *f,
+            indent: "Error: ".len(),
+            pending: 0,
+        };
+        write!(out, "{}", self.error)?;
+
+        let mut causes = 0;
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            causes += 1;
+            source = error.source();
+        }
+
+        if causes == 0 {
+            return Ok(());
+        }
+
+        f.write_str("\nCaused by:")?;
+        let number_width = measure(causes - 1);
+        let mut source = self.error.source();
+        let mut index = 0usize;
+        while let Some(error) = source {
+            write!(f, "\n  {:>width$}: ", index, width = number_width)?;
+
+            let mut out = Continuation {
+                dst: &mut *f,
+                indent: number_width + 4,
+                pending: 0,
+            };
+            write!(out, "{}", error)?;
+
+            index += 1;
+            source = error.source();
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true if the formatted output of `value` ends with the formatted output of `suffix`.
+fn ends_with(value: &dyn Display, suffix: &dyn Display) -> bool {
+    let (len, suffix_len) = (measure(value), measure(suffix));
+    if suffix_len > len {
+        return false;
+    }
+
+    let tail = fmt_fn(|f| {
+        let mut writer = SkipWriter {
+            dst: f,
+            skip: len - suffix_len,
+        };
+        write!(writer, "{}", value)
+    });
+    fmt_eq(tail, suffix)
+}
+
+/// Discards a number of bytes before forwarding output. If the skipped bytes end inside of a
+/// character, the rest of that character is also discarded, so the output is then shorter than
+/// expected and can not match.
+struct SkipWriter<W> {
+    dst: W,
+    skip: usize,
+}
+
+impl<W: Write> Write for SkipWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.skip >= s.len() {
+            self.skip -= s.len();
+            return Ok(());
+        }
+
+        let mut start = self.skip;
+        self.skip = 0;
+        while !s.is_char_boundary(start) {
+            start += 1;
+        }
+        self.dst.write_str(&s[start..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ends_with, error_chain, error_report};
+    use std::error::Error;
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct Chain(&'static str, Option<Box<Chain>>);
+
+    impl fmt::Display for Chain {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl Error for Chain {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            self.1.as_deref().map(|x| x as &(dyn Error + 'static))
+        }
+    }
+
+    #[test]
+    fn dedup_repeated_messages() {
+        let root = Chain("b", None);
+        let middle = Chain("a: b", Some(Box::new(root)));
+        let err = Chain("a: b", Some(Box::new(middle)));
+        assert_eq!(
+            "a: b | a: b | b",
+            error_chain(&err).separator(" | ").to_string()
+        );
+        assert_eq!("a: b", error_chain(&err).dedup(true).to_string());
+
+        assert_eq!("lone", error_chain(&Chain("lone", None)).to_string());
+        assert!(ends_with(&"xé", &"é"));
+        assert!(!ends_with(&"éa", &"\u{a9}a"));
+        assert!(!ends_with(&"éa", &"ya"));
+    }
+
+    #[test]
+    fn report_aligns_numbers() {
+        let mut err = Chain("root", None);
+        for _ in 0..11 {
+            err = Chain("cause", Some(Box::new(err)));
+        }
+        let report = error_report(&err).to_string();
+        assert!(report.starts_with("Error: cause\nCaused by:\n   0: cause\n"));
+        assert!(report.ends_with("\n   9: cause\n  10: root"));
+
+        assert_eq!(
+            "Error: a\n       b",
+            error_report(&Chain("a\nb", None)).to_string()
+        );
+    }
+}
@@ -0,0 +1,488 @@
+//! Adapters which escape formatted output so it can be safely embedded in other text, such as
+//! terminal output or line oriented logs.
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Control characters which are allowed through [sanitize] by default.
+pub const DEFAULT_ALLOWED: &[char] = &['\n', '\t'];
+
+/// How control characters are rendered by [sanitize].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Placeholder {
+    /// Use the matching symbol from the Unicode Control Pictures block (ex: `␀` or `␛`). C1 control
+    /// characters have no control picture, so they fall back to [Placeholder::Escape].
+    Symbol,
+    /// Use a Rust style escape sequence (ex: `\x00` or `\x1b`). C1 control characters are written
+    /// as unicode escapes (ex: `\u{9b}`).
+    Escape,
+    /// Replace every control character with the given character.
+    Char(char),
+}
+
+impl Placeholder {
+    pub(crate) fn write<W: Write>(self, out: &mut W, c: char) -> fmt::Result {
+        match (self, c) {
+            (Placeholder::Char(replacement), _) => out.write_char(replacement),
+            (Placeholder::Symbol, '\x00'..='\x1f') => {
+                // Control pictures are laid out in the same order as the C0 control characters
+                let symbol =
+                    char::from_u32(0x2400 + c as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+                out.write_char(symbol)
+            }
+            (Placeholder::Symbol, '\x7f') => out.write_char('\u{2421}'),
+            (_, '\x00'..='\x7f') => write!(out, "\\x{:02x}", c as u32),
+            (_, _) => write!(out, "\\u{{{:x}}}", c as u32),
+        }
+    }
+}
+
+/// Replace C0 and C1 control characters in the formatted output of a value with visible
+/// placeholders. This prevents the value from injecting terminal escape sequences or forged log
+/// entries. Line feeds and tabs are allowed through by default. No allocation is performed as part
+/// of this operation.
+/// ```rust
+/// use fmttools::escape::{sanitize, Placeholder};
+///
+/// let input = "user\x1b[2J\r\nname\t";
+/// assert_eq!("user␛[2J␍\nname\t", format!("{}", sanitize(input)));
+///
+/// let escaped = sanitize(input).placeholder(Placeholder::Escape).allow(&[]);
+/// assert_eq!("user\\x1b[2J\\x0d\\x0aname\\x09", format!("{}", escaped));
+/// ```
+#[inline]
+pub fn sanitize<T>(value: T) -> Sanitize<'static, T> {
+    Sanitize {
+        value,
+        allowed: DEFAULT_ALLOWED,
+        placeholder: Placeholder::Symbol,
+    }
+}
+
+pub struct Sanitize<'a, T> {
+    value: T,
+    allowed: &'a [char],
+    placeholder: Placeholder,
+}
+
+impl<T> Sanitize<'_, T> {
+    /// Set the control characters which are written without modification. This replaces the
+    /// default of [DEFAULT_ALLOWED].
+    #[inline]
+    pub fn allow(self, allowed: &[char]) -> Sanitize<'_, T> {
+        Sanitize {
+            value: self.value,
+            allowed,
+            placeholder: self.placeholder,
+        }
+    }
+
+    /// Set how disallowed control characters are written.
+    #[inline]
+    pub fn placeholder(self, placeholder: Placeholder) -> Self {
+        Sanitize {
+            placeholder,
+            ..self
+        }
+    }
+}
+
+impl<T: Debug> Debug for Sanitize<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = SanitizeWriter {
+            dst: f,
+            allowed: self.allowed,
+            placeholder: self.placeholder,
+        };
+        write!(writer, "{:?}", self.value)
+    }
+}
+
+impl<T: Display> Display for Sanitize<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = SanitizeWriter {
+            dst: f,
+            allowed: self.allowed,
+            placeholder: self.placeholder,
+        };
+        write!(writer, "{}", self.value)
+    }
+}
+
+struct SanitizeWriter<'a, W> {
+    dst: W,
+    allowed: &'a [char],
+    placeholder: Placeholder,
+}
+
+impl<W: Write> Write for SanitizeWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            if !c.is_control() || self.allowed.contains(&c) {
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            self.placeholder.write(&mut self.dst, c)?;
+            flushed = index + c.len_utf8();
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+/// Escape line breaks in the formatted output of a value so it can be embedded in line oriented
+/// formats such as logs. By default, `\n` and `\r` are written as their escape sequences. No
+/// allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::escape::single_line;
+///
+/// let payload = "first line\r\nsecond line\n";
+/// assert_eq!("first line\\r\\nsecond line\\n", format!("{}", single_line(payload)));
+///
+/// // Alternatively, each line break can be replaced. "\r\n" is treated as a single line break.
+/// let joined = single_line(payload).replacement(" | ");
+/// assert_eq!("first line | second line | ", format!("{}", joined));
+/// ```
+#[inline]
+pub fn single_line<T>(value: T) -> SingleLine<'static, T> {
+    SingleLine {
+        value,
+        replacement: None,
+    }
+}
+
+pub struct SingleLine<'a, T> {
+    value: T,
+    replacement: Option<&'a str>,
+}
+
+impl<T> SingleLine<'_, T> {
+    /// Replace each line break with the given string instead of escaping it.
+    #[inline]
+    pub fn replacement(self, replacement: &str) -> SingleLine<'_, T> {
+        SingleLine {
+            value: self.value,
+            replacement: Some(replacement),
+        }
+    }
+}
+
+impl<T: Debug> Debug for SingleLine<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = SingleLineWriter {
+            dst: f,
+            replacement: self.replacement,
+            after_cr: false,
+        };
+        write!(writer, "{:?}", self.value)
+    }
+}
+
+impl<T: Display> Display for SingleLine<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut writer = SingleLineWriter {
+            dst: f,
+            replacement: self.replacement,
+            after_cr: false,
+        };
+        write!(writer, "{}", self.value)
+    }
+}
+
+struct SingleLineWriter<'a, W> {
+    dst: W,
+    replacement: Option<&'a str>,
+    /// If the last character written was `\r`. Used to treat `\r\n` as a single line break when
+    /// using a replacement, even if it is split across writes.
+    after_cr: bool,
+}
+
+impl<W: Write> Write for SingleLineWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, byte) in s.bytes().enumerate() {
+            let after_cr = self.after_cr;
+            self.after_cr = byte == b'\r';
+
+            if byte != b'\n' && byte != b'\r' {
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            flushed = index + 1;
+
+            match self.replacement {
+                Some(_) if byte == b'\n' && after_cr => {}
+                Some(replacement) => self.dst.write_str(replacement)?,
+                None if byte == b'\n' => self.dst.write_str("\\n")?,
+                None => self.dst.write_str("\\r")?,
+            }
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+/// How special characters are escaped by [quoted_with].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EscapeStyle {
+    /// Write a backslash before the quote character and backslashes. Other characters are written
+    /// unchanged.
+    Backslash,
+    /// In addition to [EscapeStyle::Backslash], write line breaks, tabs, and other control
+    /// characters as Rust style escape sequences (ex: `\n` or `\x1b`).
+    Rust,
+    /// Write the quote character twice, as in SQL and CSV. No other characters are escaped.
+    Doubled,
+}
+
+/// Surround the formatted output of a value with double quotes, escaping any double quotes and
+/// backslashes it contains. Unlike `{:?}`, this applies to the [Display] output of any type. No
+/// allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::escape::quoted;
+///
+/// assert_eq!(r#""say \"hi\"""#, format!("{}", quoted(r#"say "hi""#)));
+/// assert_eq!(r#""C:\\temp""#, format!("{}", quoted(r"C:\temp")));
+/// ```
+#[inline]
+pub fn quoted<T>(value: T) -> Quoted<T> {
+    quoted_with(value, '"', EscapeStyle::Backslash)
+}
+
+/// Surround the formatted output of a value with the given quote character, escaping special
+/// characters using the given style. See [quoted].
+/// ```rust
+/// use fmttools::escape::{quoted_with, EscapeStyle};
+///
+/// assert_eq!("'it''s'", format!("{}", quoted_with("it's", '\'', EscapeStyle::Doubled)));
+/// assert_eq!("'a\\tb\\n'", format!("{}", quoted_with("a\tb\n", '\'', EscapeStyle::Rust)));
+/// ```
+#[inline]
+pub fn quoted_with<T>(value: T, quote: char, style: EscapeStyle) -> Quoted<T> {
+    Quoted {
+        value,
+        quote,
+        style,
+    }
+}
+
+pub struct Quoted<T> {
+    value: T,
+    quote: char,
+    style: EscapeStyle,
+}
+
+impl<T: Display> Display for Quoted<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_char(self.quote)?;
+        let mut writer = QuoteWriter {
+            dst: &mut *f,
+            quote: self.quote,
+            style: self.style,
+        };
+        write!(writer, "{}", self.value)?;
+        f.write_char(self.quote)
+    }
+}
+
+struct QuoteWriter<W> {
+    dst: W,
+    quote: char,
+    style: EscapeStyle,
+}
+
+impl<W: Write> QuoteWriter<W> {
+    #[inline]
+    fn needs_escape(&self, c: char) -> bool {
+        match self.style {
+            EscapeStyle::Doubled => c == self.quote,
+            EscapeStyle::Backslash => c == self.quote || c == '\\',
+            EscapeStyle::Rust => c == self.quote || c == '\\' || c.is_control(),
+        }
+    }
+
+    fn write_escaped(&mut self, c: char) -> fmt::Result {
+        match (self.style, c) {
+            (EscapeStyle::Doubled, _) => {
+                self.dst.write_char(c)?;
+                self.dst.write_char(c)
+            }
+            (EscapeStyle::Rust, '\n') => self.dst.write_str("\\n"),
+            (EscapeStyle::Rust, '\r') => self.dst.write_str("\\r"),
+            (EscapeStyle::Rust, '\t') => self.dst.write_str("\\t"),
+            (EscapeStyle::Rust, '\0') => self.dst.write_str("\\0"),
+            (EscapeStyle::Rust, c) if c.is_control() => Placeholder::Escape.write(&mut self.dst, c),
+            (_, c) => {
+                self.dst.write_char('\\')?;
+                self.dst.write_char(c)
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for QuoteWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            if !self.needs_escape(c) {
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            self.write_escaped(c)?;
+            flushed = index + c.len_utf8();
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+/// Quote the formatted output of a value so a POSIX shell reads it as a single word. Output made
+/// up only of ASCII letters, digits, and the characters `_@%+=:,./-` is written unchanged.
+/// Anything else is surrounded by single quotes, with single quotes inside it written as `'\''`.
+/// The value is formatted twice, first to check if it needs quotes and then to write it.
+/// ```rust
+/// use fmttools::escape::shell_quote;
+///
+/// assert_eq!("/usr/bin", format!("{}", shell_quote("/usr/bin")));
+/// assert_eq!(r#"'it'\''s $HOME'"#, format!("{}", shell_quote("it's $HOME")));
+/// assert_eq!("''", format!("{}", shell_quote("")));
+/// ```
+#[inline]
+pub fn shell_quote<T>(value: T) -> ShellQuote<T> {
+    ShellQuote { value }
+}
+
+pub struct ShellQuote<T> {
+    value: T,
+}
+
+impl<T: Display> Display for ShellQuote<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut check = ShellSafe {
+            empty: true,
+            safe: true,
+        };
+        write!(check, "{}", self.value)?;
+        if !check.empty && check.safe {
+            return write!(f, "{}", self.value);
+        }
+
+        f.write_char('\'')?;
+        write!(ShellQuoteWriter { dst: &mut *f }, "{}", self.value)?;
+        f.write_char('\'')
+    }
+}
+
+/// Checks if output can be used as a shell word without quotes.
+struct ShellSafe {
+    empty: bool,
+    safe: bool,
+}
+
+impl Write for ShellSafe {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.empty &= s.is_empty();
+        self.safe &= s
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"_@%+=:,./-".contains(&b));
+        Ok(())
+    }
+}
+
+/// Ends the quoted string around each single quote, writing it as an escaped character.
+struct ShellQuoteWriter<W> {
+    dst: W,
+}
+
+impl<W: Write> Write for ShellQuoteWriter<W> {
+    #[inline]
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        while let Some(index) = s.find('\'') {
+            self.dst.write_str(&s[..index])?;
+            self.dst.write_str(r"'\''")?;
+            s = &s[index + 1..];
+        }
+
+        self.dst.write_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        quoted, quoted_with, sanitize, shell_quote, single_line, EscapeStyle, Placeholder,
+    };
+
+    #[test]
+    fn sanitize_plain_text() {
+        assert_eq!(format!("{}", sanitize("abc\tdef\n")), "abc\tdef\n");
+    }
+
+    #[test]
+    fn sanitize_symbols() {
+        let out = format!("{}", sanitize("\0\x07\x7f\u{9b}"));
+        assert_eq!(out, "␀␇␡\\u{9b}");
+    }
+
+    #[test]
+    fn sanitize_custom() {
+        let out = format!(
+            "{}",
+            sanitize("a\rb\nc\td")
+                .allow(&['\r'])
+                .placeholder(Placeholder::Char('?'))
+        );
+        assert_eq!(out, "a\rb?c?d");
+    }
+
+    #[test]
+    fn sanitize_debug_output() {
+        // Debug output has already escaped the control character, so it passes through unchanged
+        let out = format!("{:?}", sanitize("\x1b"));
+        assert_eq!(out, "\"\\u{1b}\"");
+    }
+
+    #[test]
+    fn single_line_crlf_split_across_writes() {
+        let out = format!(
+            "{}",
+            single_line(format_args!("a\r{}b\r", "\n")).replacement(" ")
+        );
+        assert_eq!(out, "a b ");
+    }
+
+    #[test]
+    fn single_line_debug() {
+        // Debug output of strings has no raw line breaks, so nothing changes
+        assert_eq!(format!("{:?}", single_line("a\nb")), "\"a\\nb\"");
+    }
+
+    #[test]
+    fn quoted_rust_escapes() {
+        let out = format!(
+            "{}",
+            quoted_with("\"a\\\x1b\u{9b}\0", '"', EscapeStyle::Rust)
+        );
+        assert_eq!(out, r#""\"a\\\x1b\u{9b}\0""#);
+        assert_eq!(format!("{}", quoted(format_args!("{}", 1.5))), "\"1.5\"");
+    }
+
+    #[test]
+    fn shell_quote_split_across_writes() {
+        let value = format_args!("{}{}", "a'", "'b");
+        assert_eq!(r#"'a'\'''\''b'"#, format!("{}", shell_quote(value)));
+        assert_eq!("'caf\u{e9}'", format!("{}", shell_quote("caf\u{e9}")));
+    }
+}
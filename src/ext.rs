@@ -0,0 +1,90 @@
+//! An extension trait for composing adapters left to right.
+//!
+//! ```rust
+//! use fmttools::prelude::*;
+//!
+//! let message = "disk full\nretrying in 5s";
+//! assert_eq!(
+//!     "  ERROR: DISK FULL\n  RETRYING",
+//!     format!("{}", format_args!("error: {}", message).uppercase().truncate_chars(25).indent("  "))
+//! );
+//! ```
+use crate::case::{lowercase, uppercase, Lowercase, Uppercase};
+use crate::escape::{quoted, Quoted};
+use crate::gutter::{gutter, Gutter};
+use crate::replace::{replace, Replace};
+use crate::truncate::{truncate_chars, TruncateChars};
+use crate::wrap::{wrap, Wrap};
+use core::fmt::Display;
+
+/// Methods which wrap a value in one of the crate's adapters. Each method takes the value by
+/// value, so call them on a reference to avoid moving an owned value. Inherent methods with the
+/// same name, such as [Wrap::indent], take precedence over the methods of this trait.
+///
+/// This trait is implemented for every [Display] type and is exported through
+/// [crate::prelude].
+pub trait FmtToolsExt: Display + Sized {
+    /// See [crate::replace].
+    #[inline]
+    fn replace_fmt<P>(self, pattern: P, replacement: &str) -> Replace<'_, Self, P> {
+        replace(self, pattern, replacement)
+    }
+
+    /// See [crate::truncate::truncate_chars].
+    #[inline]
+    fn truncate_chars(self, max_chars: usize) -> TruncateChars<'static, Self> {
+        truncate_chars(self, max_chars)
+    }
+
+    /// Write `prefix` at the start of every line. See [crate::gutter].
+    #[inline]
+    fn indent(self, prefix: &str) -> Gutter<'_, Self> {
+        gutter(self, prefix)
+    }
+
+    /// See [crate::case::uppercase].
+    #[inline]
+    fn uppercase(self) -> Uppercase<Self> {
+        uppercase(self)
+    }
+
+    /// See [crate::case::lowercase].
+    #[inline]
+    fn lowercase(self) -> Lowercase<Self> {
+        lowercase(self)
+    }
+
+    /// See [crate::wrap].
+    #[inline]
+    fn wrap(self, width: usize) -> Wrap<'static, Self> {
+        wrap(self, width)
+    }
+
+    /// See [crate::quoted].
+    #[inline]
+    fn quoted(self) -> Quoted<Self> {
+        quoted(self)
+    }
+}
+
+impl<T: Display> FmtToolsExt for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::FmtToolsExt;
+
+    #[test]
+    fn chains_read_left_to_right() {
+        let name = String::from("Ada Lovelace");
+        assert_eq!(
+            "\"ADA L\"",
+            format!("{}", name.as_str().truncate_chars(5).uppercase().quoted())
+        );
+        assert_eq!("> a\n>\n> b", format!("{}", "a\n\nb".indent("> ")));
+        assert_eq!("a\nb", format!("{}", "a b".wrap(1)));
+        assert_eq!(
+            "x_y",
+            format!("{}", "X-Y".lowercase().replace_fmt('-', "_"))
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+
+/// Create a value which is formatted by calling the given closure. Both [Debug] and [Display] call
+/// the same closure, which is passed the [Formatter] along with any format string arguments.
+/// ```rust
+/// use fmttools::fmt_fn;
+///
+/// let point = (3, -1);
+/// let value = fmt_fn(|f| write!(f, "({}, {})", point.0, point.1));
+/// assert_eq!("at (3, -1)", format!("at {}", value));
+/// ```
+#[inline]
+pub const fn fmt_fn<F>(fmt: F) -> FmtFn<F>
+where
+    F: Fn(&mut Formatter<'_>) -> fmt::Result,
+{
+    FmtFn { fmt }
+}
+
+#[derive(Copy, Clone)]
+pub struct FmtFn<F> {
+    fmt: F,
+}
+
+impl<F> Display for FmtFn<F>
+where
+    F: Fn(&mut Formatter<'_>) -> fmt::Result,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.fmt)(f)
+    }
+}
+
+impl<F> Debug for FmtFn<F>
+where
+    F: Fn(&mut Formatter<'_>) -> fmt::Result,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (self.fmt)(f)
+    }
+}
+
+/// Create a value which implements [Debug] and [Display] by formatting its arguments with the
+/// given format string, in the same way as [format_args]. Unlike [format_args], the arguments are
+/// evaluated once and moved into the returned value, so it can be stored or returned from a
+/// function. Formatting is only performed when the value is written.
+/// ```rust
+/// use std::fmt::Display;
+/// use fmttools::lazy_fmt;
+///
+/// fn version(major: u32, minor: u32) -> impl Display {
+///     let name = String::from("fmttools");
+///     lazy_fmt!("{name} v{}.{}{suffix}", major, minor, suffix = "-dev")
+/// }
+///
+/// assert_eq!("fmttools v1.2-dev", version(1, 2).to_string());
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner values.
+#[macro_export]
+macro_rules! lazy_fmt {
+    (@munch [$fmt:literal] [$(($arg:ident $value:expr))*] [$(($key:ident $named:expr))*]) => {
+        match ($($value,)* $($named,)*) {
+            ($($arg,)* $($key,)*) => $crate::fmt_fn(
+                move |f: &mut ::std::fmt::Formatter<'_>| {
+                    ::std::write!(f, $fmt, $($arg,)* $($key = $key,)*)
+                },
+            ),
+        }
+    };
+    (@munch [$fmt:literal] [$($args:tt)*] [$($keys:tt)*] $key:ident = $named:expr $(, $($rest:tt)*)?) => {
+        $crate::lazy_fmt!(@munch [$fmt] [$($args)*] [$($keys)* ($key $named)] $($($rest)*)?)
+    };
+    (@munch [$fmt:literal] [$($args:tt)*] [$($keys:tt)*] $value:expr $(, $($rest:tt)*)?) => {
+        $crate::lazy_fmt!(@munch [$fmt] [$($args)* (arg $value)] [$($keys)*] $($($rest)*)?)
+    };
+    ($fmt:literal $(, $($args:tt)*)?) => {
+        $crate::lazy_fmt!(@munch [$fmt] [] [] $($($args)*)?)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fmt_fn, FmtFn};
+    use std::cell::Cell;
+    use std::fmt::{self, Formatter};
+
+    #[test]
+    fn arguments_evaluated_once() {
+        let calls = Cell::new(0);
+        let next = || {
+            calls.set(calls.get() + 1);
+            calls.get()
+        };
+
+        let value = lazy_fmt!("{}-{:?}-{x:>3}", next(), "a", x = next(),);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(value.to_string(), "1-\"a\"-  2");
+        assert_eq!(format!("{:?}", value), "1-\"a\"-  2");
+        assert_eq!(calls.get(), 2);
+
+        assert_eq!(lazy_fmt!("plain").to_string(), "plain");
+    }
+
+    #[test]
+    fn usable_in_const() {
+        const UNKNOWN: FmtFn<fn(&mut Formatter<'_>) -> fmt::Result> = fmt_fn(|f| f.pad("?"));
+        assert_eq!(format!("[{:>3}]", UNKNOWN), "[  ?]");
+    }
+}
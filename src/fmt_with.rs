@@ -38,8 +38,8 @@
 //!
 //! assert_eq!("FooEntry { key: \"FooB\" }", format!("{:?}", entry.fmt_with(&registry)));
 //! ```
-use std::fmt;
-use std::fmt::{Debug, Display, Formatter};
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
 
 /// See [crate::fmt_with] for more information.
 pub trait ToFormatWith<T> {
@@ -0,0 +1,128 @@
+use crate::table::Align;
+use crate::width::display_width;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Format rows of cells as a grid, with the cells of each column aligned to the widest among
+/// them. Cells are right aligned and separated by a single space by default, which suits
+/// numeric matrices and game boards. Lines are separated by `\n`, without a trailing line break.
+///
+/// This is a lighter alternative to [crate::table] without borders or headers. The rows are
+/// iterated twice, so they must be [Clone], and each cell is formatted twice.
+/// ```rust
+/// use fmttools::grid;
+///
+/// let matrix = [[1, -20, 3], [400, 5, -6]];
+/// assert_eq!("  1 -20  3\n400   5 -6", grid(&matrix).to_string());
+///
+/// let board = [['X', 'O', ' '], [' ', 'X', ' '], ['O', ' ', 'X']];
+/// assert_eq!("X|O| \n |X| \nO| |X", grid(&board).separator("|").to_string());
+/// ```
+#[inline]
+pub fn grid<R>(rows: R) -> Grid<'static, R> {
+    Grid::new(rows)
+}
+
+/// A grid of rows created by [grid]. Up to `N` columns are aligned, and any further columns are
+/// written without padding.
+pub struct Grid<'a, R, const N: usize = 16> {
+    rows: R,
+    separator: &'a str,
+    align: Align,
+}
+
+impl<R, const N: usize> Grid<'static, R, N> {
+    /// Create a grid which aligns up to `N` columns.
+    #[inline]
+    pub fn new(rows: R) -> Self {
+        Grid {
+            rows,
+            separator: " ",
+            align: Align::Right,
+        }
+    }
+}
+
+impl<'a, R, const N: usize> Grid<'a, R, N> {
+    /// Set the string written between cells. Defaults to a single space.
+    #[inline]
+    pub fn separator(self, separator: &str) -> Grid<'_, R, N> {
+        Grid {
+            rows: self.rows,
+            separator,
+            align: self.align,
+        }
+    }
+
+    /// Set the alignment of every cell. Defaults to [Align::Right].
+    #[inline]
+    pub fn align(self, align: Align) -> Self {
+        Grid { align, ..self }
+    }
+}
+
+impl<R, const N: usize> Display for Grid<'_, R, N>
+where
+    R: IntoIterator + Clone,
+    R::Item: IntoIterator,
+    <R::Item as IntoIterator>::Item: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut widths = [0; N];
+        for row in self.rows.clone() {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(display_width(&cell));
+            }
+        }
+
+        for (index, row) in self.rows.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+
+            let mut cells = row.into_iter().enumerate().peekable();
+            while let Some((column, cell)) = cells.next() {
+                if column > 0 {
+                    f.write_str(self.separator)?;
+                }
+
+                let width = widths.get(column).copied().unwrap_or(0);
+                let space = width.saturating_sub(display_width(&cell));
+                let (before, after) = match self.align {
+                    Align::Left => (0, space),
+                    Align::Right => (space, 0),
+                    Align::Center => (space / 2, space - space / 2),
+                };
+
+                write_repeated(f, ' ', before)?;
+                write!(f, "{}", cell)?;
+                // The last cell is not padded, so no line has trailing whitespace
+                if cells.peek().is_some() {
+                    write_repeated(f, ' ', after)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grid, Grid};
+    use crate::table::Align;
+
+    #[test]
+    fn alignment_and_column_limit() {
+        let rows = [[1.5, 10.0], [-2.25, 3.0]];
+        assert_eq!(
+            " 1.5 ,10\n-2.25,3",
+            grid(&rows).align(Align::Center).separator(",").to_string()
+        );
+        assert_eq!(
+            "1 10\n22 3",
+            Grid::<_, 0>::new(&[[1, 10], [22, 3]]).to_string()
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use crate::width::display_width;
+use crate::wrap::wrap;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Write a prefix at the start of every line of a value's output, like a Markdown blockquote.
+/// No allocation is performed as part of this operation.
+///
+/// Empty lines are given the prefix without its trailing whitespace, so no line ends in
+/// whitespace. A trailing line break is preserved without a prefix after it, and an empty value
+/// is written as nothing at all.
+/// ```rust
+/// use fmttools::gutter;
+///
+/// let reply = "Sounds good.\n\nSee you then!\n";
+/// assert_eq!("> Sounds good.\n>\n> See you then!\n", format!("{}", gutter(reply, "> ")));
+///
+/// // Gutters can be nested
+/// assert_eq!("| > a\n| > b", format!("{}", gutter(gutter("a\nb", "> "), "| ")));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn gutter<'a, T>(value: T, prefix: &'a str) -> Gutter<'a, T> {
+    Gutter { value, prefix }
+}
+
+pub struct Gutter<'a, T> {
+    value: T,
+    prefix: &'a str,
+}
+
+impl<T> Gutter<'_, T> {
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut GutterWriter<'_, &mut Formatter<'_>>) -> fmt::Result,
+    {
+        let mut writer = GutterWriter {
+            dst: f,
+            prefix: self.prefix,
+            line_start: true,
+        };
+        func(&mut writer)
+    }
+}
+
+impl<T: Debug> Debug for Gutter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for Gutter<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+/// Wrap a value's output so that lines do not exceed `width` columns, then start every line with
+/// a comment leader. The leader counts towards the width of each line. See [crate::wrap] and
+/// [gutter] for how lines are broken and prefixed.
+/// ```rust
+/// use fmttools::gutter::comment;
+///
+/// let notice = "This file is generated by build.rs and should not be edited by hand.";
+/// assert_eq!(
+///     "// This file is generated by build.rs\n// and should not be edited by hand.",
+///     format!("{}", comment(notice, "// ", 40))
+/// );
+/// ```
+#[inline]
+pub fn comment<'a, T>(value: T, leader: &'a str, width: usize) -> Comment<'a, T> {
+    Comment {
+        value,
+        leader,
+        width,
+    }
+}
+
+pub struct Comment<'a, T> {
+    value: T,
+    leader: &'a str,
+    width: usize,
+}
+
+impl<T: Display> Display for Comment<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self.width.saturating_sub(display_width(self.leader));
+        write!(f, "{}", gutter(wrap(&self.value, width), self.leader))
+    }
+}
+
+/// Writes the prefix before the first text of each line.
+struct GutterWriter<'a, W> {
+    dst: W,
+    prefix: &'a str,
+    /// If nothing has been written on the current line yet
+    line_start: bool,
+}
+
+impl<W: Write> Write for GutterWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut lines = s.split('\n');
+        let mut line = lines.next().unwrap_or("");
+
+        loop {
+            if self.line_start && !line.is_empty() {
+                self.dst.write_str(self.prefix)?;
+                self.line_start = false;
+            }
+            self.dst.write_str(line)?;
+
+            line = match lines.next() {
+                Some(next) => next,
+                None => return Ok(()),
+            };
+
+            if self.line_start {
+                self.dst.write_str(self.prefix.trim_end())?;
+            }
+            self.dst.write_char('\n')?;
+            self.line_start = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{comment, gutter};
+
+    #[test]
+    fn lines_split_across_writes() {
+        let value = format_args!("{}{}{}", "a", "b\n", "\nc");
+        assert_eq!("# ab\n#\n# c", format!("{}", gutter(value, "# ")));
+        assert_eq!("", format!("{}", gutter("", "# ")));
+        assert_eq!("#\n", format!("{}", gutter("\n", "# ")));
+        assert_eq!("// \"a\\nb\"", format!("{:?}", gutter("a\nb", "// ")));
+    }
+
+    #[test]
+    fn comment_preserves_paragraphs() {
+        let text = "Copyright 2024\n\nLicensed under MIT or Apache-2.0";
+        assert_eq!(
+            " * Copyright 2024\n *\n * Licensed under\n * MIT or\n * Apache-2.0",
+            format!("{}", comment(text, " * ", 18))
+        );
+    }
+}
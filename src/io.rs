@@ -0,0 +1,385 @@
+//! Adapters between [std::fmt::Write] and [std::io::Write].
+//!
+//! ```rust
+//! use std::fmt::Write;
+//! use fmttools::io::IoWriteAdapter;
+//! use fmttools::join;
+//!
+//! let mut writer = IoWriteAdapter::new(Vec::new());
+//! write!(writer, "[{}]", join(&[1, 2, 3], ", ")).unwrap();
+//! assert_eq!(b"[1, 2, 3]", &writer.into_inner()[..]);
+//! ```
+use std::fmt;
+use std::io;
+use std::str;
+
+/// Wraps an [io::Write] so it can be used as a [fmt::Write]. Since [fmt::Error] does not carry
+/// any information, the underlying [io::Error] is kept and can be retrieved with
+/// [IoWriteAdapter::take_error].
+/// ```rust
+/// use std::fmt::Write;
+/// use std::io;
+/// use fmttools::io::IoWriteAdapter;
+///
+/// let mut buf = [0u8; 4];
+/// let mut writer = IoWriteAdapter::new(&mut buf[..]);
+/// assert!(write!(writer, "too long").is_err());
+/// assert_eq!(io::ErrorKind::WriteZero, writer.take_error().unwrap().kind());
+/// ```
+#[derive(Debug)]
+pub struct IoWriteAdapter<W> {
+    dst: W,
+    error: Option<io::Error>,
+}
+
+impl<W> IoWriteAdapter<W> {
+    #[inline]
+    pub fn new(dst: W) -> Self {
+        IoWriteAdapter { dst, error: None }
+    }
+
+    /// Take the error which caused the most recent write to fail, if any.
+    #[inline]
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Unwrap this adapter, returning the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoWriteAdapter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.dst.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// A variant of [IoWriteAdapter] which collects output in an internal buffer of `N` bytes and
+/// writes it to the underlying writer in blocks. Adapters such as [crate::replace] and
+/// [crate::join] produce many small writes, which can otherwise result in a large number of
+/// system calls when writing to an unbuffered file or socket.
+///
+/// Buffered output is written when the buffer is full, when [BufferedIoWriteAdapter::flush] or
+/// [BufferedIoWriteAdapter::into_inner] is called, and when the adapter is dropped. Errors which
+/// occur while dropping are ignored.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::io::BufferedIoWriteAdapter;
+/// use fmttools::join;
+///
+/// let mut writer = BufferedIoWriteAdapter::<_, 64>::new(Vec::new());
+/// write!(writer, "[{}]", join(1..=5, ", ")).unwrap();
+/// assert!(writer.get_ref().is_empty());
+/// assert_eq!(b"[1, 2, 3, 4, 5]", &writer.into_inner().unwrap()[..]);
+/// ```
+#[derive(Debug)]
+pub struct BufferedIoWriteAdapter<W: io::Write, const N: usize = 512> {
+    /// Only `None` once the writer has been taken by [BufferedIoWriteAdapter::into_inner]
+    dst: Option<W>,
+    buf: [u8; N],
+    len: usize,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write, const N: usize> BufferedIoWriteAdapter<W, N> {
+    #[inline]
+    pub fn new(dst: W) -> Self {
+        BufferedIoWriteAdapter {
+            dst: Some(dst),
+            buf: [0; N],
+            len: 0,
+            error: None,
+        }
+    }
+
+    /// Take the error which caused the most recent write to fail, if any.
+    #[inline]
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Get a reference to the underlying writer. This does not include any buffered output.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        self.dst
+            .as_ref()
+            .expect("writer is present until into_inner")
+    }
+
+    /// Write any buffered output and flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.flush_buf()?;
+        self.dst_mut().flush()
+    }
+
+    /// Write any buffered output, then unwrap this adapter, returning the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush_buf()?;
+        Ok(self.dst.take().expect("writer is present until into_inner"))
+    }
+
+    #[inline]
+    fn dst_mut(&mut self) -> &mut W {
+        self.dst
+            .as_mut()
+            .expect("writer is present until into_inner")
+    }
+
+    /// Write the buffered output. Like [io::BufWriter], output which could not be written is kept
+    /// in the buffer so it can be written by a later flush.
+    fn flush_buf(&mut self) -> io::Result<()> {
+        let dst = self
+            .dst
+            .as_mut()
+            .expect("writer is present until into_inner");
+
+        let mut written = 0;
+        let mut result = Ok(());
+        while written < self.len {
+            match dst.write(&self.buf[written..self.len]) {
+                Ok(0) => {
+                    result = Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    ));
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+
+        self.buf.copy_within(written..self.len, 0);
+        self.len -= written;
+        result
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if bytes.len() > N - self.len {
+            self.flush_buf()?;
+        }
+
+        if bytes.len() >= N {
+            return self.dst_mut().write_all(bytes);
+        }
+
+        self.buf[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+        Ok(())
+    }
+}
+
+impl<W: io::Write, const N: usize> fmt::Write for BufferedIoWriteAdapter<W, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+impl<W: io::Write, const N: usize> Drop for BufferedIoWriteAdapter<W, N> {
+    fn drop(&mut self) {
+        if self.dst.is_some() {
+            let _ = self.flush_buf();
+        }
+    }
+}
+
+/// Wraps a [fmt::Write] so it can be used as an [io::Write]. Characters split across writes are
+/// buffered until they are complete, and bytes which are not valid UTF-8 produce an error of kind
+/// [io::ErrorKind::InvalidData].
+/// ```rust
+/// use std::io::Write;
+/// use fmttools::io::FmtWriteAdapter;
+///
+/// let mut writer = FmtWriteAdapter::new(String::new());
+/// writer.write_all(&"é".as_bytes()[..1]).unwrap();
+/// writer.write_all(&"é".as_bytes()[1..]).unwrap();
+/// assert_eq!("é", writer.into_inner());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FmtWriteAdapter<W> {
+    dst: W,
+    /// The start of a character which has not been completed yet
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+impl<W> FmtWriteAdapter<W> {
+    #[inline]
+    pub fn new(dst: W) -> Self {
+        FmtWriteAdapter {
+            dst,
+            pending: [0; 4],
+            pending_len: 0,
+        }
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Unwrap this adapter, returning the underlying writer. Any incomplete character is dropped.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W: fmt::Write> FmtWriteAdapter<W> {
+    fn write_to_dst(&mut self, s: &str) -> io::Result<()> {
+        self.dst
+            .write_str(s)
+            .map_err(|_| io::Error::other("formatter error"))
+    }
+
+    /// Continue an incomplete character, returning the number of bytes consumed from `buf`.
+    fn write_pending(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for (consumed, &byte) in buf.iter().enumerate() {
+            self.pending[self.pending_len] = byte;
+            self.pending_len += 1;
+
+            let pending = self.pending;
+            match str::from_utf8(&pending[..self.pending_len]) {
+                Ok(s) => {
+                    self.pending_len = 0;
+                    self.write_to_dst(s)?;
+                    return Ok(consumed + 1);
+                }
+                Err(err) if err.error_len().is_some() => {
+                    self.pending_len = 0;
+                    return Err(invalid_utf8());
+                }
+                Err(_) => {}
+            }
+        }
+
+        Ok(buf.len())
+    }
+}
+
+impl<W: fmt::Write> io::Write for FmtWriteAdapter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.pending_len > 0 {
+            return self.write_pending(buf);
+        }
+
+        match str::from_utf8(buf) {
+            Ok(s) => self.write_to_dst(s).map(|_| buf.len()),
+            Err(err) if err.valid_up_to() > 0 => {
+                let valid = &buf[..err.valid_up_to()];
+                // The prefix was already checked, so this can not fail
+                let s = str::from_utf8(valid).map_err(|_| invalid_utf8())?;
+                self.write_to_dst(s).map(|_| valid.len())
+            }
+            Err(err) if err.error_len().is_some() => Err(invalid_utf8()),
+            Err(_) => self.write_pending(buf),
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferedIoWriteAdapter, FmtWriteAdapter};
+    use std::io::{self, Write};
+
+    /// Records the size of every write it receives
+    struct RecordWrites(Vec<usize>);
+
+    impl Write for RecordWrites {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn buffered_writes_in_blocks() {
+        let mut writer = BufferedIoWriteAdapter::<_, 8>::new(RecordWrites(Vec::new()));
+        for _ in 0..5 {
+            std::fmt::Write::write_str(&mut writer, "abc").unwrap();
+        }
+        std::fmt::Write::write_str(&mut writer, "0123456789").unwrap();
+        std::fmt::Write::write_str(&mut writer, "x").unwrap();
+        assert_eq!(vec![6, 6, 3, 10, 1], writer.into_inner().unwrap().0);
+    }
+
+    /// Writes at most 2 bytes at a time and fails every other write
+    struct Flaky(Vec<u8>, bool);
+
+    impl Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+
+            let len = buf.len().min(2);
+            self.0.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failed_flush_keeps_output() {
+        let mut writer = BufferedIoWriteAdapter::<_, 8>::new(Flaky(Vec::new(), false));
+        std::fmt::Write::write_str(&mut writer, "abcde").unwrap();
+        while writer.flush().is_err() {}
+        assert_eq!(b"abcde", &writer.get_ref().0[..]);
+    }
+
+    #[test]
+    fn split_and_invalid_characters() {
+        let text = "a€b😀";
+        let mut writer = FmtWriteAdapter::new(String::new());
+        for byte in text.as_bytes() {
+            writer.write_all(&[*byte]).unwrap();
+        }
+        writer.write_all(&text.as_bytes()[..6]).unwrap();
+        writer.write_all(&text.as_bytes()[6..]).unwrap();
+        assert_eq!("a€b😀a€b😀", writer.get_ref());
+
+        let err = writer.write_all(&[b'x', 0xE2, b'y']).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, err.kind());
+        assert_eq!("a€b😀a€b😀x", writer.into_inner());
+    }
+}
@@ -1,6 +1,8 @@
-use std::cell::Cell;
-use std::fmt;
-use std::fmt::{Debug, Display, Formatter};
+use core::cell::Cell;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+
+use crate::num::Integer;
 
 pub struct Join<'a, I> {
     iter: Cell<Option<I>>,
@@ -9,6 +11,14 @@ pub struct Join<'a, I> {
 
 /// Joins iterator elements together with a given separator. Formatting is only performed during
 /// [Debug::fmt] or [Display::fmt].
+///
+/// Constructing the adapter only calls [IntoIterator::into_iter], so it is cheap to pass to
+/// logging macros whose event may be disabled. The iterator is consumed when the value is
+/// formatted, so it can only be formatted once. Use [reusable] to format a join over a [Clone]
+/// iterator any number of times.
+///
+/// Elements are always written using their [Debug] or [Display] implementations, even when the
+/// `itoa` feature is enabled. Use [join_integers] to opt in to the faster integer path.
 /// ```rust
 /// use fmttools::join;
 ///
@@ -18,51 +28,71 @@ pub struct Join<'a, I> {
 ///
 /// ## Note
 /// Elements are formatted according to either their debug or display implementations. Format string
-/// arguments are not passed to elements.
+/// arguments such as the width and precision are passed to each element. To limit the length of the
+/// joined output, wrap it in [truncate_chars](crate::truncate::truncate_chars).
 /// ```rust
 /// use fmttools::join;
 ///
 /// let elements = vec!["abc", "\n", "123"];
 /// assert_eq!("abc, \n, 123", format!("{}", join(&elements, ", ")));
 /// assert_eq!("\"abc\", \"\\n\", \"123\"", format!("{:?}", join(&elements, ", ")));
+/// assert_eq!("ab, \n, 12", format!("{:.2}", join(&elements, ", ")));
+/// assert_eq!("  abc|    \n", format!("{:>5}", join(&elements[..2], "|")));
 /// ```
 ///
 /// See [join_fmt] and [join_fmt_all] for additional control over element and separator formatting.
 #[inline]
-pub fn join<I: IntoIterator>(iter: I, separator: &str) -> Join<I::IntoIter> {
+pub fn join<I: IntoIterator>(iter: I, separator: &str) -> Join<'_, I::IntoIter> {
     Join {
         iter: Cell::new(Some(iter.into_iter())),
         separator,
     }
 }
 
-impl<I> Debug for Join<'_, I>
-where
-    I: Iterator,
-    <I as Iterator>::Item: Debug,
-{
+impl<I: Clone> Clone for Join<'_, I> {
     #[inline]
+    fn clone(&self) -> Self {
+        Join {
+            iter: Cell::new(clone_cell(&self.iter)),
+            separator: self.separator,
+        }
+    }
+}
+
+impl<I: Iterator> Join<'_, I> {
     #[track_caller]
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut item_iter = match self.iter.take() {
-            Some(value) => value,
-            None => panic!("Join can only be used once"),
-        };
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, mut fmt_item: F) -> fmt::Result
+    where
+        F: FnMut(&I::Item, &mut Formatter<'_>) -> fmt::Result,
+    {
+        let mut item_iter = take_once(&self.iter);
 
         match item_iter.next() {
-            Some(value) => <I::Item as Debug>::fmt(&value, f)?,
+            Some(value) => fmt_item(&value, f)?,
             None => return Ok(()),
         }
 
         for item in item_iter {
             f.write_str(self.separator)?;
-            <I::Item as Debug>::fmt(&item, f)?;
+            fmt_item(&item, f)?;
         }
 
         Ok(())
     }
 }
 
+impl<I> Debug for Join<'_, I>
+where
+    I: Iterator,
+    <I as Iterator>::Item: Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, <I::Item as Debug>::fmt)
+    }
+}
+
 impl<I> Display for Join<'_, I>
 where
     I: Iterator,
@@ -71,25 +101,82 @@ where
     #[inline]
     #[track_caller]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut item_iter = match self.iter.take() {
-            Some(value) => value,
-            None => panic!("Join can only be used once"),
-        };
+        self.fmt_impl(f, <I::Item as Display>::fmt)
+    }
+}
+
+/// Joins integers together with a given separator. This produces the same output as [join], but
+/// writes each integer directly instead of going through its [Display] implementation. Enable the
+/// `itoa` feature to render integers using the [itoa](https://docs.rs/itoa) crate. Since [join]
+/// can not tell when its elements are integers, this is the only join which uses `itoa`.
+/// ```rust
+/// use fmttools::join_integers;
+///
+/// let elements = vec![1, -20, 300];
+/// assert_eq!("1, -20, 300", format!("{}", join_integers(&elements, ", ")));
+/// ```
+#[inline]
+pub fn join_integers<I>(iter: I, separator: &str) -> JoinIntegers<'_, I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Integer,
+{
+    JoinIntegers {
+        iter: Cell::new(Some(iter.into_iter())),
+        separator,
+    }
+}
+
+pub struct JoinIntegers<'a, I> {
+    iter: Cell<Option<I>>,
+    separator: &'a str,
+}
+
+impl<I: Clone> Clone for JoinIntegers<'_, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        JoinIntegers {
+            iter: Cell::new(clone_cell(&self.iter)),
+            separator: self.separator,
+        }
+    }
+}
+
+impl<I> JoinIntegers<'_, I>
+where
+    I: Iterator,
+    I::Item: Integer,
+{
+    #[track_caller]
+    fn fmt_impl(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut item_iter = take_once(&self.iter);
 
         match item_iter.next() {
-            Some(value) => <I::Item as Display>::fmt(&value, f)?,
+            Some(value) => value.write_decimal(f)?,
             None => return Ok(()),
         }
 
         for item in item_iter {
             f.write_str(self.separator)?;
-            <I::Item as Display>::fmt(&item, f)?;
+            item.write_decimal(f)?;
         }
 
         Ok(())
     }
 }
 
+impl<I> Display for JoinIntegers<'_, I>
+where
+    I: Iterator,
+    I::Item: Integer,
+{
+    #[inline]
+    #[track_caller]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
 /// Joins iterator elements together with a given separator. Formatting is only performed during
 /// [Debug::fmt] or [Display::fmt].
 /// ```rust
@@ -134,11 +221,22 @@ pub struct JoinFmt<I, F, S> {
     separator: S,
 }
 
+#[derive(Clone)]
 struct JoinFmtInner<I, F> {
     iter: I,
     element_writer: F,
 }
 
+impl<I: Clone, F: Clone, S: Clone> Clone for JoinFmt<I, F, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        JoinFmt {
+            inner: Cell::new(clone_cell(&self.inner)),
+            separator: self.separator.clone(),
+        }
+    }
+}
+
 impl<I, F, S> Display for JoinFmt<I, F, S>
 where
     I: Iterator,
@@ -151,10 +249,7 @@ where
         let JoinFmtInner {
             mut iter,
             mut element_writer,
-        } = match self.inner.take() {
-            Some(value) => value,
-            None => panic!("Join can only be used once"),
-        };
+        } = take_once(&self.inner);
 
         let mut previous = match iter.next() {
             Some(value) => value,
@@ -223,12 +318,22 @@ pub struct JoinFmtAll<I, F, S> {
     inner: Cell<Option<JoinFmtAllInner<I, F, S>>>,
 }
 
+#[derive(Clone)]
 struct JoinFmtAllInner<I, F, S> {
     iter: I,
     element_writer: F,
     separator_writer: S,
 }
 
+impl<I: Clone, F: Clone, S: Clone> Clone for JoinFmtAll<I, F, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        JoinFmtAll {
+            inner: Cell::new(clone_cell(&self.inner)),
+        }
+    }
+}
+
 impl<I, F, S> Display for JoinFmtAll<I, F, S>
 where
     I: Iterator,
@@ -238,14 +343,11 @@ where
     #[inline]
     #[track_caller]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let Some(JoinFmtAllInner {
+        let JoinFmtAllInner {
             mut iter,
             mut element_writer,
             mut separator_writer,
-        }) = self.inner.take()
-        else {
-            panic!("Join can only be used once");
-        };
+        } = take_once(&self.inner);
 
         let Some(mut previous) = iter.next() else {
             return Ok(());
@@ -261,13 +363,68 @@ where
     }
 }
 
+/// Take the value out of a single-use join.
+#[inline]
+#[track_caller]
+pub(crate) fn take_once<T>(cell: &Cell<Option<T>>) -> T {
+    match cell.take() {
+        Some(value) => value,
+        None => panic!("Join can only be used once"),
+    }
+}
+
+/// Clone the value in a cell without consuming it.
+#[inline]
+pub(crate) fn clone_cell<T: Clone>(cell: &Cell<Option<T>>) -> Option<T> {
+    let value = cell.take();
+    let cloned = value.clone();
+    cell.set(value);
+    cloned
+}
+
+/// Format a fresh clone of a value each time it is written. This allows adapters which can only be
+/// formatted once, such as [join] and [join_fmt], to be formatted any number of times as long as
+/// their iterator and formatting functions can be cloned. This is useful when passing joins to
+/// logging frameworks which may format a value more than once, such as once by each layer of a
+/// `tracing` subscriber.
+/// ```rust
+/// use fmttools::join::{join, reusable};
+///
+/// let values = [1, 2, 3];
+/// let joined = reusable(join(&values, "-"));
+/// assert_eq!("1-2-3 1-2-3", format!("{} {:?}", joined, joined));
+/// ```
+#[inline]
+pub fn reusable<T: Clone>(value: T) -> Reusable<T> {
+    Reusable { value }
+}
+
+#[derive(Clone)]
+pub struct Reusable<T> {
+    value: T,
+}
+
+impl<T: Clone + Debug> Debug for Reusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.value.clone(), f)
+    }
+}
+
+impl<T: Clone + Display> Display for Reusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.value.clone(), f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::join::join;
+    use crate::join::{join, join_fmt_all, join_integers, reusable};
 
     #[test]
     pub fn join_debug() {
-        let values = ["abc", "def", "\0123"];
+        let values = ["abc", "def", "\x00123"];
 
         let output = format!("{:?}", join(values, ", "));
         assert_eq!(output, "\"abc\", \"def\", \"\\0123\"");
@@ -275,9 +432,55 @@ mod tests {
 
     #[test]
     pub fn join_display() {
-        let values = ["abc", "def", "\0123"];
+        let values = ["abc", "def", "\x00123"];
 
         let output = format!("{}", join(values, ", "));
-        assert_eq!(output, "abc, def, \0123");
+        assert_eq!(output, "abc, def, \x00123");
+    }
+
+    #[test]
+    pub fn join_integers_extremes() {
+        let values = [i128::MIN, 0, i128::MAX];
+
+        let output = format!("{}", join_integers(values, ","));
+        assert_eq!(output, format!("{},0,{}", i128::MIN, i128::MAX));
+    }
+
+    #[test]
+    pub fn join_precision_per_element() {
+        let values = [1.5, -2.25];
+        assert_eq!(format!("{:.2}", join(&values, ", ")), "1.50, -2.25");
+        assert_eq!(format!("{:>5}", join(&values, ", ")), "  1.5, -2.25");
+        assert_eq!(format!("{:.1?}", join(["ab", "c"], ",")), "\"ab\",\"c\"");
+        assert_eq!(format!("{:.1}", join_integers([10, -20], ",")), "10,-20");
+    }
+
+    #[test]
+    pub fn join_formatted_repeatedly() {
+        let values = [1, 2, 3];
+        let joined = reusable(join(&values, "-"));
+        assert_eq!(format!("{} {:?}", joined, joined), "1-2-3 1-2-3");
+
+        let counted = reusable(join_fmt_all(
+            &values,
+            |f| f.write_str("+"),
+            |x, f| write!(f, "{}", x),
+        ));
+        assert_eq!(format!("{}={}", counted, counted), "1+2+3=1+2+3");
+    }
+
+    #[test]
+    #[should_panic(expected = "Join can only be used once")]
+    pub fn join_formatted_twice() {
+        let joined = join(&[1, 2, 3], "-");
+        let _ = format!("{} {}", joined, joined);
+    }
+
+    #[test]
+    pub fn join_clone_before_format() {
+        let joined = join(&[1, 2, 3], "-");
+        let copy = joined.clone();
+        assert_eq!(format!("{}", joined), "1-2-3");
+        assert_eq!(format!("{}", copy), "1-2-3");
     }
 }
@@ -0,0 +1,300 @@
+//! Write JSON values directly from iterators, without building a document in memory.
+//!
+//! Values are written in a single pass in their compact form, without any whitespace between
+//! tokens. Object keys and strings are escaped so the output is always valid JSON.
+//!
+//! ```rust
+//! use fmttools::json::{json_array, json_object, json_string, ToJson};
+//!
+//! let tags = ["web", "prod"];
+//! let fields = [
+//!     ("level", &"warn" as &dyn ToJson),
+//!     ("latency_ms", &12.5),
+//!     ("cached", &false),
+//!     ("tags", &json_array(&tags)),
+//!     ("msg", &json_string(format_args!("retry {} of {}", 2, 3))),
+//! ];
+//!
+//! assert_eq!(
+//!     format!("{}", json_object(fields.iter().copied())),
+//!     r#"{"level":"warn","latency_ms":12.5,"cached":false,"tags":["web","prod"],"msg":"retry 2 of 3"}"#
+//! );
+//! ```
+use crate::num::Float;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Types which can be written as a JSON value.
+///
+/// Strings and characters are written as escaped JSON strings, numbers and booleans are written
+/// as is, and `None` is written as `null`. Floats which are infinite or NaN have no JSON
+/// representation and are written as `null`. Use [json_string] to write the [Display] output of
+/// other types as a string.
+pub trait ToJson {
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result;
+}
+
+macro_rules! impl_to_json_display {
+    ($($t:ty),*) => {
+        $(impl ToJson for $t {
+            #[inline]
+            fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+                write!(out, "{}", self)
+            }
+        })*
+    };
+}
+
+impl_to_json_display!(bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_to_json_float {
+    ($($t:ty),*) => {
+        $(impl ToJson for $t {
+            #[inline]
+            fn write_json(&self, mut out: &mut dyn Write) -> fmt::Result {
+                if Float::is_finite(*self) {
+                    self.write_shortest(&mut out)
+                } else {
+                    out.write_str("null")
+                }
+            }
+        })*
+    };
+}
+
+impl_to_json_float!(f32, f64);
+
+impl ToJson for str {
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{}", json_string(self))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl ToJson for alloc::string::String {
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        self.as_str().write_json(out)
+    }
+}
+
+impl ToJson for char {
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{}", json_string(self))
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T> {
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        match self {
+            Some(value) => value.write_json(out),
+            None => out.write_str("null"),
+        }
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        (**self).write_json(out)
+    }
+}
+
+/// Write the [Display] output of a value as an escaped JSON string, including the surrounding
+/// quotes. No allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::json::json_string;
+///
+/// assert_eq!(r#""say \"hi\"\n""#, format!("{}", json_string("say \"hi\"\n")));
+/// assert_eq!(r#""\u001b[1m""#, format!("{}", json_string("\x1b[1m")));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn json_string<T>(value: T) -> JsonString<T> {
+    JsonString { value }
+}
+
+pub struct JsonString<T> {
+    value: T,
+}
+
+impl<T: Display> Display for JsonString<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        write!(JsonEscaper { dst: &mut *f }, "{}", self.value)?;
+        f.write_char('"')
+    }
+}
+
+impl<T: Display> ToJson for JsonString<T> {
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+/// Write an iterator of values as a JSON array. The values are iterated each time the array is
+/// formatted, so they must be [Clone].
+/// ```rust
+/// use fmttools::json::json_array;
+///
+/// let values = [Some(1.5), None, Some(f64::NAN)];
+/// assert_eq!("[1.5,null,null]", format!("{}", json_array(&values)));
+///
+/// let rows = [[1, 2], [3, 4]];
+/// assert_eq!("[[1,2],[3,4]]", format!("{}", json_array(rows.iter().map(json_array))));
+/// ```
+#[inline]
+pub fn json_array<I>(values: I) -> JsonArray<I> {
+    JsonArray { values }
+}
+
+pub struct JsonArray<I> {
+    values: I,
+}
+
+impl<I> Display for JsonArray<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: ToJson,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_char('[')?;
+        for (index, value) in self.values.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char(',')?;
+            }
+            value.write_json(f)?;
+        }
+        f.write_char(']')
+    }
+}
+
+impl<I> ToJson for JsonArray<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: ToJson,
+{
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+/// Write an iterator of key-value pairs as a JSON object. Keys are written using their [Display]
+/// output as escaped strings. The pairs are iterated each time the object is formatted, so they
+/// must be [Clone]. Duplicate keys are not detected.
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use fmttools::json::json_object;
+///
+/// let mut counts = BTreeMap::new();
+/// counts.insert("a\"b", 1);
+/// counts.insert("c", 2);
+/// assert_eq!(r#"{"a\"b":1,"c":2}"#, format!("{}", json_object(&counts)));
+/// ```
+#[inline]
+pub fn json_object<I>(pairs: I) -> JsonObject<I> {
+    JsonObject { pairs }
+}
+
+pub struct JsonObject<I> {
+    pairs: I,
+}
+
+impl<I, K, V> Display for JsonObject<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: ToJson,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_char('{')?;
+        for (index, (key, value)) in self.pairs.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char(',')?;
+            }
+            write!(f, "{}:", json_string(key))?;
+            value.write_json(f)?;
+        }
+        f.write_char('}')
+    }
+}
+
+impl<I, K, V> ToJson for JsonObject<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: ToJson,
+{
+    #[inline]
+    fn write_json(&self, out: &mut dyn Write) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+/// Escapes quotes, backslashes, and control characters in the contents of a JSON string.
+pub(crate) struct JsonEscaper<W> {
+    pub(crate) dst: W,
+}
+
+impl<W: Write> Write for JsonEscaper<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            let escape = match c {
+                '"' => Some("\\\""),
+                '\\' => Some("\\\\"),
+                '\n' => Some("\\n"),
+                '\r' => Some("\\r"),
+                '\t' => Some("\\t"),
+                '\x08' => Some("\\b"),
+                '\x0c' => Some("\\f"),
+                '\x00'..='\x1f' => None,
+                _ => continue,
+            };
+
+            self.dst.write_str(&s[flushed..index])?;
+            match escape {
+                Some(escape) => self.dst.write_str(escape)?,
+                None => write!(self.dst, "\\u{:04x}", c as u32)?,
+            }
+            flushed = index + c.len_utf8();
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_array, json_object, json_string, ToJson};
+
+    #[test]
+    fn escapes_split_across_writes() {
+        let value = format_args!("{}{}{}", "a\"", "\\\u{7f}\x01", "é\x0c");
+        let expected = "\"a\\\"\\\\\u{7f}\\u0001é\\f\"";
+        assert_eq!(expected, format!("{}", json_string(value)));
+    }
+
+    #[test]
+    fn nested_and_empty() {
+        let empty: [(&str, i32); 0] = [];
+        let inner = json_object(empty.iter().copied());
+        let outer = [
+            ("inner", &inner as &dyn ToJson),
+            ("list", &json_array(&[-1i64])),
+        ];
+        assert_eq!(
+            r#"{"inner":{},"list":[-1]}"#,
+            format!("{}", json_object(outer.iter().copied()))
+        );
+        assert_eq!("[]", format!("{}", json_array(Vec::<String>::new())));
+    }
+}
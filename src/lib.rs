@@ -1,6 +1,8 @@
 //! Tools for efficient modification of text as part of a single `write!` call.
 //!  - **No allocation is performed**
 //!  - **Implemented using only safe Rust**
+//!  - **Supports `no_std`** by disabling the default `std` feature. Adapters for `std` types such
+//!    as [Path](std::path::Path) and [SystemTime](std::time::SystemTime) are only available with it.
 //!
 //! ## Examples
 //! ### Joining iterator elements
@@ -44,11 +46,91 @@
 //! assert_eq!("FooBiz { a: \"Biz\" }", format!("{:?}", replace(&value, "Bar", "Biz")));
 //! ```
 #![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+extern crate alloc;
+
+pub mod boxed;
+pub mod bytes;
+pub mod cached;
+pub mod case;
+pub mod center;
+pub mod chart;
+pub mod columns;
+pub mod csv;
+pub mod debug;
+#[cfg(feature = "defmt")]
+pub mod defmt;
+pub mod either;
+pub mod env;
+pub mod error;
+pub mod escape;
+pub mod ext;
+pub mod fmt_fn;
 pub mod fmt_with;
+pub mod grid;
+pub mod gutter;
+#[cfg(feature = "std")]
+pub mod io;
 pub mod join;
+pub mod json;
+#[cfg(feature = "locale")]
+pub mod locale;
+#[cfg(feature = "log")]
+pub mod log;
+pub mod logfmt;
+pub mod map;
+mod math;
+pub mod num;
+pub mod option;
+#[cfg(feature = "std")]
+pub mod path;
+pub mod prelude;
+pub mod prometheus;
+pub mod redact;
 pub mod replace;
+pub mod result;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod snapshot;
+pub mod snippet;
+pub mod spec;
+pub mod style;
+pub mod surround;
+pub mod table;
+pub mod tabs;
+pub mod time;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+pub mod tree;
+pub mod truncate;
+pub mod url;
+pub mod width;
+pub mod wrap;
+pub mod writer;
 
+pub use boxed::boxed;
+pub use cached::Cached;
+pub use center::center_block;
+pub use columns::columns;
+pub use either::{either, Either};
+pub use escape::{quoted, sanitize, shell_quote, single_line};
+pub use fmt_fn::fmt_fn;
 pub use fmt_with::{DebugWith, DisplayWith, ToFormatWith};
-pub use join::{join, join_fmt, join_fmt_all};
+pub use grid::grid;
+pub use gutter::{comment, gutter};
+pub use join::{join, join_fmt, join_fmt_all, join_integers, reusable};
+#[cfg(feature = "locale")]
+pub use locale::join_locale;
+pub use logfmt::logfmt;
+pub use map::{definition_list, fmt_map};
+pub use option::{display_if, display_if_some, display_or, display_or_else};
+pub use redact::{mask, redact_fields};
 pub use replace::replace;
+pub use result::display_result;
+pub use style::{styled, styled_if, Style};
+pub use surround::surround;
+pub use tabs::{expand_tabs, unexpand};
+pub use width::display_width;
+pub use wrap::wrap;
@@ -0,0 +1,194 @@
+//! Joining of lists according to CLDR list patterns (ex: `A, B, and C`). Patterns are supplied as
+//! data in [ListStyle], with presets provided for a handful of common locales.
+//!
+//! Requires the `locale` feature.
+//! ```rust
+//! use fmttools::locale::{join_locale, ListStyle};
+//!
+//! let items = ["apples", "pears", "plums"];
+//! assert_eq!("apples, pears, and plums", format!("{}", join_locale(&items, ListStyle::ENGLISH_AND)));
+//! assert_eq!("apples, pears et plums", format!("{}", join_locale(&items, ListStyle::FRENCH_AND)));
+//! ```
+use crate::join::{clone_cell, take_once};
+use core::cell::Cell;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter};
+
+/// The separators of a CLDR list pattern. CLDR describes each pattern in the form `{0}, {1}`, so
+/// only the text between the placeholders is stored here.
+///
+/// For a list of 2 elements, `pair` is used. For longer lists, `start` is placed after the first
+/// element, `end` is placed before the last element, and `middle` is placed between all others.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ListStyle<'a> {
+    pub pair: &'a str,
+    pub start: &'a str,
+    pub middle: &'a str,
+    pub end: &'a str,
+}
+
+impl ListStyle<'static> {
+    /// `A, B, and C`
+    pub const ENGLISH_AND: ListStyle<'static> = ListStyle::conjunction(" and ", ", ", ", and ");
+    /// `A, B, or C`
+    pub const ENGLISH_OR: ListStyle<'static> = ListStyle::conjunction(" or ", ", ", ", or ");
+    /// `A, B et C`
+    pub const FRENCH_AND: ListStyle<'static> = ListStyle::conjunction(" et ", ", ", " et ");
+    /// `A, B ou C`
+    pub const FRENCH_OR: ListStyle<'static> = ListStyle::conjunction(" ou ", ", ", " ou ");
+    /// `A, B und C`
+    pub const GERMAN_AND: ListStyle<'static> = ListStyle::conjunction(" und ", ", ", " und ");
+    /// `A, B oder C`
+    pub const GERMAN_OR: ListStyle<'static> = ListStyle::conjunction(" oder ", ", ", " oder ");
+    /// `A, B y C`
+    pub const SPANISH_AND: ListStyle<'static> = ListStyle::conjunction(" y ", ", ", " y ");
+    /// `A, B o C`
+    pub const SPANISH_OR: ListStyle<'static> = ListStyle::conjunction(" o ", ", ", " o ");
+    /// `A、B和C`
+    pub const CHINESE_AND: ListStyle<'static> = ListStyle::conjunction("和", "、", "和");
+}
+
+impl<'a> ListStyle<'a> {
+    /// Create a list style where the start and middle separators are the same, as is the case for
+    /// most locales.
+    #[inline]
+    pub const fn conjunction(pair: &'a str, middle: &'a str, end: &'a str) -> Self {
+        ListStyle {
+            pair,
+            start: middle,
+            middle,
+            end,
+        }
+    }
+}
+
+/// Joins iterator elements together according to a [ListStyle]. Formatting is only performed
+/// during [Debug::fmt] or [Display::fmt]. See [crate::join] for more information.
+/// ```rust
+/// use fmttools::locale::{join_locale, ListStyle};
+///
+/// assert_eq!("1 or 2", format!("{}", join_locale([1, 2], ListStyle::ENGLISH_OR)));
+/// assert_eq!("\"a\"", format!("{:?}", join_locale(["a"], ListStyle::ENGLISH_OR)));
+/// ```
+#[inline]
+pub fn join_locale<I: IntoIterator>(iter: I, style: ListStyle<'_>) -> JoinLocale<'_, I::IntoIter> {
+    JoinLocale {
+        iter: Cell::new(Some(iter.into_iter())),
+        style,
+    }
+}
+
+pub struct JoinLocale<'a, I> {
+    iter: Cell<Option<I>>,
+    style: ListStyle<'a>,
+}
+
+impl<I: Clone> Clone for JoinLocale<'_, I> {
+    #[inline]
+    fn clone(&self) -> Self {
+        JoinLocale {
+            iter: Cell::new(clone_cell(&self.iter)),
+            style: self.style,
+        }
+    }
+}
+
+impl<I: Iterator> JoinLocale<'_, I> {
+    #[track_caller]
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, mut fmt_item: F) -> fmt::Result
+    where
+        F: FnMut(&I::Item, &mut Formatter<'_>) -> fmt::Result,
+    {
+        let mut iter = take_once(&self.iter);
+
+        let Some(first) = iter.next() else {
+            return Ok(());
+        };
+        fmt_item(&first, f)?;
+
+        let Some(mut previous) = iter.next() else {
+            return Ok(());
+        };
+
+        let Some(mut current) = iter.next() else {
+            f.write_str(self.style.pair)?;
+            return fmt_item(&previous, f);
+        };
+
+        // One element of lookahead is kept to know when the end separator is needed
+        f.write_str(self.style.start)?;
+        for next in iter {
+            fmt_item(&previous, f)?;
+            f.write_str(self.style.middle)?;
+            previous = current;
+            current = next;
+        }
+
+        fmt_item(&previous, f)?;
+        f.write_str(self.style.end)?;
+        fmt_item(&current, f)
+    }
+}
+
+impl<I> Debug for JoinLocale<'_, I>
+where
+    I: Iterator,
+    <I as Iterator>::Item: Debug,
+{
+    #[inline]
+    #[track_caller]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, <I::Item as Debug>::fmt)
+    }
+}
+
+impl<I> Display for JoinLocale<'_, I>
+where
+    I: Iterator,
+    <I as Iterator>::Item: Display,
+{
+    #[inline]
+    #[track_caller]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, <I::Item as Display>::fmt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{join_locale, ListStyle};
+
+    #[test]
+    fn list_lengths() {
+        let style = ListStyle::ENGLISH_AND;
+        let cases = [
+            (0, ""),
+            (1, "1"),
+            (2, "1 and 2"),
+            (3, "1, 2, and 3"),
+            (5, "1, 2, 3, 4, and 5"),
+        ];
+
+        for (length, expected) in cases {
+            assert_eq!(format!("{}", join_locale(1..=length, style)), expected);
+        }
+    }
+
+    #[test]
+    fn precision_per_element() {
+        let values = [0.5, 1.25];
+        let joined = join_locale(&values, ListStyle::ENGLISH_AND);
+        assert_eq!(format!("{:.2}", joined), "0.50 and 1.25");
+    }
+
+    #[test]
+    fn distinct_start_separator() {
+        let style = ListStyle {
+            pair: "+",
+            start: "<",
+            middle: "|",
+            end: ">",
+        };
+        assert_eq!(format!("{}", join_locale(1..=4, style)), "1<2|3>4");
+    }
+}
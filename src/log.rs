@@ -0,0 +1,87 @@
+//! Pass adapters directly as structured values to the [log](::log) crate. Requires the `log`
+//! feature.
+//!
+//! Adapters implement [ToValue] by capturing themselves as [Display] values. Nothing is formatted
+//! until the record is written, so constructing an adapter for a record which is filtered out is
+//! cheap.
+//!
+//! Since a logger may format a value any number of times, [ToValue] is only implemented for
+//! adapters which can be formatted repeatedly. Adapters which can only be formatted once, such as
+//! [crate::join], or which wrap another value, such as [crate::replace], must be wrapped in
+//! [crate::reusable] first.
+//! ```rust
+//! use fmttools::{join, reusable};
+//! use log::kv::ToValue;
+//!
+//! let ports = [80, 443];
+//! let value = reusable(join(&ports, ", "));
+//! assert_eq!("80, 443", value.to_value().to_string());
+//! assert_eq!("80, 443", value.to_value().to_string());
+//! ```
+use ::log::kv::{ToValue, Value};
+use core::fmt::Display;
+
+macro_rules! impl_to_value {
+    ($([$($generics:tt)*] $t:ty),* $(,)?) => {
+        $(impl<$($generics)*> ToValue for $t
+        where
+            Self: Display,
+        {
+            #[inline]
+            fn to_value(&self) -> Value<'_> {
+                Value::from_display(self)
+            }
+        })*
+    };
+}
+
+impl_to_value!(
+    [F] crate::fmt_fn::FmtFn<F>,
+    [T] crate::join::Reusable<T>,
+);
+
+impl_to_value!(
+    [] crate::num::ByteSize,
+    [T] crate::num::Currency<'_, T>,
+    [] crate::num::Engineering,
+    [T] crate::num::FixedPoint<T>,
+    [T] crate::num::FloatShortest<T>,
+    [T] crate::num::Grouped<T>,
+    [] crate::num::GroupedFloat,
+    [T] crate::num::Ordinal<T>,
+    [] crate::num::PerSecond,
+    [] crate::num::Percent,
+    [T] crate::num::Radix<'_, T>,
+    [] crate::num::Rate,
+    [T] crate::num::Roman<T>,
+    [T] crate::num::RustLiteral<'_, T>,
+    [] crate::num::Si<'_>,
+    [] crate::num::SigFigs,
+    [T] crate::num::TrimZeros<T>,
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::join::{join, reusable};
+    use crate::num::ordinal;
+    use crate::replace::replace;
+    use ::log::kv::ToValue;
+
+    #[test]
+    pub fn replace_to_value() {
+        let value = reusable(replace("a-b", '-', "+"));
+        assert_eq!(value.to_value().to_string(), "a+b");
+    }
+
+    #[test]
+    pub fn reusable_to_value() {
+        let value = reusable(join(&[1, 2, 3], "-"));
+        assert_eq!(value.to_value().to_string(), "1-2-3");
+        assert_eq!(value.to_value().to_string(), "1-2-3");
+    }
+
+    #[test]
+    pub fn numeric_to_value() {
+        assert_eq!(ordinal(3).to_value().to_string(), "3rd");
+    }
+}
@@ -0,0 +1,123 @@
+use crate::escape::{quoted_with, EscapeStyle};
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Write key-value pairs in [logfmt](https://brandur.org/logfmt) style, as `key=value` pairs
+/// separated by spaces.
+///
+/// Values which are empty or contain spaces, quotes, `=`, or control characters are surrounded by
+/// double quotes, with quotes, backslashes, and control characters escaped as in Rust strings.
+/// Any spaces, quotes, `=`, or control characters in keys are replaced with `_`. Each value is
+/// formatted twice, first to check if it needs quotes and then to write it. The pairs are iterated
+/// each time they are formatted, so they must be [Clone].
+/// ```rust
+/// use fmttools::logfmt;
+///
+/// let pairs = [
+///     ("level", format_args!("{}", "info")),
+///     ("msg", format_args!("{}", "request \"done\"")),
+///     ("status", format_args!("{}", 200)),
+///     ("user", format_args!("")),
+/// ];
+/// assert_eq!(
+///     r#"level=info msg="request \"done\"" status=200 user="""#,
+///     format!("{}", logfmt(pairs.iter().copied()))
+/// );
+/// ```
+#[inline]
+pub fn logfmt<I>(pairs: I) -> Logfmt<I> {
+    Logfmt { pairs }
+}
+
+pub struct Logfmt<I> {
+    pairs: I,
+}
+
+impl<I, K, V> Display for Logfmt<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, (key, value)) in self.pairs.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char(' ')?;
+            }
+
+            write!(KeyWriter { dst: &mut *f }, "{}", key)?;
+            f.write_char('=')?;
+
+            let mut check = NeedsQuotes {
+                empty: true,
+                special: false,
+            };
+            write!(check, "{}", value)?;
+            if check.empty || check.special {
+                write!(f, "{}", quoted_with(&value, '"', EscapeStyle::Rust))?;
+            } else {
+                write!(f, "{}", value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true for characters which can not appear in a key or unquoted value.
+#[inline]
+fn is_special(c: char) -> bool {
+    c == ' ' || c == '"' || c == '=' || c.is_control()
+}
+
+/// Checks if a value needs to be quoted.
+struct NeedsQuotes {
+    empty: bool,
+    special: bool,
+}
+
+impl Write for NeedsQuotes {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.empty &= s.is_empty();
+        self.special |= s.contains(is_special);
+        Ok(())
+    }
+}
+
+/// Replaces characters which are not allowed in keys.
+struct KeyWriter<W> {
+    dst: W,
+}
+
+impl<W: Write> Write for KeyWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            if !is_special(c) {
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            self.dst.write_char('_')?;
+            flushed = index + c.len_utf8();
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::logfmt;
+
+    #[test]
+    fn keys_and_values_escaped() {
+        let pairs = [("user id", "a=b"), ("path", "/tmp/x"), ("err", "line\n\\")];
+        assert_eq!(
+            r#"user_id="a=b" path=/tmp/x err="line\n\\""#,
+            format!("{}", logfmt(pairs.iter().copied()))
+        );
+    }
+}
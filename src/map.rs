@@ -0,0 +1,313 @@
+use crate::width::{display_width, WidthCounter};
+use crate::wrap::wrap;
+#[cfg(feature = "alloc")]
+use crate::writer::fmt_cmp;
+use crate::writer::{write_repeated, TeeWriter};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Format key-value pairs as `key: value` lines, with the values aligned after the widest key.
+/// Lines are separated by `\n`, without a trailing line break.
+///
+/// The pairs are iterated twice, first to measure the keys and then to write the lines, so they
+/// must be [Clone]. As a result, each key is formatted twice. Lines in multi-line values are
+/// indented to line up with the first line of the value, so maps can be nested.
+/// ```rust
+/// use std::collections::BTreeMap;
+/// use fmttools::fmt_map;
+///
+/// let mut config = BTreeMap::new();
+/// config.insert("host", "localhost");
+/// config.insert("port", "8080");
+/// config.insert("log_level", "debug");
+///
+/// assert_eq!(
+///     format!("{}", fmt_map(&config)),
+///     "host:      localhost\nlog_level: debug\nport:      8080"
+/// );
+///
+/// let api = [("host", "10.0.0.1"), ("port", "80")];
+/// let db = [("host", "10.0.0.2")];
+/// let services = [("api", fmt_map(api.iter().copied())), ("db", fmt_map(db.iter().copied()))];
+/// assert_eq!(
+///     format!("{}", fmt_map(services.iter().map(|(k, v)| (k, v)))),
+///     "api: host: 10.0.0.1\n     port: 80\ndb:  host: 10.0.0.2"
+/// );
+/// ```
+#[inline]
+pub fn fmt_map<I>(pairs: I) -> FmtMap<I> {
+    FmtMap {
+        pairs,
+        indent: 0,
+        #[cfg(feature = "alloc")]
+        sorted: false,
+    }
+}
+
+pub struct FmtMap<I> {
+    pairs: I,
+    indent: usize,
+    #[cfg(feature = "alloc")]
+    sorted: bool,
+}
+
+impl<I> FmtMap<I> {
+    /// Write the given number of spaces at the start of every line.
+    #[inline]
+    pub fn indent(self, indent: usize) -> Self {
+        FmtMap { indent, ..self }
+    }
+
+    /// Write the pairs in order of their formatted keys. This requires allocating a buffer to hold
+    /// the pairs.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn sorted(self) -> Self {
+        FmtMap {
+            sorted: true,
+            ..self
+        }
+    }
+}
+
+impl<I, K, V> FmtMap<I>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Display,
+    V: Display,
+{
+    fn write_pairs<P>(&self, f: &mut Formatter<'_>, pairs: P, key_width: usize) -> fmt::Result
+    where
+        P: Iterator<Item = (K, V)>,
+    {
+        for (index, (key, value)) in pairs.enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+
+            write_repeated(f, ' ', self.indent)?;
+            let mut key_out = TeeWriter::new(&mut *f, WidthCounter::new());
+            write!(key_out, "{}:", key)?;
+            let (_, counter) = key_out.into_inner();
+            write_repeated(f, ' ', (key_width + 2).saturating_sub(counter.width()))?;
+
+            let mut out = Continuation {
+                dst: &mut *f,
+                indent: self.indent + key_width + 2,
+                pending: 0,
+            };
+            write!(out, "{}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<I, K, V> Display for FmtMap<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let key_width = self
+            .pairs
+            .clone()
+            .into_iter()
+            .map(|(key, _)| display_width(key))
+            .max()
+            .unwrap_or(0);
+
+        #[cfg(feature = "alloc")]
+        if self.sorted {
+            let mut pairs: Vec<(K, V)> = self.pairs.clone().into_iter().collect();
+            pairs.sort_by(|a, b| fmt_cmp(&a.0, &b.0));
+            return self.write_pairs(f, pairs.into_iter(), key_width);
+        }
+
+        self.write_pairs(f, self.pairs.clone().into_iter(), key_width)
+    }
+}
+
+/// Format terms and their descriptions in two columns, as in the option listings of `--help`
+/// output. Each term is padded to `term_width` columns, followed by two spaces and then its
+/// description. Descriptions are wrapped to fit within a total width of 80 columns, with
+/// continuation lines indented to line up under the description column. Lines are separated by
+/// `\n`, without a trailing line break.
+///
+/// Terms which are wider than `term_width` are written on a line of their own, with the
+/// description starting on the following line.
+/// ```rust
+/// use fmttools::map::definition_list;
+///
+/// let options = [
+///     ("-v, --verbose", "Print additional information about each step"),
+///     ("-o, --output <FILE>", "Write to FILE"),
+///     ("-q", "Silence all output"),
+/// ];
+///
+/// let output = definition_list(options.iter().copied(), 14).width(48).indent(2);
+/// let expected = [
+///     "  -v, --verbose   Print additional information",
+///     "                  about each step",
+///     "  -o, --output <FILE>",
+///     "                  Write to FILE",
+///     "  -q              Silence all output",
+/// ];
+/// assert_eq!(output.to_string(), expected.join("\n"));
+/// ```
+#[inline]
+pub fn definition_list<I>(pairs: I, term_width: usize) -> DefinitionList<I> {
+    DefinitionList {
+        pairs,
+        term_width,
+        width: 80,
+        indent: 0,
+    }
+}
+
+pub struct DefinitionList<I> {
+    pairs: I,
+    term_width: usize,
+    width: usize,
+    indent: usize,
+}
+
+impl<I> DefinitionList<I> {
+    /// Set the total width that descriptions are wrapped to fit within, including the term
+    /// column and indent. Defaults to 80.
+    #[inline]
+    pub fn width(self, width: usize) -> Self {
+        DefinitionList { width, ..self }
+    }
+
+    /// Write the given number of spaces at the start of every line.
+    #[inline]
+    pub fn indent(self, indent: usize) -> Self {
+        DefinitionList { indent, ..self }
+    }
+}
+
+impl<I, K, V> Display for DefinitionList<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let column = self.indent + self.term_width + 2;
+        let description_width = self.width.saturating_sub(column).max(1);
+
+        for (index, (term, description)) in self.pairs.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+
+            write_repeated(f, ' ', self.indent)?;
+            let mut term_out = TeeWriter::new(&mut *f, WidthCounter::new());
+            write!(term_out, "{}", term)?;
+            let (_, counter) = term_out.into_inner();
+
+            // Padding is only written once the description is, so empty descriptions do not leave
+            // trailing whitespace
+            let pending = if counter.width() > self.term_width {
+                f.write_char('\n')?;
+                column
+            } else {
+                column - self.indent - counter.width()
+            };
+
+            let mut out = Continuation {
+                dst: &mut *f,
+                indent: column,
+                pending,
+            };
+            write!(out, "{}", wrap(description, description_width))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Indents every line after the first by a number of spaces.
+pub(crate) struct Continuation<W> {
+    pub(crate) dst: W,
+    pub(crate) indent: usize,
+    /// Spaces to write before the first text of the first line
+    pub(crate) pending: usize,
+}
+
+impl<W: Write> Write for Continuation<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        write_repeated(&mut self.dst, ' ', self.pending)?;
+        self.pending = 0;
+
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.dst.write_str(first)?;
+        }
+
+        for line in lines {
+            self.dst.write_char('\n')?;
+            write_repeated(&mut self.dst, ' ', self.indent)?;
+            self.dst.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{definition_list, fmt_map};
+    use std::cell::Cell;
+    use std::fmt::{self, Display, Formatter};
+
+    /// A key which is one character wider each time it is formatted.
+    #[derive(Clone, Copy)]
+    struct Growing<'a>(&'a Cell<usize>);
+
+    impl Display for Growing<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            self.0.set(self.0.get() + 1);
+            (0..self.0.get()).try_for_each(|_| f.write_str("k"))
+        }
+    }
+
+    #[test]
+    fn multi_line_values() {
+        let pairs = [("a", "1\n2"), ("bcd", "3")];
+        let output = fmt_map(pairs.iter().copied()).indent(1).to_string();
+        assert_eq!(output, " a:   1\n      2\n bcd: 3");
+        assert_eq!(fmt_map(None::<(&str, &str)>).to_string(), "");
+    }
+
+    #[test]
+    fn key_wider_than_measured() {
+        let calls = Cell::new(0);
+        let pairs = [(Growing(&calls), "v")];
+        let output = fmt_map(pairs.iter().copied()).to_string();
+        assert_eq!(output, "kk:v");
+    }
+
+    #[test]
+    fn definition_list_line_breaks() {
+        let pairs = [("a", "one two\nthree four five"), ("b", "")];
+        let output = definition_list(pairs.iter().copied(), 1).width(12);
+        assert_eq!(output.to_string(), "a  one two\n   three\n   four five\nb");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sorted_by_formatted_key() {
+        let pairs = [(10, 'a'), (9, 'b'), (100, 'c')];
+        let output = fmt_map(pairs.iter().copied()).sorted().to_string();
+        assert_eq!(output, "10:  a\n100: c\n9:   b");
+    }
+}
@@ -0,0 +1,85 @@
+//! Floating point functions which are only provided by `std`. Without the `std` feature, they are
+//! implemented using [libm].
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn round(x: f64) -> f64 {
+    x.round()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn round(x: f64) -> f64 {
+    libm::round(x)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn floor(x: f64) -> f64 {
+    x.floor()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn floor(x: f64) -> f64 {
+    libm::floor(x)
+}
+
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn log10(x: f64) -> f64 {
+    x.log10()
+}
+
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn log10(x: f64) -> f64 {
+    libm::log10(x)
+}
+
+/// Returns `10^exp`.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn pow10(exp: i32) -> f64 {
+    10f64.powi(exp)
+}
+
+/// Returns `10^exp`.
+#[cfg(not(feature = "std"))]
+#[inline]
+pub(crate) fn pow10(exp: i32) -> f64 {
+    libm::pow(10.0, exp as f64)
+}
+
+/// The largest number of significant digits which is meaningful for an f64.
+pub(crate) const MAX_DIGITS: usize = 17;
+
+/// Returns `value * 10^exp`. Large exponents are applied in two steps, since `10^exp` alone would
+/// overflow or underflow for values close to the limits of an f64 (ex: `5e-324 * 10^324`).
+#[inline]
+pub(crate) fn scale_pow10(value: f64, exp: i32) -> f64 {
+    if exp > 300 {
+        value * 1e300 * pow10(exp - 300)
+    } else if exp < -300 {
+        value * 1e-300 * pow10(exp + 300)
+    } else {
+        value * pow10(exp)
+    }
+}
+
+/// Get the decimal exponent of a finite, non-zero value, such that `|value| / 10^exponent` is in
+/// the range `1..10`.
+pub(crate) fn decimal_exponent(value: f64) -> i32 {
+    let abs = value.abs();
+
+    // log10 may be off by one for values close to a power of 10, so correct it
+    let exponent = floor(log10(abs)) as i32;
+    let mantissa = scale_pow10(abs, -exponent);
+    if mantissa >= 10.0 {
+        exponent + 1
+    } else if mantissa < 1.0 {
+        exponent - 1
+    } else {
+        exponent
+    }
+}
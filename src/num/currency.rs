@@ -0,0 +1,136 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use super::fixed::write_minor_units;
+use super::grouped::Integer;
+use super::locale::{NegativeStyle, NumberFormat};
+
+/// Where the currency symbol is placed relative to the amount.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SymbolPosition {
+    /// `$1.00`
+    Before,
+    /// `CHF 1.00`
+    BeforeSpaced,
+    /// `1.00€`
+    After,
+    /// `1.00 €`
+    AfterSpaced,
+}
+
+/// Format an amount of money given as an integer number of minor units (ex: cents). The amount is
+/// split into whole and fractional parts without going through floating point, so no rounding
+/// occurs. The symbol is placed before the amount by default, and any sign is written first.
+/// ```rust
+/// use fmttools::num::{currency, NumberFormat, SymbolPosition};
+///
+/// assert_eq!("$1,234.05", format!("{}", currency(123405, "$", 2)));
+/// assert_eq!("-$0.99", format!("{}", currency(-99, "$", 2)));
+/// assert_eq!("¥1,500", format!("{}", currency(1500, "¥", 0)));
+///
+/// let euros = currency(123405, "€", 2)
+///     .format(NumberFormat::EUROPEAN)
+///     .position(SymbolPosition::AfterSpaced);
+/// assert_eq!("1.234,05 €", format!("{}", euros));
+/// ```
+#[inline]
+pub fn currency<T: Integer>(amount: T, symbol: &str, decimals: u8) -> Currency<'_, T> {
+    Currency {
+        amount,
+        symbol,
+        decimals,
+        format: NumberFormat::ENGLISH,
+        position: SymbolPosition::Before,
+        negative_style: NegativeStyle::Minus,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Currency<'a, T> {
+    amount: T,
+    symbol: &'a str,
+    decimals: u8,
+    format: NumberFormat,
+    position: SymbolPosition,
+    negative_style: NegativeStyle,
+}
+
+impl<T> Currency<'_, T> {
+    /// Set the decimal and group separators used according to a [NumberFormat].
+    #[inline]
+    pub fn format(self, format: NumberFormat) -> Self {
+        Currency { format, ..self }
+    }
+
+    /// Set where the currency symbol is placed.
+    #[inline]
+    pub fn position(self, position: SymbolPosition) -> Self {
+        Currency { position, ..self }
+    }
+
+    /// Set how the sign of the amount is written.
+    /// ```rust
+    /// use fmttools::num::{currency, NegativeStyle};
+    ///
+    /// let loss = currency(-123400, "$", 2).negative_style(NegativeStyle::Parentheses);
+    /// assert_eq!("($1,234.00)", format!("{}", loss));
+    /// ```
+    #[inline]
+    pub fn negative_style(self, negative_style: NegativeStyle) -> Self {
+        Currency {
+            negative_style,
+            ..self
+        }
+    }
+}
+
+impl<T: Integer> Display for Currency<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, magnitude) = self.amount.into_parts();
+        self.negative_style.write_prefix(f, negative)?;
+
+        match self.position {
+            SymbolPosition::Before => f.write_str(self.symbol)?,
+            SymbolPosition::BeforeSpaced => write!(f, "{} ", self.symbol)?,
+            _ => {}
+        }
+
+        write_minor_units(f, magnitude, self.decimals, self.format)?;
+
+        match self.position {
+            SymbolPosition::After => f.write_str(self.symbol)?,
+            SymbolPosition::AfterSpaced => write!(f, " {}", self.symbol)?,
+            _ => {}
+        }
+
+        self.negative_style.write_suffix(f, negative)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{currency, SymbolPosition};
+
+    #[test]
+    fn fraction_is_zero_padded() {
+        assert_eq!(format!("{}", currency(5u8, "$", 3)), "$0.005");
+        assert_eq!(format!("{}", currency(0, "$", 2)), "$0.00");
+    }
+
+    #[test]
+    fn symbol_positions() {
+        let value = currency(-1250, "CHF", 2).position(SymbolPosition::BeforeSpaced);
+        assert_eq!(format!("{}", value), "-CHF 12.50");
+        let value = currency(1250, "kr", 2).position(SymbolPosition::After);
+        assert_eq!(format!("{:+}", value), "+12.50kr");
+    }
+
+    #[test]
+    fn extreme_decimals() {
+        let value = currency(u128::MAX, "", 39);
+        assert_eq!(
+            format!("{}", value),
+            "0.340282366920938463463374607431768211455"
+        );
+    }
+}
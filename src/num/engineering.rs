@@ -0,0 +1,228 @@
+use crate::math::{self, MAX_DIGITS};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// SI prefixes from 10^-30 to 10^30 in steps of 10^3.
+const SI_PREFIXES: [&str; 21] = [
+    "q", "r", "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+    "R", "Q",
+];
+const SI_MIN_EXPONENT: i32 = -30;
+const SI_MAX_EXPONENT: i32 = 30;
+
+/// A finite, non-zero value split into a mantissa and an exponent which is a multiple of 3.
+struct Normalized {
+    mantissa: f64,
+    exponent: i32,
+    decimals: usize,
+}
+
+/// Split a value into a mantissa in the range `1..1000` and an exponent which is a multiple of 3.
+/// The number of decimals required to show the requested number of significant digits is also
+/// computed. Zero, infinite, and NaN values are not supported.
+fn normalize(value: f64, digits: usize) -> Normalized {
+    let digits = digits.clamp(1, MAX_DIGITS);
+    let mut magnitude = math::decimal_exponent(value);
+
+    let mut exponent = magnitude.div_euclid(3) * 3;
+    loop {
+        let mantissa = math::scale_pow10(value, -exponent);
+        let leading = (magnitude - exponent).max(0) as usize;
+        let decimals = (digits - 1).saturating_sub(leading);
+
+        // Rounding may carry into the next power of 1000 (ex: 999.96 -> 1000.0 -> 1.00e3)
+        let scale = math::pow10(decimals as i32);
+        if math::round(mantissa.abs() * scale) / scale >= 1000.0 {
+            exponent += 3;
+            magnitude = exponent;
+            continue;
+        }
+
+        return Normalized {
+            mantissa,
+            exponent,
+            decimals,
+        };
+    }
+}
+
+/// Get the number of decimals required to show a value without trailing zeros, up to the given
+/// maximum number of decimals.
+fn trimmed_decimals(value: f64, decimals: usize) -> usize {
+    let mut decimals = decimals;
+    let mut scaled = math::round(value.abs() * math::pow10(decimals as i32));
+    while decimals > 0 && scaled % 10.0 == 0.0 {
+        scaled /= 10.0;
+        decimals -= 1;
+    }
+
+    decimals
+}
+
+/// Format a number using an SI prefix (ex: `47µ` or `1.5k`) with the given number of significant
+/// digits. Three significant digits are shown by default and trailing zeros are removed. A unit
+/// can be appended directly after the prefix using [Si::unit]. Values outside the range of SI
+/// prefixes (`1e-30` to `1e33`) are written in engineering notation instead (ex: `100e-42`).
+/// ```rust
+/// use fmttools::num::si;
+///
+/// assert_eq!("47µ", format!("{}", si(0.000047)));
+/// assert_eq!("1.23M", format!("{}", si(1_234_567.0)));
+/// assert_eq!("4.7kΩ", format!("{}", si(4700.0).unit("Ω")));
+/// assert_eq!("1.000k", format!("{}", si(1000.0).digits(4).trim_zeros(false)));
+/// ```
+#[inline]
+pub fn si(value: f64) -> Si<'static> {
+    Si {
+        value,
+        digits: 3,
+        trim_zeros: true,
+        unit: "",
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Si<'a> {
+    value: f64,
+    digits: usize,
+    trim_zeros: bool,
+    unit: &'a str,
+}
+
+impl<'a> Si<'a> {
+    /// Set the number of significant digits shown, between 1 and 17.
+    #[inline]
+    pub fn digits(self, digits: usize) -> Self {
+        Si { digits, ..self }
+    }
+
+    /// Set whether trailing zeros after the decimal point are removed.
+    #[inline]
+    pub fn trim_zeros(self, trim_zeros: bool) -> Self {
+        Si { trim_zeros, ..self }
+    }
+
+    /// Append a unit after the SI prefix.
+    #[inline]
+    pub fn unit(self, unit: &str) -> Si<'_> {
+        Si {
+            value: self.value,
+            digits: self.digits,
+            trim_zeros: self.trim_zeros,
+            unit,
+        }
+    }
+}
+
+impl Display for Si<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.value == 0.0 || !self.value.is_finite() {
+            return write!(f, "{}{}", self.value, self.unit);
+        }
+
+        let Normalized {
+            mantissa,
+            exponent,
+            mut decimals,
+        } = normalize(self.value, self.digits);
+        if self.trim_zeros {
+            decimals = trimmed_decimals(mantissa, decimals);
+        }
+
+        if !(SI_MIN_EXPONENT..=SI_MAX_EXPONENT).contains(&exponent) {
+            return write!(f, "{:.*}e{}{}", decimals, mantissa, exponent, self.unit);
+        }
+
+        let prefix = SI_PREFIXES[((exponent - SI_MIN_EXPONENT) / 3) as usize];
+        write!(f, "{:.*}{}{}", decimals, mantissa, prefix, self.unit)
+    }
+}
+
+/// Format a number in engineering notation (ex: `47.0e3`), where the exponent is always a multiple
+/// of 3. Three significant digits are shown by default.
+/// ```rust
+/// use fmttools::num::engineering;
+///
+/// assert_eq!("47.0e3", format!("{}", engineering(47_000.0)));
+/// assert_eq!("-123e-9", format!("{}", engineering(-0.000000123456)));
+/// assert_eq!("1.2346e0", format!("{}", engineering(1.23456).digits(5)));
+/// ```
+#[inline]
+pub fn engineering(value: f64) -> Engineering {
+    Engineering { value, digits: 3 }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Engineering {
+    value: f64,
+    digits: usize,
+}
+
+impl Engineering {
+    /// Set the number of significant digits shown, between 1 and 17.
+    #[inline]
+    pub fn digits(self, digits: usize) -> Self {
+        Engineering { digits, ..self }
+    }
+}
+
+impl Display for Engineering {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.value.is_finite() {
+            return write!(f, "{}", self.value);
+        }
+
+        if self.value == 0.0 {
+            let decimals = self.digits.clamp(1, MAX_DIGITS) - 1;
+            return write!(f, "{:.*}e0", decimals, self.value);
+        }
+
+        let normalized = normalize(self.value, self.digits);
+        write!(
+            f,
+            "{:.*}e{}",
+            normalized.decimals, normalized.mantissa, normalized.exponent
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{engineering, si};
+
+    #[test]
+    fn rounding_carries_to_next_prefix() {
+        assert_eq!(format!("{}", si(999_960.0)), "1M");
+        assert_eq!(format!("{}", engineering(999.96)), "1.00e3");
+    }
+
+    #[test]
+    fn powers_of_ten() {
+        assert_eq!(format!("{}", si(1000.0)), "1k");
+        assert_eq!(format!("{}", si(0.001)), "1m");
+        assert_eq!(format!("{}", engineering(100.0)), "100e0");
+    }
+
+    #[test]
+    fn zero_and_non_finite() {
+        assert_eq!(format!("{}", si(0.0).unit("V")), "0V");
+        assert_eq!(format!("{}", engineering(0.0)), "0.00e0");
+        assert_eq!(format!("{}", engineering(f64::INFINITY)), "inf");
+    }
+
+    #[test]
+    fn out_of_prefix_range() {
+        assert_eq!(format!("{}", si(999e30)), "999Q");
+        assert_eq!(format!("{}", si(1e33)), "1e33");
+        assert_eq!(format!("{}", si(1e-40).unit("F")), "100e-42F");
+        assert_eq!(format!("{}", si(999_960e27)), "1e33");
+    }
+
+    #[test]
+    fn subnormal_values() {
+        assert_eq!(format!("{}", engineering(5e-324)), "4.94e-324");
+        assert_eq!(format!("{}", engineering(-2.5e-310)), "-250e-312");
+        assert_eq!(format!("{}", si(5e-324)), "4.94e-324");
+        assert_eq!(format!("{}", engineering(f64::MAX)), "180e306");
+    }
+}
@@ -0,0 +1,101 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::grouped::{write_grouped, Integer};
+use super::locale::{NegativeStyle, NumberFormat};
+
+/// Format an integer number of minor units as a decimal with `scale` decimal places. No floating
+/// point arithmetic is used, so the output is always exact. Digits are not grouped by default, but
+/// separators can be set using [FixedPoint::format].
+/// ```rust
+/// use fmttools::num::{fixed_point, NumberFormat};
+///
+/// assert_eq!("123.45", format!("{}", fixed_point(12345, 2)));
+/// assert_eq!("-0.005", format!("{}", fixed_point(-5, 3)));
+/// assert_eq!("42", format!("{}", fixed_point(42u8, 0)));
+/// assert_eq!("1.234.567,8", format!("{}", fixed_point(12345678, 1).format(NumberFormat::EUROPEAN)));
+/// ```
+#[inline]
+pub fn fixed_point<T: Integer>(value: T, scale: u8) -> FixedPoint<T> {
+    FixedPoint {
+        value,
+        scale,
+        format: NumberFormat::PLAIN,
+        negative_style: NegativeStyle::Minus,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FixedPoint<T> {
+    value: T,
+    scale: u8,
+    format: NumberFormat,
+    negative_style: NegativeStyle,
+}
+
+impl<T> FixedPoint<T> {
+    /// Set the decimal and group separators used according to a [NumberFormat].
+    #[inline]
+    pub fn format(self, format: NumberFormat) -> Self {
+        FixedPoint { format, ..self }
+    }
+
+    /// Set how the sign of the number is written.
+    #[inline]
+    pub fn negative_style(self, negative_style: NegativeStyle) -> Self {
+        FixedPoint {
+            negative_style,
+            ..self
+        }
+    }
+}
+
+impl<T: Integer> Display for FixedPoint<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, magnitude) = self.value.into_parts();
+        self.negative_style.write_prefix(f, negative)?;
+        write_minor_units(f, magnitude, self.scale, self.format)?;
+        self.negative_style.write_suffix(f, negative)
+    }
+}
+
+/// Write an unsigned number of minor units as a decimal with the given number of decimal places.
+pub(crate) fn write_minor_units<W: Write + ?Sized>(
+    out: &mut W,
+    magnitude: u128,
+    decimals: u8,
+    format: NumberFormat,
+) -> fmt::Result {
+    // 10^39 does not fit in a u128, but every u128 is less than it
+    let (whole, fraction) = match 10u128.checked_pow(u32::from(decimals)) {
+        Some(scale) => (magnitude / scale, magnitude % scale),
+        None => (0, magnitude),
+    };
+
+    write_grouped(out, whole, format.group, usize::from(format.group_size))?;
+    if decimals > 0 {
+        out.write_char(format.decimal)?;
+        write!(out, "{:0width$}", fraction, width = usize::from(decimals))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixed_point;
+
+    #[test]
+    fn fixed_point_extremes() {
+        assert_eq!(
+            format!("{}", fixed_point(i64::MIN, 18)),
+            "-9.223372036854775808"
+        );
+        assert_eq!(format!("{}", fixed_point(0, 2)), "0.00");
+    }
+
+    #[test]
+    fn fixed_point_sign_flag() {
+        assert_eq!(format!("{:+}", fixed_point(150u32, 2)), "+1.50");
+    }
+}
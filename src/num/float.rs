@@ -0,0 +1,109 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Primitive floating point types.
+pub trait Float: Copy + Display + Debug {
+    /// Returns true if the value is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+
+    /// Write the shortest representation of the value which parses back to the same value. The
+    /// output is identical to the value's `Debug` output.
+    fn write_shortest<W: Write>(self, out: &mut W) -> fmt::Result;
+}
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {
+        $(impl Float for $t {
+            #[inline]
+            fn is_finite(self) -> bool {
+                <$t>::is_finite(self)
+            }
+
+            #[cfg(not(feature = "ryu"))]
+            #[inline]
+            fn write_shortest<W: Write>(self, out: &mut W) -> fmt::Result {
+                write!(out, "{:?}", self)
+            }
+
+            #[cfg(feature = "ryu")]
+            #[inline]
+            fn write_shortest<W: Write>(self, out: &mut W) -> fmt::Result {
+                // ryu and std switch to scientific notation at different magnitudes, so only use
+                // ryu within the range where both are written in decimal notation.
+                let abs = self.abs();
+                if abs == 0.0 || (1e-4..1e13).contains(&abs) {
+                    out.write_str(ryu::Buffer::new().format_finite(self))
+                } else {
+                    write!(out, "{:?}", self)
+                }
+            }
+        })*
+    };
+}
+
+impl_float!(f32, f64);
+
+/// Format a float using the fewest digits which parse back to the same value. The output matches
+/// the value's `Debug` output, so whole numbers keep a trailing `.0` and very large or small values
+/// use scientific notation. When the `ryu` feature is enabled, the digits are generated using the
+/// [ryu](https://docs.rs/ryu) crate instead of the standard library.
+/// ```rust
+/// use fmttools::num::float_shortest;
+///
+/// assert_eq!("0.1", format!("{}", float_shortest(0.1f32)));
+/// assert_eq!("0.30000000000000004", format!("{}", float_shortest(0.1 + 0.2)));
+/// assert_eq!("2.0", format!("{}", float_shortest(2.0)));
+/// assert_eq!("1e21", format!("{}", float_shortest(1e21)));
+/// ```
+#[inline]
+pub fn float_shortest<T: Float>(value: T) -> FloatShortest<T> {
+    FloatShortest(value)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FloatShortest<T>(T);
+
+impl<T: Float> Display for FloatShortest<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.write_shortest(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::float_shortest;
+
+    #[test]
+    fn matches_debug() {
+        let values = [
+            0.0,
+            -0.0,
+            1.0,
+            -1.5,
+            1e-4,
+            3.3e-5,
+            1e-5,
+            123456.789,
+            1e12,
+            1e15,
+            1e16,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::NAN,
+            f64::NEG_INFINITY,
+        ];
+
+        for value in values.iter().copied() {
+            assert_eq!(format!("{}", float_shortest(value)), format!("{:?}", value));
+            let value = value as f32;
+            assert_eq!(format!("{}", float_shortest(value)), format!("{:?}", value));
+        }
+    }
+
+    #[test]
+    fn shortest_f32() {
+        assert_eq!(format!("{}", float_shortest(16777216.0f32)), "16777216.0");
+        assert_eq!(format!("{}", float_shortest(1.1f32)), "1.1");
+    }
+}
@@ -0,0 +1,482 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::locale::{NegativeStyle, NumberFormat};
+
+/// Primitive integer types which can be used with the digit grouping adapters.
+pub trait Integer: Copy + Display {
+    /// Split the integer into whether it is negative and its magnitude.
+    fn into_parts(self) -> (bool, u128);
+
+    /// Write the integer in decimal. When the `itoa` feature is enabled, the digits are rendered
+    /// into a stack buffer using the [itoa](https://docs.rs/itoa) crate instead of going through
+    /// the standard formatting machinery.
+    #[inline]
+    fn write_decimal<W: Write + ?Sized>(self, out: &mut W) -> fmt::Result {
+        #[cfg(feature = "itoa")]
+        {
+            let (negative, magnitude) = self.into_parts();
+            if negative {
+                out.write_char('-')?;
+            }
+            out.write_str(itoa::Buffer::new().format(magnitude))
+        }
+
+        #[cfg(not(feature = "itoa"))]
+        write!(out, "{}", self)
+    }
+}
+
+macro_rules! impl_signed_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            #[inline]
+            fn into_parts(self) -> (bool, u128) {
+                (self < 0, self.unsigned_abs() as u128)
+            }
+        })*
+    };
+}
+
+macro_rules! impl_unsigned_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            #[inline]
+            fn into_parts(self) -> (bool, u128) {
+                (false, self as u128)
+            }
+        })*
+    };
+}
+
+impl_signed_integer!(i8, i16, i32, i64, i128, isize);
+impl_unsigned_integer!(u8, u16, u32, u64, u128, usize);
+
+impl<T: Integer> Integer for &T {
+    #[inline]
+    fn into_parts(self) -> (bool, u128) {
+        (*self).into_parts()
+    }
+
+    #[inline]
+    fn write_decimal<W: Write + ?Sized>(self, out: &mut W) -> fmt::Result {
+        (*self).write_decimal(out)
+    }
+}
+
+/// Format an integer with its digits separated into groups of three (ex: `1,234,567`). Digits are
+/// written from most to least significant without any intermediate buffer.
+/// ```rust
+/// use fmttools::num::grouped;
+///
+/// assert_eq!("1,234,567", format!("{}", grouped(1234567)));
+/// assert_eq!("-12 345", format!("{}", grouped(-12345i16).separator(' ')));
+/// assert_eq!("+999", format!("{:+}", grouped(999u64)));
+/// ```
+#[inline]
+pub fn grouped<T: Integer>(value: T) -> Grouped<T> {
+    Grouped {
+        value,
+        format: NumberFormat::ENGLISH,
+        negative_style: NegativeStyle::Minus,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Grouped<T> {
+    value: T,
+    format: NumberFormat,
+    negative_style: NegativeStyle,
+}
+
+impl<T> Grouped<T> {
+    /// Set the character written between groups of digits.
+    #[inline]
+    pub fn separator(mut self, separator: char) -> Self {
+        self.format.group = separator;
+        self
+    }
+
+    /// Set the separators used according to a [NumberFormat].
+    #[inline]
+    pub fn format(self, format: NumberFormat) -> Self {
+        Grouped { format, ..self }
+    }
+
+    /// Set how the sign of the number is written.
+    /// ```rust
+    /// use fmttools::num::{grouped, NegativeStyle};
+    ///
+    /// assert_eq!("(1,234)", format!("{}", grouped(-1234).negative_style(NegativeStyle::Parentheses)));
+    /// assert_eq!("1,234 ", format!("{}", grouped(1234).negative_style(NegativeStyle::Parentheses)));
+    /// ```
+    #[inline]
+    pub fn negative_style(self, negative_style: NegativeStyle) -> Self {
+        Grouped {
+            negative_style,
+            ..self
+        }
+    }
+}
+
+impl<T: Integer> Display for Grouped<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, magnitude) = self.value.into_parts();
+        self.negative_style.write_prefix(f, negative)?;
+        write_grouped(
+            f,
+            magnitude,
+            self.format.group,
+            usize::from(self.format.group_size),
+        )?;
+        self.negative_style.write_suffix(f, negative)
+    }
+}
+
+/// Format a float with the given number of decimal places, separating the digits of the integer
+/// part into groups of three. The digits of the fractional part may also be grouped using
+/// [GroupedFloat::fraction_separator]. The precision of the format string takes priority over the
+/// precision given here.
+/// ```rust
+/// use fmttools::num::grouped_float;
+///
+/// assert_eq!("1,234,567.89", format!("{}", grouped_float(1234567.891, 2)));
+/// assert_eq!("-1,234.5", format!("{:.1}", grouped_float(-1234.5, 3)));
+///
+/// let spaced = grouped_float(1234567.89012, 5).separator(' ').fraction_separator(' ');
+/// assert_eq!("1 234 567.890 12", format!("{}", spaced));
+/// ```
+///
+/// ## Note
+/// The float is formatted twice: once to count the digits of the integer part and once to write
+/// the output.
+#[inline]
+pub fn grouped_float(value: f64, precision: usize) -> GroupedFloat {
+    GroupedFloat {
+        value,
+        precision,
+        format: NumberFormat::ENGLISH,
+        fraction_separator: None,
+        negative_style: NegativeStyle::Minus,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GroupedFloat {
+    value: f64,
+    precision: usize,
+    format: NumberFormat,
+    fraction_separator: Option<char>,
+    negative_style: NegativeStyle,
+}
+
+impl GroupedFloat {
+    /// Set the character written between groups of digits in the integer part.
+    #[inline]
+    pub fn separator(mut self, separator: char) -> Self {
+        self.format.group = separator;
+        self
+    }
+
+    /// Set the decimal and group separators used according to a [NumberFormat]. The fractional
+    /// part is grouped using the same group size.
+    #[inline]
+    pub fn format(self, format: NumberFormat) -> Self {
+        GroupedFloat { format, ..self }
+    }
+
+    /// Group the digits of the fractional part using the given separator.
+    #[inline]
+    pub fn fraction_separator(self, separator: char) -> Self {
+        GroupedFloat {
+            fraction_separator: Some(separator),
+            ..self
+        }
+    }
+
+    /// Set how the sign of the number is written.
+    /// ```rust
+    /// use fmttools::num::{grouped_float, NegativeStyle};
+    ///
+    /// let value = grouped_float(-1234.0, 2).negative_style(NegativeStyle::Parentheses);
+    /// assert_eq!("(1,234.00)", format!("{}", value));
+    /// ```
+    #[inline]
+    pub fn negative_style(self, negative_style: NegativeStyle) -> Self {
+        GroupedFloat {
+            negative_style,
+            ..self
+        }
+    }
+}
+
+impl Display for GroupedFloat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(self.precision);
+
+        let mut counter = IntegerDigitCounter {
+            digits: 0,
+            in_fraction: false,
+        };
+        write!(counter, "{:.*}", precision, self.value)?;
+
+        // The minus sign is written by the float itself unless another style is used
+        let negative = self.value.is_sign_negative() && !self.value.is_nan();
+        match self.negative_style {
+            NegativeStyle::Minus if negative => {}
+            style => style.write_prefix(f, negative)?,
+        }
+
+        let mut writer = FloatGroupWriter {
+            dst: &mut *f,
+            integer_digits: counter.digits,
+            fraction_digits: 0,
+            in_fraction: false,
+            format: self.format,
+            fraction_separator: self.fraction_separator,
+            skip_minus: self.negative_style != NegativeStyle::Minus,
+        };
+        write!(writer, "{:.*}", precision, self.value)?;
+        self.negative_style.write_suffix(f, negative)
+    }
+}
+
+struct IntegerDigitCounter {
+    digits: usize,
+    in_fraction: bool,
+}
+
+impl Write for IntegerDigitCounter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for byte in s.bytes() {
+            match byte {
+                b'.' => self.in_fraction = true,
+                b'0'..=b'9' if !self.in_fraction => self.digits += 1,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Inserts separators into the output of a formatted float. The number of digits in the integer
+/// part must be known in advance.
+struct FloatGroupWriter<W> {
+    dst: W,
+    /// The number of digits in the integer part which have not been written yet
+    integer_digits: usize,
+    /// The number of digits in the fractional part which have been written
+    fraction_digits: usize,
+    in_fraction: bool,
+    format: NumberFormat,
+    fraction_separator: Option<char>,
+    /// Drop the minus sign since the sign is written separately
+    skip_minus: bool,
+}
+
+impl<W: Write> Write for FloatGroupWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let group_size = usize::from(self.format.group_size);
+
+        for c in s.chars() {
+            match c {
+                '.' => {
+                    self.in_fraction = true;
+                    self.dst.write_char(self.format.decimal)?;
+                }
+                '0'..='9' if self.in_fraction => {
+                    if let Some(separator) = self.fraction_separator {
+                        if self.fraction_digits > 0
+                            && group_size > 0
+                            && self.fraction_digits.is_multiple_of(group_size)
+                        {
+                            self.dst.write_char(separator)?;
+                        }
+                    }
+                    self.fraction_digits += 1;
+                    self.dst.write_char(c)?;
+                }
+                '0'..='9' => {
+                    self.dst.write_char(c)?;
+                    self.integer_digits = self.integer_digits.saturating_sub(1);
+                    if self.integer_digits > 0
+                        && group_size > 0
+                        && self.integer_digits.is_multiple_of(group_size)
+                    {
+                        self.dst.write_char(self.format.group)?;
+                    }
+                }
+                '-' if self.skip_minus => {}
+                _ => self.dst.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the digits of an integer, inserting a separator between every `group_size` digits
+/// counting from the least significant digit. A group size of zero disables grouping.
+#[inline]
+pub(crate) fn write_grouped<W: Write + ?Sized>(
+    out: &mut W,
+    value: u128,
+    separator: char,
+    group_size: usize,
+) -> fmt::Result {
+    write_digits(out, value, 10, false, separator, group_size)
+}
+
+/// Write the digits of an integer in the given radix (between 2 and 36), inserting a separator
+/// between every `group_size` digits counting from the least significant digit. A group size of
+/// zero disables grouping.
+pub(crate) fn write_digits<W: Write + ?Sized>(
+    out: &mut W,
+    value: u128,
+    radix: u32,
+    uppercase: bool,
+    separator: char,
+    group_size: usize,
+) -> fmt::Result {
+    #[cfg(feature = "itoa")]
+    {
+        if radix == 10 {
+            let mut buffer = itoa::Buffer::new();
+            return write_grouped_str(out, buffer.format(value), separator, group_size);
+        }
+    }
+
+    let radix = u128::from(radix);
+    let mut digits = 1;
+    let mut divisor = 1u128;
+    while value / divisor >= radix {
+        divisor *= radix;
+        digits += 1;
+    }
+
+    while divisor > 0 {
+        let digit = (value / divisor % radix) as u32;
+        let c = match char::from_digit(digit, radix as u32) {
+            Some(c) if uppercase => c.to_ascii_uppercase(),
+            Some(c) => c,
+            None => unreachable!("digit is always less than the radix"),
+        };
+        out.write_char(c)?;
+
+        digits -= 1;
+        divisor /= radix;
+        if digits > 0 && group_size > 0 && digits % group_size == 0 {
+            out.write_char(separator)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a string of ASCII digits, inserting a separator between every `group_size` digits counting
+/// from the end. A group size of zero disables grouping.
+#[cfg(feature = "itoa")]
+fn write_grouped_str<W: Write + ?Sized>(
+    out: &mut W,
+    digits: &str,
+    separator: char,
+    group_size: usize,
+) -> fmt::Result {
+    if group_size == 0 {
+        return out.write_str(digits);
+    }
+
+    let mut split = match digits.len() % group_size {
+        0 => group_size,
+        first => first,
+    };
+    out.write_str(&digits[..split])?;
+
+    while split < digits.len() {
+        out.write_char(separator)?;
+        out.write_str(&digits[split..split + group_size])?;
+        split += group_size;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{grouped, grouped_float};
+    use crate::num::{NegativeStyle, NumberFormat};
+
+    #[test]
+    fn small_values_are_not_grouped() {
+        assert_eq!(format!("{}", grouped(0u8)), "0");
+        assert_eq!(format!("{}", grouped(-999)), "-999");
+    }
+
+    #[test]
+    fn full_width_groups() {
+        assert_eq!(
+            format!("{}", grouped(u128::MAX).separator('_')),
+            "340_282_366_920_938_463_463_374_607_431_768_211_455"
+        );
+        assert_eq!(format!("{}", grouped(-123456)), "-123,456");
+    }
+
+    #[test]
+    fn group_boundaries() {
+        assert_eq!(format!("{}", grouped(1000)), "1,000");
+        assert_eq!(format!("{}", grouped(100_000)), "100,000");
+    }
+
+    #[test]
+    fn extreme_values() {
+        assert_eq!(format!("{}", grouped(i8::MIN)), "-128");
+        assert_eq!(
+            format!("{}", grouped(u128::MAX)),
+            "340,282,366,920,938,463,463,374,607,431,768,211,455"
+        );
+        assert_eq!(
+            format!("{}", grouped(i128::MIN)),
+            "-170,141,183,460,469,231,731,687,303,715,884,105,728"
+        );
+    }
+
+    #[test]
+    fn float_rounding_adds_digit() {
+        assert_eq!(format!("{}", grouped_float(999.96, 1)), "1,000.0");
+        assert_eq!(format!("{}", grouped_float(-0.5, 0)), "-0");
+    }
+
+    #[test]
+    fn float_non_finite() {
+        assert_eq!(format!("{}", grouped_float(f64::NAN, 2)), "NaN");
+        assert_eq!(format!("{}", grouped_float(f64::NEG_INFINITY, 2)), "-inf");
+    }
+
+    #[test]
+    fn float_fraction_grouping() {
+        let out = grouped_float(0.1234567, 7).fraction_separator('_');
+        assert_eq!(format!("{}", out), "0.123_456_7");
+    }
+
+    #[test]
+    fn locale_formats() {
+        let value = grouped(-1234567).format(NumberFormat::SWISS);
+        assert_eq!(format!("{}", value), "-1'234'567");
+
+        let value = grouped_float(1234.5, 2).format(NumberFormat::FRENCH);
+        assert_eq!(format!("{}", value), "1\u{202f}234,50");
+
+        let value = grouped_float(1234.5678, 4).format(NumberFormat::PLAIN);
+        assert_eq!(format!("{}", value.fraction_separator(' ')), "1234.5678");
+    }
+
+    #[test]
+    fn accounting_negative_zero() {
+        let value = grouped_float(-0.001, 2).negative_style(NegativeStyle::Parentheses);
+        assert_eq!(format!("{}", value), "(0.00)");
+        let value = grouped_float(0.0, 2).negative_style(NegativeStyle::Parentheses);
+        assert_eq!(format!("{:+}", value), "0.00 ");
+    }
+}
@@ -0,0 +1,131 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::grouped::{write_digits, Integer};
+
+/// The base used when writing an integer literal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum LiteralBase {
+    Decimal,
+    Hex,
+    Octal,
+    Binary,
+}
+
+/// Format an integer as a Rust integer literal with digits grouped by underscores, so the output
+/// can be pasted back into source code. Decimal and octal literals are grouped by three digits
+/// while hex and binary literals are grouped by four.
+/// ```rust
+/// use fmttools::num::rust_literal;
+///
+/// assert_eq!("1_000_000", format!("{}", rust_literal(1_000_000)));
+/// assert_eq!("0x1_f4a9", format!("{}", rust_literal(0x1F4A9u32).hex()));
+/// assert_eq!("0x1_F4A9", format!("{}", rust_literal(0x1F4A9u32).hex().uppercase()));
+/// assert_eq!("0b1010_0101", format!("{}", rust_literal(0xA5u8).binary()));
+/// assert_eq!("-0o1_777", format!("{}", rust_literal(-1023i32).octal()));
+/// assert_eq!("255u8", format!("{}", rust_literal(255u8).suffix("u8")));
+/// ```
+#[inline]
+pub fn rust_literal<T: Integer>(value: T) -> RustLiteral<'static, T> {
+    RustLiteral {
+        value,
+        base: LiteralBase::Decimal,
+        uppercase: false,
+        suffix: "",
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RustLiteral<'a, T> {
+    value: T,
+    base: LiteralBase,
+    uppercase: bool,
+    suffix: &'a str,
+}
+
+impl<'a, T> RustLiteral<'a, T> {
+    /// Write the literal in hexadecimal with a `0x` prefix.
+    #[inline]
+    pub fn hex(self) -> Self {
+        RustLiteral {
+            base: LiteralBase::Hex,
+            ..self
+        }
+    }
+
+    /// Write the literal in octal with a `0o` prefix.
+    #[inline]
+    pub fn octal(self) -> Self {
+        RustLiteral {
+            base: LiteralBase::Octal,
+            ..self
+        }
+    }
+
+    /// Write the literal in binary with a `0b` prefix.
+    #[inline]
+    pub fn binary(self) -> Self {
+        RustLiteral {
+            base: LiteralBase::Binary,
+            ..self
+        }
+    }
+
+    /// Use uppercase hexadecimal digits.
+    #[inline]
+    pub fn uppercase(self) -> Self {
+        RustLiteral {
+            uppercase: true,
+            ..self
+        }
+    }
+
+    /// Append a type suffix such as `u32` to the literal.
+    #[inline]
+    pub fn suffix(self, suffix: &str) -> RustLiteral<'_, T> {
+        RustLiteral {
+            value: self.value,
+            base: self.base,
+            uppercase: self.uppercase,
+            suffix,
+        }
+    }
+}
+
+impl<T: Integer> Display for RustLiteral<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, magnitude) = self.value.into_parts();
+        if negative {
+            f.write_char('-')?;
+        }
+
+        let (prefix, radix, group_size) = match self.base {
+            LiteralBase::Decimal => ("", 10, 3),
+            LiteralBase::Hex => ("0x", 16, 4),
+            LiteralBase::Octal => ("0o", 8, 3),
+            LiteralBase::Binary => ("0b", 2, 4),
+        };
+
+        f.write_str(prefix)?;
+        write_digits(f, magnitude, radix, self.uppercase, '_', group_size)?;
+        f.write_str(self.suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rust_literal;
+
+    #[test]
+    fn short_literals_are_not_grouped() {
+        assert_eq!(format!("{}", rust_literal(0)), "0");
+        assert_eq!(format!("{}", rust_literal(0u8).hex()), "0x0");
+        assert_eq!(format!("{}", rust_literal(0xffffu16).hex()), "0xffff");
+    }
+
+    #[test]
+    fn extreme_literals() {
+        let out = rust_literal(i64::MIN).hex().suffix("i64");
+        assert_eq!(format!("{}", out), "-0x8000_0000_0000_0000i64");
+    }
+}
@@ -0,0 +1,101 @@
+use core::fmt;
+use core::fmt::{Formatter, Write};
+
+/// The separators used when writing numbers. This allows numbers to be written according to the
+/// conventions of different locales without depending on a full localization library.
+/// ```rust
+/// use fmttools::num::{grouped_float, NumberFormat};
+///
+/// let value = grouped_float(1234567.891, 2);
+/// assert_eq!("1,234,567.89", format!("{}", value.format(NumberFormat::ENGLISH)));
+/// assert_eq!("1.234.567,89", format!("{}", value.format(NumberFormat::EUROPEAN)));
+///
+/// let custom = NumberFormat { decimal: '.', group: '_', group_size: 4 };
+/// assert_eq!("123_4567.89", format!("{}", value.format(custom)));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NumberFormat {
+    /// The character written between the integer and fractional parts.
+    pub decimal: char,
+    /// The character written between groups of digits in the integer part.
+    pub group: char,
+    /// The number of digits in each group. A group size of zero disables grouping.
+    pub group_size: u8,
+}
+
+impl NumberFormat {
+    /// `1,234,567.89`
+    pub const ENGLISH: NumberFormat = NumberFormat {
+        decimal: '.',
+        group: ',',
+        group_size: 3,
+    };
+
+    /// `1.234.567,89`, as used in much of continental Europe and South America.
+    pub const EUROPEAN: NumberFormat = NumberFormat {
+        decimal: ',',
+        group: '.',
+        group_size: 3,
+    };
+
+    /// `1 234 567,89` using a narrow no-break space (U+202F) between groups.
+    pub const FRENCH: NumberFormat = NumberFormat {
+        decimal: ',',
+        group: '\u{202f}',
+        group_size: 3,
+    };
+
+    /// `1'234'567.89`
+    pub const SWISS: NumberFormat = NumberFormat {
+        decimal: '.',
+        group: '\'',
+        group_size: 3,
+    };
+
+    /// `1234567.89`, matching the output of the standard library.
+    pub const PLAIN: NumberFormat = NumberFormat {
+        decimal: '.',
+        group: ',',
+        group_size: 0,
+    };
+}
+
+impl Default for NumberFormat {
+    #[inline]
+    fn default() -> Self {
+        NumberFormat::ENGLISH
+    }
+}
+
+/// How the sign of a number is written.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum NegativeStyle {
+    /// Negative numbers are prefixed by `-`. Positive numbers are prefixed by `+` if the `+` flag
+    /// is given in the format string.
+    #[default]
+    Minus,
+    /// Negative numbers are surrounded by parentheses, as is common in financial reports. Positive
+    /// numbers are followed by a space so they stay aligned with negative numbers in a column.
+    Parentheses,
+}
+
+impl NegativeStyle {
+    #[inline]
+    pub(crate) fn write_prefix(self, f: &mut Formatter<'_>, negative: bool) -> fmt::Result {
+        match self {
+            NegativeStyle::Minus if negative => f.write_char('-'),
+            NegativeStyle::Minus if f.sign_plus() => f.write_char('+'),
+            NegativeStyle::Parentheses if negative => f.write_char('('),
+            _ => Ok(()),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn write_suffix(self, f: &mut Formatter<'_>, negative: bool) -> fmt::Result {
+        match self {
+            NegativeStyle::Minus => Ok(()),
+            NegativeStyle::Parentheses if negative => f.write_char(')'),
+            NegativeStyle::Parentheses => f.write_char(' '),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Human readable formatting of numbers and quantities.
+mod currency;
+mod engineering;
+mod fixed;
+mod float;
+mod grouped;
+mod literal;
+mod locale;
+mod ordinal;
+mod percent;
+mod radix;
+mod rate;
+mod roman;
+mod sig_figs;
+mod size;
+mod trim;
+
+pub use currency::{currency, Currency, SymbolPosition};
+pub use engineering::{engineering, si, Engineering, Si};
+pub use fixed::{fixed_point, FixedPoint};
+pub use float::{float_shortest, Float, FloatShortest};
+pub use grouped::{grouped, grouped_float, Grouped, GroupedFloat, Integer};
+pub use literal::{rust_literal, RustLiteral};
+pub use locale::{NegativeStyle, NumberFormat};
+pub use ordinal::{ordinal, Ordinal};
+pub use percent::{percent, Percent};
+pub use radix::{radix, Radix};
+pub use rate::{per_second, rate, rate_binary, PerSecond, Rate};
+pub use roman::{roman, Roman, MAX_ROMAN};
+pub use sig_figs::{sig_figs, SigFigs};
+pub use size::{bytes, bytes_binary, ByteSize};
+pub use trim::{trim_zeros, TrimZeros};
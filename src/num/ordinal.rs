@@ -0,0 +1,57 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use super::grouped::Integer;
+
+/// Format an integer as an English ordinal number (ex: `1st`, `22nd`, `113th`).
+/// ```rust
+/// use fmttools::num::ordinal;
+///
+/// assert_eq!("23rd", format!("{}", ordinal(23)));
+/// assert_eq!("11th", format!("{}", ordinal(11u8)));
+/// assert_eq!("101st", format!("{}", ordinal(101)));
+/// ```
+#[inline]
+pub fn ordinal<T: Integer>(value: T) -> Ordinal<T> {
+    Ordinal(value)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ordinal<T>(T);
+
+impl<T: Integer> Display for Ordinal<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, magnitude) = self.0.into_parts();
+
+        // 11, 12, and 13 are exceptions to the usual rules
+        let suffix = match (magnitude % 10, magnitude % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+
+        let sign = if negative { "-" } else { "" };
+        write!(f, "{}{}{}", sign, magnitude, suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ordinal;
+
+    #[test]
+    fn teen_exceptions() {
+        let out: Vec<String> = (10..15).map(|x| ordinal(x).to_string()).collect();
+        assert_eq!(out, ["10th", "11th", "12th", "13th", "14th"]);
+        assert_eq!(format!("{}", ordinal(112)), "112th");
+    }
+
+    #[test]
+    fn zero_and_negative() {
+        assert_eq!(format!("{}", ordinal(0)), "0th");
+        assert_eq!(format!("{}", ordinal(i8::MIN)), "-128th");
+        assert_eq!(format!("{}", ordinal(-2)), "-2nd");
+    }
+}
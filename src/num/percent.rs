@@ -0,0 +1,82 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Format a ratio as a percentage with the given number of decimal places, such that `1.0` is
+/// written as `100%`. The precision of the format string takes priority over the precision given
+/// here.
+/// ```rust
+/// use fmttools::num::percent;
+///
+/// assert_eq!("12.3%", format!("{}", percent(0.12345, 1)));
+/// assert_eq!("12.35%", format!("{:.2}", percent(0.12345, 1)));
+/// assert_eq!("+4%", format!("{}", percent(0.04, 0).plus_sign(true)));
+/// assert_eq!("100.0%", format!("{}", percent(1.2, 1).clamp(0.0, 1.0)));
+/// ```
+#[inline]
+pub fn percent(ratio: f64, precision: usize) -> Percent {
+    Percent {
+        ratio,
+        precision,
+        plus_sign: false,
+        bounds: None,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Percent {
+    ratio: f64,
+    precision: usize,
+    plus_sign: bool,
+    bounds: Option<(f64, f64)>,
+}
+
+impl Percent {
+    /// Write a `+` before positive percentages. This can also be enabled using the `+` flag of the
+    /// format string.
+    #[inline]
+    pub fn plus_sign(self, plus_sign: bool) -> Self {
+        Percent { plus_sign, ..self }
+    }
+
+    /// Restrict the ratio to the range `min..=max` before it is written. The bounds are given as
+    /// ratios, so `clamp(0.0, 1.0)` limits the output to between `0%` and `100%`.
+    #[inline]
+    pub fn clamp(self, min: f64, max: f64) -> Self {
+        Percent {
+            bounds: Some((min, max)),
+            ..self
+        }
+    }
+}
+
+impl Display for Percent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ratio = match self.bounds {
+            Some((min, max)) => self.ratio.max(min).min(max),
+            None => self.ratio,
+        };
+
+        let precision = f.precision().unwrap_or(self.precision);
+        if self.plus_sign || f.sign_plus() {
+            write!(f, "{:+.*}%", precision, ratio * 100.0)
+        } else {
+            write!(f, "{:.*}%", precision, ratio * 100.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::percent;
+
+    #[test]
+    fn negative_percent() {
+        assert_eq!(format!("{}", percent(-0.5, 0).plus_sign(true)), "-50%");
+        assert_eq!(format!("{}", percent(-0.5, 0).clamp(0.0, 1.0)), "0%");
+    }
+
+    #[test]
+    fn sign_flag() {
+        assert_eq!(format!("{:+.1}", percent(0.25, 3)), "+25.0%");
+    }
+}
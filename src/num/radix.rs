@@ -0,0 +1,110 @@
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::grouped::{write_digits, Integer};
+
+/// Format an integer in any base from 2 to 36. Digits above 9 are written using letters, which
+/// are lowercase by default. The `#` flag of the format string writes the conventional prefix for
+/// bases 2, 8, and 16 (`0b`, `0o`, and `0x`). Other prefixes can be given using [Radix::prefix].
+/// ```rust
+/// use fmttools::num::radix;
+///
+/// assert_eq!("zz", format!("{}", radix(1295, 36)));
+/// assert_eq!("ZZ", format!("{}", radix(1295, 36).uppercase()));
+/// assert_eq!("-0x1f", format!("{:#}", radix(-31, 16)));
+/// assert_eq!("b36:1z", format!("{}", radix(71u8, 36).prefix("b36:")));
+/// ```
+///
+/// ## Panics
+/// Panics if the base is not in the range `2..=36`.
+#[inline]
+#[track_caller]
+pub fn radix<T: Integer>(value: T, base: u32) -> Radix<'static, T> {
+    assert!(
+        (2..=36).contains(&base),
+        "radix must be between 2 and 36, but got {}",
+        base
+    );
+
+    Radix {
+        value,
+        base,
+        uppercase: false,
+        prefix: None,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Radix<'a, T> {
+    value: T,
+    base: u32,
+    uppercase: bool,
+    prefix: Option<&'a str>,
+}
+
+impl<'a, T> Radix<'a, T> {
+    /// Use uppercase letters for digits above 9.
+    #[inline]
+    pub fn uppercase(self) -> Self {
+        Radix {
+            uppercase: true,
+            ..self
+        }
+    }
+
+    /// Write the given prefix after the sign and before the digits.
+    #[inline]
+    pub fn prefix(self, prefix: &str) -> Radix<'_, T> {
+        Radix {
+            value: self.value,
+            base: self.base,
+            uppercase: self.uppercase,
+            prefix: Some(prefix),
+        }
+    }
+}
+
+impl<T: Integer> Display for Radix<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, magnitude) = self.value.into_parts();
+        if negative {
+            f.write_char('-')?;
+        } else if f.sign_plus() {
+            f.write_char('+')?;
+        }
+
+        let prefix = match (self.prefix, self.base) {
+            (Some(prefix), _) => prefix,
+            (None, 2) if f.alternate() => "0b",
+            (None, 8) if f.alternate() => "0o",
+            (None, 16) if f.alternate() => "0x",
+            _ => "",
+        };
+
+        f.write_str(prefix)?;
+        write_digits(f, magnitude, self.base, self.uppercase, ',', 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::radix;
+
+    #[test]
+    fn matches_std_radixes() {
+        for value in [0u64, 1, 7, 255, 1 << 40, u64::MAX] {
+            assert_eq!(format!("{}", radix(value, 2)), format!("{:b}", value));
+            assert_eq!(format!("{:#}", radix(value, 8)), format!("{:#o}", value));
+            assert_eq!(
+                format!("{}", radix(value, 16).uppercase()),
+                format!("{:X}", value)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_radix() {
+        let _ = radix(10, 37);
+    }
+}
@@ -0,0 +1,146 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::time::Duration;
+
+use super::size::scale_bytes;
+
+/// Format the rate at which bytes were transferred over a period of time using SI units (see
+/// [crate::num::bytes]). One decimal place is shown by default. The precision can be set using
+/// [Rate::precision] or the precision of the format string.
+/// ```rust
+/// use std::time::Duration;
+/// use fmttools::num::{rate, rate_binary};
+///
+/// assert_eq!("12.4 MB/s", format!("{}", rate(24_800_000, Duration::from_secs(2))));
+/// assert_eq!("1.00 KiB/s", format!("{:.2}", rate_binary(512, Duration::from_millis(500))));
+/// assert_eq!("300 B/s", format!("{}", rate(150, Duration::from_millis(500))));
+/// ```
+///
+/// ## Note
+/// If no time has elapsed, the rate is written as `∞ B/s`.
+#[inline]
+pub fn rate(bytes: u64, elapsed: Duration) -> Rate {
+    Rate {
+        bytes,
+        elapsed,
+        binary: false,
+        precision: 1,
+    }
+}
+
+/// Format the rate at which bytes were transferred using binary units. See [rate].
+#[inline]
+pub fn rate_binary(bytes: u64, elapsed: Duration) -> Rate {
+    Rate {
+        bytes,
+        elapsed,
+        binary: true,
+        precision: 1,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rate {
+    bytes: u64,
+    elapsed: Duration,
+    binary: bool,
+    precision: usize,
+}
+
+impl Rate {
+    /// Set the number of decimal places shown when the rate is not written in bytes. This is
+    /// overridden by the precision of the format string if one is given.
+    #[inline]
+    pub fn precision(self, precision: usize) -> Self {
+        Rate { precision, ..self }
+    }
+}
+
+impl Display for Rate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.elapsed.is_zero() {
+            return f.write_str("∞ B/s");
+        }
+
+        let per_second = self.bytes as f64 / self.elapsed.as_secs_f64();
+        let precision = f.precision().unwrap_or(self.precision);
+        match scale_bytes(per_second, self.binary, precision) {
+            (scaled, "B") => write!(f, "{:.0} B/s", scaled),
+            (scaled, unit) => write!(f, "{:.*} {}/s", precision, scaled, unit),
+        }
+    }
+}
+
+/// Format the number of events per second which occurred over a period of time. One decimal place
+/// is shown by default. The precision can be set using [PerSecond::precision] or the precision of
+/// the format string.
+/// ```rust
+/// use std::time::Duration;
+/// use fmttools::num::per_second;
+///
+/// assert_eq!("2.5/s", format!("{}", per_second(5, Duration::from_secs(2))));
+/// assert_eq!("1500/s", format!("{:.0}", per_second(3, Duration::from_millis(2))));
+/// ```
+///
+/// ## Note
+/// If no time has elapsed, the rate is written as `∞/s`.
+#[inline]
+pub fn per_second(count: u64, elapsed: Duration) -> PerSecond {
+    PerSecond {
+        count,
+        elapsed,
+        precision: 1,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PerSecond {
+    count: u64,
+    elapsed: Duration,
+    precision: usize,
+}
+
+impl PerSecond {
+    /// Set the number of decimal places shown. This is overridden by the precision of the format
+    /// string if one is given.
+    #[inline]
+    pub fn precision(self, precision: usize) -> Self {
+        PerSecond { precision, ..self }
+    }
+}
+
+impl Display for PerSecond {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.elapsed.is_zero() {
+            return f.write_str("∞/s");
+        }
+
+        let per_second = self.count as f64 / self.elapsed.as_secs_f64();
+        let precision = f.precision().unwrap_or(self.precision);
+        write!(f, "{:.*}/s", precision, per_second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{per_second, rate};
+    use std::time::Duration;
+
+    #[test]
+    fn rate_zero_elapsed() {
+        assert_eq!(format!("{}", rate(100, Duration::ZERO)), "∞ B/s");
+        assert_eq!(format!("{}", per_second(100, Duration::ZERO)), "∞/s");
+    }
+
+    #[test]
+    fn rate_precision() {
+        let out = rate(1_234_567, Duration::from_secs(1)).precision(3);
+        assert_eq!(format!("{}", out), "1.235 MB/s");
+    }
+
+    #[test]
+    fn rate_rounds_up_to_kilobytes() {
+        let out = rate(9_997, Duration::from_secs(10));
+        assert_eq!(format!("{}", out), "1.0 kB/s");
+    }
+}
@@ -0,0 +1,101 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+use super::grouped::Integer;
+
+const NUMERALS: [(u128, &str, &str); 13] = [
+    (1000, "M", "m"),
+    (900, "CM", "cm"),
+    (500, "D", "d"),
+    (400, "CD", "cd"),
+    (100, "C", "c"),
+    (90, "XC", "xc"),
+    (50, "L", "l"),
+    (40, "XL", "xl"),
+    (10, "X", "x"),
+    (9, "IX", "ix"),
+    (5, "V", "v"),
+    (4, "IV", "iv"),
+    (1, "I", "i"),
+];
+
+/// The largest value which can be written using standard Roman numerals.
+pub const MAX_ROMAN: u16 = 3999;
+
+/// Format an integer using Roman numerals (ex: `MMXXIV`).
+/// ```rust
+/// use fmttools::num::roman;
+///
+/// assert_eq!("MMXXIV", format!("{}", roman(2024)));
+/// assert_eq!("xiv", format!("{}", roman(14u8).lowercase()));
+/// ```
+///
+/// ## Note
+/// Roman numerals can only represent values from 1 to [MAX_ROMAN]. Values outside of this range
+/// are written as regular decimal numbers instead.
+/// ```rust
+/// use fmttools::num::roman;
+///
+/// assert_eq!("0", format!("{}", roman(0)));
+/// assert_eq!("4000", format!("{}", roman(4000)));
+/// ```
+#[inline]
+pub fn roman<T: Integer>(value: T) -> Roman<T> {
+    Roman {
+        value,
+        lowercase: false,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Roman<T> {
+    value: T,
+    lowercase: bool,
+}
+
+impl<T> Roman<T> {
+    /// Use lowercase numerals (ex: `xiv`).
+    #[inline]
+    pub fn lowercase(self) -> Self {
+        Roman {
+            lowercase: true,
+            ..self
+        }
+    }
+}
+
+impl<T: Integer> Display for Roman<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (negative, mut remaining) = self.value.into_parts();
+        if negative || remaining == 0 || remaining > u128::from(MAX_ROMAN) {
+            let sign = if negative { "-" } else { "" };
+            return write!(f, "{}{}", sign, remaining);
+        }
+
+        for (value, upper, lower) in NUMERALS {
+            while remaining >= value {
+                f.write_str(if self.lowercase { lower } else { upper })?;
+                remaining -= value;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::roman;
+
+    #[test]
+    fn subtractive_forms() {
+        assert_eq!(format!("{}", roman(4)), "IV");
+        assert_eq!(format!("{}", roman(1994)), "MCMXCIV");
+        assert_eq!(format!("{}", roman(3999)), "MMMCMXCIX");
+    }
+
+    #[test]
+    fn negative_values() {
+        assert_eq!(format!("{}", roman(-5)), "-5");
+    }
+}
@@ -0,0 +1,77 @@
+use crate::math::{self, MAX_DIGITS};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Format a float to the given number of significant figures. Fixed notation is used when the
+/// decimal exponent is between -5 and the number of significant figures. Otherwise, scientific
+/// notation is used (similar to `%g` in C). Trailing zeros are kept since they are significant.
+/// ```rust
+/// use fmttools::num::sig_figs;
+///
+/// assert_eq!("3.142", format!("{}", sig_figs(3.14159, 4)));
+/// assert_eq!("123", format!("{}", sig_figs(123.456, 3)));
+/// assert_eq!("1.2e3", format!("{}", sig_figs(1234.5, 2)));
+/// assert_eq!("0.0001000", format!("{}", sig_figs(0.0001, 4)));
+/// assert_eq!("1.235e7", format!("{}", sig_figs(12_345_678.0, 4)));
+/// assert_eq!("1.00e-6", format!("{}", sig_figs(0.000001, 3)));
+/// ```
+#[inline]
+pub fn sig_figs(value: f64, digits: usize) -> SigFigs {
+    SigFigs { value, digits }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SigFigs {
+    value: f64,
+    digits: usize,
+}
+
+impl Display for SigFigs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let digits = self.digits.clamp(1, MAX_DIGITS);
+        if !self.value.is_finite() {
+            return write!(f, "{}", self.value);
+        }
+
+        if self.value == 0.0 {
+            return write!(f, "{:.*}", digits - 1, self.value);
+        }
+
+        // Find the exponent of the value after rounding to the requested number of digits
+        let mut exponent = math::decimal_exponent(self.value);
+        let scaled = math::scale_pow10(self.value.abs(), digits as i32 - 1 - exponent);
+        if math::round(scaled) >= math::pow10(digits as i32) {
+            exponent += 1;
+        }
+
+        if exponent < -5 || exponent >= digits as i32 {
+            write!(f, "{:.*e}", digits - 1, self.value)
+        } else {
+            let decimals = (digits as i32 - 1 - exponent) as usize;
+            write!(f, "{:.*}", decimals, self.value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sig_figs;
+
+    #[test]
+    fn rounding_changes_exponent() {
+        assert_eq!(format!("{}", sig_figs(9.9999, 3)), "10.0");
+        assert_eq!(format!("{}", sig_figs(999.99, 3)), "1.00e3");
+    }
+
+    #[test]
+    fn negative_and_zero() {
+        assert_eq!(format!("{}", sig_figs(-0.012345, 2)), "-0.012");
+        assert_eq!(format!("{}", sig_figs(0.0, 3)), "0.00");
+    }
+
+    #[test]
+    fn subnormal_values() {
+        assert_eq!(format!("{}", sig_figs(5e-324, 3)), "4.94e-324");
+        assert_eq!(format!("{}", sig_figs(1e-310, 2)), "1.0e-310");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::math;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+const DECIMAL_UNITS: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+const BINARY_UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Format a number of bytes using SI units (powers of 1000). Sizes below 1 kB are written as an
+/// exact number of bytes. Otherwise, one decimal place is shown by default. The precision can be
+/// set using [ByteSize::precision] or the precision of the format string.
+/// ```rust
+/// use fmttools::num::bytes;
+///
+/// assert_eq!("1.5 MB", format!("{}", bytes(1_532_000)));
+/// assert_eq!("1.532 MB", format!("{:.3}", bytes(1_532_000)));
+/// assert_eq!("2 MB", format!("{}", bytes(1_532_000).precision(0)));
+/// assert_eq!("999 B", format!("{}", bytes(999)));
+/// ```
+#[inline]
+pub fn bytes(value: u64) -> ByteSize {
+    ByteSize {
+        value,
+        binary: false,
+        precision: 1,
+    }
+}
+
+/// Format a number of bytes using binary units (powers of 1024). See [bytes].
+/// ```rust
+/// use fmttools::num::bytes_binary;
+///
+/// assert_eq!("1.5 KiB", format!("{}", bytes_binary(1536)));
+/// assert_eq!("16.00 EiB", format!("{:.2}", bytes_binary(u64::MAX)));
+/// ```
+#[inline]
+pub fn bytes_binary(value: u64) -> ByteSize {
+    ByteSize {
+        value,
+        binary: true,
+        precision: 1,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ByteSize {
+    value: u64,
+    binary: bool,
+    precision: usize,
+}
+
+impl ByteSize {
+    /// Set the number of decimal places shown when the size is not written in bytes. This is
+    /// overridden by the precision of the format string if one is given.
+    #[inline]
+    pub fn precision(self, precision: usize) -> Self {
+        ByteSize { precision, ..self }
+    }
+}
+
+/// Split a number of bytes into a scaled value and unit, rounded to the given precision.
+pub(crate) fn scale_bytes(value: f64, binary: bool, precision: usize) -> (f64, &'static str) {
+    let (base, units) = if binary {
+        (1024.0, &BINARY_UNITS)
+    } else {
+        (1000.0, &DECIMAL_UNITS)
+    };
+
+    let mut scaled = value;
+    let mut unit = 0;
+    while scaled >= base && unit + 1 < units.len() {
+        scaled /= base;
+        unit += 1;
+    }
+
+    // Rounding may push the value up to the next unit (ex: 999.96 kB -> 1000.0 kB -> 1.0 MB).
+    // Bytes are always written without a fractional part.
+    let factor = match unit {
+        0 => 1.0,
+        _ => math::pow10(precision.min(i32::MAX as usize) as i32),
+    };
+    if unit + 1 < units.len() && math::round(scaled * factor) / factor >= base {
+        scaled /= base;
+        unit += 1;
+    }
+
+    (scaled, units[unit])
+}
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(self.precision);
+        match scale_bytes(self.value as f64, self.binary, precision) {
+            (_, "B") => write!(f, "{} B", self.value),
+            (scaled, unit) => write!(f, "{:.*} {}", precision, scaled, unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bytes, bytes_binary};
+
+    #[test]
+    fn small_sizes_are_exact() {
+        assert_eq!(format!("{:.3}", bytes(0)), "0 B");
+        assert_eq!(format!("{}", bytes_binary(1023)), "1023 B");
+    }
+
+    #[test]
+    fn rounding_moves_to_next_unit() {
+        assert_eq!(format!("{}", bytes(999_960)), "1.0 MB");
+        assert_eq!(format!("{:.2}", bytes(999_960)), "999.96 kB");
+    }
+
+    #[test]
+    fn largest_unit() {
+        assert_eq!(format!("{}", bytes(u64::MAX)), "18.4 EB");
+    }
+}
@@ -0,0 +1,163 @@
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+use super::float::Float;
+
+/// Format a float with any trailing zeros after the decimal point removed. This is applied to the
+/// precision requested by the format string, so `{:.4}` writes at most 4 decimal places. If every
+/// decimal place is removed, the decimal point is removed as well unless
+/// [TrimZeros::keep_decimal] is set.
+/// ```rust
+/// use fmttools::num::trim_zeros;
+///
+/// assert_eq!("3.14", format!("{:.4}", trim_zeros(3.14)));
+/// assert_eq!("3.1416", format!("{:.4}", trim_zeros(3.1415926)));
+/// assert_eq!("4", format!("{:.2}", trim_zeros(4.0)));
+/// assert_eq!("4.0", format!("{:.2}", trim_zeros(4.0).keep_decimal(true)));
+/// assert_eq!("4.0", format!("{}", trim_zeros(4.0f32).keep_decimal(true)));
+/// ```
+#[inline]
+pub fn trim_zeros<T: Float>(value: T) -> TrimZeros<T> {
+    TrimZeros {
+        value,
+        keep_decimal: false,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TrimZeros<T> {
+    value: T,
+    keep_decimal: bool,
+}
+
+impl<T> TrimZeros<T> {
+    /// Keep a single zero after the decimal point for whole numbers (ex: `4.0`).
+    #[inline]
+    pub fn keep_decimal(self, keep_decimal: bool) -> Self {
+        TrimZeros {
+            keep_decimal,
+            ..self
+        }
+    }
+}
+
+impl<T: Float> Display for TrimZeros<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let mut writer = TrimZerosWriter::new(&mut *f, self.keep_decimal);
+        match precision {
+            Some(precision) => write!(writer, "{:.*}", precision, self.value)?,
+            None => write!(writer, "{}", self.value)?,
+        }
+
+        writer.end_fraction()?;
+        let seen_point = writer.seen_point;
+        if self.keep_decimal && !seen_point && self.value.is_finite() {
+            f.write_str(".0")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Withholds zeros in the fractional part of a number until a non-zero digit is found.
+struct TrimZerosWriter<W> {
+    dst: W,
+    in_fraction: bool,
+    seen_point: bool,
+    /// The decimal point has been withheld since no non-zero digits have followed it yet
+    pending_point: bool,
+    pending_zeros: usize,
+    keep_decimal: bool,
+}
+
+impl<W: Write> TrimZerosWriter<W> {
+    fn new(dst: W, keep_decimal: bool) -> Self {
+        TrimZerosWriter {
+            dst,
+            in_fraction: false,
+            seen_point: false,
+            pending_point: false,
+            pending_zeros: 0,
+            keep_decimal,
+        }
+    }
+
+    /// Discard any withheld zeros. This must be called once the number has been written.
+    fn end_fraction(&mut self) -> fmt::Result {
+        if self.pending_point && self.keep_decimal {
+            self.dst.write_str(".0")?;
+        }
+
+        self.in_fraction = false;
+        self.pending_point = false;
+        self.pending_zeros = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for TrimZerosWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '.' if !self.in_fraction => {
+                    self.in_fraction = true;
+                    self.seen_point = true;
+                    self.pending_point = true;
+                }
+                '0' if self.in_fraction => self.pending_zeros += 1,
+                '1'..='9' if self.in_fraction => {
+                    if self.pending_point {
+                        self.dst.write_char('.')?;
+                        self.pending_point = false;
+                    }
+                    write_repeated(&mut self.dst, '0', self.pending_zeros)?;
+                    self.pending_zeros = 0;
+                    self.dst.write_char(c)?;
+                }
+                // The end of the mantissa such as the `e` of an exponent
+                _ if self.in_fraction => {
+                    self.end_fraction()?;
+                    self.dst.write_char(c)?;
+                }
+                _ => self.dst.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::trim_zeros;
+
+    #[test]
+    fn zeros_within_fraction_are_kept() {
+        assert_eq!(format!("{:.5}", trim_zeros(1.00501)), "1.00501");
+        assert_eq!(format!("{:.4}", trim_zeros(100.0)), "100");
+    }
+
+    #[test]
+    fn non_finite() {
+        let out = trim_zeros(f64::NAN).keep_decimal(true);
+        assert_eq!(format!("{:.2}", out), "NaN");
+        assert_eq!(format!("{}", trim_zeros(f64::INFINITY)), "inf");
+    }
+
+    #[test]
+    fn plain_decimal_without_precision() {
+        assert_eq!(format!("{}", trim_zeros(2.5e-7)), "0.00000025");
+        assert_eq!(
+            format!("{}", trim_zeros(1e20).keep_decimal(true)),
+            "100000000000000000000.0"
+        );
+    }
+
+    #[test]
+    fn negative_values() {
+        assert_eq!(format!("{:.3}", trim_zeros(-0.5)), "-0.5");
+        assert_eq!(format!("{:.1}", trim_zeros(-0.01)), "-0");
+    }
+}
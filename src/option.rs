@@ -0,0 +1,162 @@
+//! Adapters for displaying optional values.
+//!
+//! ```rust
+//! use fmttools::display_or;
+//!
+//! struct User {
+//!     name: String,
+//!     email: Option<String>,
+//! }
+//!
+//! let user = User { name: "jdoe".to_string(), email: None };
+//! assert_eq!("jdoe <none>", format!("{} <{}>", user.name, display_or(&user.email, "none")));
+//! ```
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Display the contained value if present, otherwise display a fallback. Format string arguments
+/// are passed to whichever value is written.
+/// ```rust
+/// use fmttools::display_or;
+///
+/// assert_eq!("42", format!("{}", display_or(Some(42), "N/A")));
+/// assert_eq!("  N/A", format!("{:>5}", display_or(None::<i32>, "N/A")));
+/// ```
+#[inline]
+pub fn display_or<T, D>(value: T, fallback: D) -> DisplayOr<T::Value, D>
+where
+    T: IntoOption,
+{
+    DisplayOr {
+        value: value.into_option(),
+        fallback,
+    }
+}
+
+/// Display the contained value if present, otherwise display the value returned by `fallback`.
+/// The fallback is only created when needed.
+/// ```rust
+/// use fmttools::{display_or_else, join};
+///
+/// let defaults = ["a", "b"];
+/// let value = display_or_else(None::<&str>, || join(&defaults, ", "));
+/// assert_eq!("a, b", format!("{}", value));
+/// assert_eq!("x", format!("{}", display_or_else(Some("x"), || join(&defaults, ", "))));
+/// ```
+#[inline]
+pub fn display_or_else<T, F, D>(value: T, fallback: F) -> DisplayOrElse<T::Value, F>
+where
+    T: IntoOption,
+    F: Fn() -> D,
+{
+    DisplayOrElse {
+        value: value.into_option(),
+        fallback,
+    }
+}
+
+/// Display a value only if `cond` is true. Nothing is written otherwise, not even padding.
+/// ```rust
+/// use fmttools::display_if;
+///
+/// let cached = true;
+/// assert_eq!("fetched (cached)", format!("fetched{}", display_if(cached, " (cached)")));
+///
+/// let count = 1;
+/// assert_eq!("1 file", format!("{} file{}", count, display_if(count != 1, 's')));
+/// ```
+#[inline]
+pub fn display_if<T>(cond: bool, value: T) -> DisplayIf<T> {
+    DisplayIf {
+        value: if cond { Some(value) } else { None },
+    }
+}
+
+/// Display the contained value if present. Nothing is written otherwise, not even padding.
+/// ```rust
+/// use fmttools::display_if_some;
+///
+/// let unit: Option<&str> = Some("ms");
+/// assert_eq!("12ms", format!("{}{}", 12, display_if_some(unit)));
+/// assert_eq!("12", format!("{}{}", 12, display_if_some(None::<&str>)));
+/// ```
+#[inline]
+pub fn display_if_some<T: IntoOption>(value: T) -> DisplayIf<T::Value> {
+    DisplayIf {
+        value: value.into_option(),
+    }
+}
+
+/// Conversion into an [Option] so both owned and borrowed options can be displayed.
+pub trait IntoOption {
+    type Value;
+
+    fn into_option(self) -> Option<Self::Value>;
+}
+
+impl<T> IntoOption for Option<T> {
+    type Value = T;
+
+    #[inline]
+    fn into_option(self) -> Option<T> {
+        self
+    }
+}
+
+impl<'a, T> IntoOption for &'a Option<T> {
+    type Value = &'a T;
+
+    #[inline]
+    fn into_option(self) -> Option<&'a T> {
+        self.as_ref()
+    }
+}
+
+pub struct DisplayOr<T, D> {
+    value: Option<T>,
+    fallback: D,
+}
+
+impl<T: Display, D: Display> Display for DisplayOr<T, D> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => value.fmt(f),
+            None => self.fallback.fmt(f),
+        }
+    }
+}
+
+pub struct DisplayIf<T> {
+    value: Option<T>,
+}
+
+impl<T: Display> Display for DisplayIf<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => value.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct DisplayOrElse<T, F> {
+    value: Option<T>,
+    fallback: F,
+}
+
+impl<T, F, D> Display for DisplayOrElse<T, F>
+where
+    T: Display,
+    F: Fn() -> D,
+    D: Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => value.fmt(f),
+            None => (self.fallback)().fmt(f),
+        }
+    }
+}
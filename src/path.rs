@@ -0,0 +1,167 @@
+//! Adapters for displaying file system paths.
+use std::fmt;
+use std::fmt::{Display, Formatter, Write};
+use std::path::{is_separator, Path};
+
+use crate::escape::Placeholder;
+
+/// Display a path, replacing any invalid unicode with U+FFFD (`�`) in the same way as
+/// [Path::display]. Optionally, paths which would be ambiguous when embedded in a message can be
+/// quoted and the separators between components can be normalized.
+/// ```rust
+/// use std::path::Path;
+/// use fmttools::path::path;
+///
+/// let file = Path::new("/tmp/my file.txt");
+/// assert_eq!("/tmp/my file.txt", format!("{}", path(file)));
+/// assert_eq!("\"/tmp/my file.txt\"", format!("{}", path(file).quoted(true)));
+/// assert_eq!("/tmp/plain.txt", format!("{}", path(Path::new("/tmp/plain.txt")).quoted(true)));
+/// assert_eq!("\\tmp\\my file.txt", format!("{}", path(file).separator('\\')));
+/// ```
+#[inline]
+pub fn path(path: &Path) -> PathDisplay<'_> {
+    PathDisplay {
+        path,
+        quoted: false,
+        separator: None,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PathDisplay<'a> {
+    path: &'a Path,
+    quoted: bool,
+    separator: Option<char>,
+}
+
+impl PathDisplay<'_> {
+    /// Wrap the path in double quotes if it is empty or contains whitespace, quotes or control
+    /// characters. Within quotes, `"` and `\` are escaped with a backslash and control characters
+    /// are written as escape sequences (ex: `\x1b`).
+    ///
+    /// ## Note
+    /// The path is formatted twice when quoting is enabled: once to check if it needs to be quoted
+    /// and once to write the output.
+    #[inline]
+    pub fn quoted(self, quoted: bool) -> Self {
+        PathDisplay { quoted, ..self }
+    }
+
+    /// Write every path separator as the given character. On Windows, this replaces both `\` and
+    /// `/`. On other platforms, only `/` is a path separator.
+    #[inline]
+    pub fn separator(self, separator: char) -> Self {
+        PathDisplay {
+            separator: Some(separator),
+            ..self
+        }
+    }
+}
+
+impl Display for PathDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let quote = self.quoted && {
+            let mut checker = NeedsQuotes(self.path.as_os_str().is_empty());
+            write!(checker, "{}", self.path.display())?;
+            checker.0
+        };
+
+        if quote {
+            f.write_char('"')?;
+        }
+
+        let mut writer = PathWriter {
+            dst: &mut *f,
+            escape: quote,
+            separator: self.separator,
+        };
+        write!(writer, "{}", self.path.display())?;
+
+        if quote {
+            f.write_char('"')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks if any character of the output requires the path to be quoted.
+struct NeedsQuotes(bool);
+
+impl Write for NeedsQuotes {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 |= s
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || c == '"' || c == '\'');
+        Ok(())
+    }
+}
+
+struct PathWriter<W> {
+    dst: W,
+    escape: bool,
+    separator: Option<char>,
+}
+
+impl<W: Write> Write for PathWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            // Separators are replaced before escaping so a `\` separator is escaped within quotes
+            let out = match self.separator {
+                Some(separator) if is_separator(c) => separator,
+                _ => c,
+            };
+            let escape = self.escape && (out == '"' || out == '\\' || out.is_control());
+            if !escape && out == c {
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            flushed = index + c.len_utf8();
+
+            if !escape {
+                self.dst.write_char(out)?;
+            } else if out.is_control() {
+                Placeholder::Escape.write(&mut self.dst, out)?;
+            } else {
+                self.dst.write_char('\\')?;
+                self.dst.write_char(out)?;
+            }
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::path;
+    use std::path::Path;
+
+    #[test]
+    fn quoted_escapes() {
+        let out = format!("{}", path(Path::new("a\"b\\c\nd")).quoted(true));
+        assert_eq!(out, "\"a\\\"b\\\\c\\x0ad\"");
+        assert_eq!(format!("{}", path(Path::new("")).quoted(true)), "\"\"");
+    }
+
+    #[test]
+    fn quoted_windows_separator() {
+        let file = Path::new("C:/Program Files/app.exe");
+        let out = format!("{}", path(file).quoted(true).separator('\\'));
+        assert_eq!(out, r#""C:\\Program Files\\app.exe""#);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_unicode() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let file = Path::new(OsStr::from_bytes(b"/tmp/\xff"));
+        assert_eq!(format!("{}", path(file).separator('|')), "|tmp|\u{FFFD}");
+    }
+}
@@ -0,0 +1,9 @@
+//! Traits which are useful to have in scope when composing adapters.
+//!
+//! ```rust
+//! use fmttools::prelude::*;
+//!
+//! assert_eq!("\"HELLO\"", format!("{}", "hello".uppercase().quoted()));
+//! ```
+pub use crate::ext::FmtToolsExt;
+pub use crate::fmt_with::{DebugWith, DisplayWith, ToFormatWith};
@@ -0,0 +1,179 @@
+//! Write metrics in the Prometheus text exposition format.
+//!
+//! ```rust
+//! use std::fmt::Write;
+//! use fmttools::prometheus::prom_metric;
+//!
+//! let mut scrape = String::new();
+//! let requests = [("GET", 1027u64), ("POST", 3)];
+//! for (method, count) in requests.iter() {
+//!     let labels = [("method", method), ("path", &"/api")];
+//!     let metric = prom_metric("http_requests_total", labels.iter().copied(), count);
+//!     writeln!(scrape, "{}", metric).unwrap();
+//! }
+//!
+//! assert_eq!(scrape, "\
+//! http_requests_total{method=\"GET\",path=\"/api\"} 1027
+//! http_requests_total{method=\"POST\",path=\"/api\"} 3
+//! ");
+//! ```
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// Numbers which can be written as the value of a sample.
+///
+/// Integers are written as is. Floats are written using their [Display] output, except that
+/// infinite values are written as `+Inf` and `-Inf` as required by the exposition format.
+pub trait PromValue {
+    fn write_value(&self, out: &mut dyn Write) -> fmt::Result;
+}
+
+macro_rules! impl_prom_value_integer {
+    ($($t:ty),*) => {
+        $(impl PromValue for $t {
+            #[inline]
+            fn write_value(&self, out: &mut dyn Write) -> fmt::Result {
+                write!(out, "{}", self)
+            }
+        })*
+    };
+}
+
+impl_prom_value_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_prom_value_float {
+    ($($t:ty),*) => {
+        $(impl PromValue for $t {
+            #[inline]
+            fn write_value(&self, out: &mut dyn Write) -> fmt::Result {
+                if *self == <$t>::INFINITY {
+                    out.write_str("+Inf")
+                } else if *self == <$t>::NEG_INFINITY {
+                    out.write_str("-Inf")
+                } else {
+                    write!(out, "{}", self)
+                }
+            }
+        })*
+    };
+}
+
+impl_prom_value_float!(f32, f64);
+
+impl<T: PromValue + ?Sized> PromValue for &T {
+    #[inline]
+    fn write_value(&self, out: &mut dyn Write) -> fmt::Result {
+        (**self).write_value(out)
+    }
+}
+
+/// Write a single sample as `name{label="value",...} value`, without a trailing line break.
+/// Label values are escaped, while the metric name and label names are written unchanged. The
+/// braces are omitted when there are no labels. The labels are iterated each time the sample is
+/// formatted, so they must be [Clone].
+/// ```rust
+/// use fmttools::prometheus::prom_metric;
+///
+/// let none: [(&str, &str); 0] = [];
+/// assert_eq!("up 1", format!("{}", prom_metric("up", none, 1)));
+///
+/// let labels = [("path", "C:\\data\n\"tmp\"")];
+/// assert_eq!(
+///     r#"disk_free{path="C:\\data\n\"tmp\""} +Inf"#,
+///     format!("{}", prom_metric("disk_free", labels.iter().copied(), f64::INFINITY))
+/// );
+/// ```
+#[inline]
+pub fn prom_metric<'a, L, V>(name: &'a str, labels: L, value: V) -> PromMetric<'a, L, V> {
+    PromMetric {
+        name,
+        labels,
+        value,
+    }
+}
+
+pub struct PromMetric<'a, L, V> {
+    name: &'a str,
+    labels: L,
+    value: V,
+}
+
+impl<L, K, T, V> Display for PromMetric<'_, L, V>
+where
+    L: IntoIterator<Item = (K, T)> + Clone,
+    K: Display,
+    T: Display,
+    V: PromValue,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name)?;
+
+        let mut labels = self.labels.clone().into_iter().peekable();
+        if labels.peek().is_some() {
+            f.write_char('{')?;
+            for (index, (key, value)) in labels.enumerate() {
+                if index > 0 {
+                    f.write_char(',')?;
+                }
+                write!(f, "{}=\"", key)?;
+                write!(LabelEscaper { dst: &mut *f }, "{}", value)?;
+                f.write_char('"')?;
+            }
+            f.write_char('}')?;
+        }
+
+        f.write_char(' ')?;
+        self.value.write_value(f)
+    }
+}
+
+/// Escapes backslashes, double quotes, and line breaks in label values.
+struct LabelEscaper<W> {
+    dst: W,
+}
+
+impl<W: Write> Write for LabelEscaper<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            let escape = match c {
+                '\\' => "\\\\",
+                '"' => "\\\"",
+                '\n' => "\\n",
+                _ => continue,
+            };
+
+            self.dst.write_str(&s[flushed..index])?;
+            self.dst.write_str(escape)?;
+            flushed = index + 1;
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prom_metric;
+
+    #[test]
+    fn float_values_and_split_labels() {
+        let labels = [("a", format_args!("{}{}", "x\\", "\"y"))];
+        assert_eq!(
+            r#"m{a="x\\\"y"} -Inf"#,
+            format!(
+                "{}",
+                prom_metric("m", labels.iter().copied(), f32::NEG_INFINITY)
+            )
+        );
+        assert_eq!(
+            "m NaN",
+            format!("{}", prom_metric("m", None::<(&str, &str)>, f64::NAN))
+        );
+        assert_eq!(
+            "m 0.25",
+            format!("{}", prom_metric("m", None::<(&str, &str)>, 0.25))
+        );
+    }
+}
@@ -0,0 +1,420 @@
+//! Adapters for hiding sensitive information in formatted output.
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+use crate::debug::{Token, Tokenizer};
+use crate::writer::{write_repeated, CountingWriter};
+
+/// Which characters of the output are left visible by [mask].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MaskStyle {
+    /// Mask every character.
+    All,
+    /// Show the first `n` characters and mask the rest.
+    AllButFirst(usize),
+    /// Show the last `n` characters and mask the rest.
+    AllButLast(usize),
+    /// Show the first `first` and last `last` characters and mask everything in between.
+    Edges { first: usize, last: usize },
+}
+
+impl MaskStyle {
+    /// Returns true if the total length of the output must be known before masking.
+    #[inline]
+    fn needs_length(self) -> bool {
+        matches!(self, MaskStyle::AllButLast(_) | MaskStyle::Edges { .. })
+    }
+
+    /// Get the range of character indices which are masked for an output of the given length.
+    #[inline]
+    fn masked_range(self, length: usize) -> (usize, usize) {
+        match self {
+            MaskStyle::All => (0, usize::MAX),
+            MaskStyle::AllButFirst(n) => (n, usize::MAX),
+            MaskStyle::AllButLast(n) => (0, length.saturating_sub(n)),
+            MaskStyle::Edges { first, last } => (first, length.saturating_sub(last)),
+        }
+    }
+}
+
+/// Replace the characters of a value's formatted output with `*`, except for those left visible
+/// by the [MaskStyle]. No allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::redact::{mask, MaskStyle};
+///
+/// let card = "4111111111111111";
+/// assert_eq!("************1111", format!("{}", mask(card, MaskStyle::AllButLast(4))));
+///
+/// let email = mask("jane@example.com", MaskStyle::Edges { first: 1, last: 12 }).mask_char('#');
+/// assert_eq!("j###@example.com", format!("{}", email));
+/// ```
+///
+/// ## Note
+/// [MaskStyle::All] and [MaskStyle::AllButFirst] are applied while streaming the output. The
+/// other styles need to know the length of the output in advance, so the value is formatted twice:
+/// once to count the characters and once to write them. The value must produce the same output
+/// both times. If fewer characters are written than the style would reveal, the entire output is
+/// shown.
+#[inline]
+pub fn mask<T>(value: T, style: MaskStyle) -> Mask<T> {
+    Mask {
+        value,
+        style,
+        mask_char: '*',
+    }
+}
+
+pub struct Mask<T> {
+    value: T,
+    style: MaskStyle,
+    mask_char: char,
+}
+
+impl<T> Mask<T> {
+    /// Set the character used in place of masked characters.
+    #[inline]
+    pub fn mask_char(self, mask_char: char) -> Self {
+        Mask { mask_char, ..self }
+    }
+
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, mut func: F) -> fmt::Result
+    where
+        F: FnMut(&mut dyn Write) -> fmt::Result,
+    {
+        let length = if self.style.needs_length() {
+            let mut counter = CountingWriter::new();
+            func(&mut counter)?;
+            counter.chars()
+        } else {
+            0
+        };
+
+        let (mask_start, mask_end) = self.style.masked_range(length);
+        let mut writer = MaskWriter {
+            dst: f,
+            position: 0,
+            mask_start,
+            mask_end,
+            mask_char: self.mask_char,
+        };
+        func(&mut writer)
+    }
+}
+
+impl<T: Debug> Debug for Mask<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for Mask<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+struct MaskWriter<W> {
+    dst: W,
+    /// Index of the next character to be written
+    position: usize,
+    mask_start: usize,
+    mask_end: usize,
+    mask_char: char,
+}
+
+impl<W: Write> Write for MaskWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            if self.position >= self.mask_start && self.position < self.mask_end {
+                self.dst.write_str(&s[flushed..index])?;
+                self.dst.write_char(self.mask_char)?;
+                flushed = index + c.len_utf8();
+            }
+            self.position += 1;
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+/// Replace the values of the named fields in a value's [Debug] output with `***`. Fields are
+/// matched by name, either as struct fields (`password: ..`) or as string map keys
+/// (`"password": ..`). Everything up to the next comma or closing bracket is hidden, so fields
+/// holding nested structures are redacted entirely. No allocation is performed as part of this
+/// operation.
+/// ```rust
+/// use fmttools::redact::redact_fields;
+///
+/// #[derive(Debug)]
+/// struct Login {
+///     user: &'static str,
+///     password: &'static str,
+///     token: Option<u64>,
+/// }
+///
+/// let login = Login { user: "jane", password: "hunter2", token: Some(42) };
+/// assert_eq!(
+///     r#"Login { user: "jane", password: ***, token: *** }"#,
+///     format!("{:?}", redact_fields(&login, &["password", "token"]))
+/// );
+/// ```
+///
+/// ## Note
+/// The alternate flag (`{:#?}`) is passed on to the inner value. Field names are compared with the
+/// Debug output, so names which need escaping in a string literal never match a map key.
+#[inline]
+pub fn redact_fields<'a, T>(value: T, fields: &'a [&'a str]) -> RedactFields<'a, T> {
+    RedactFields { value, fields }
+}
+
+pub struct RedactFields<'a, T> {
+    value: T,
+    fields: &'a [&'a str],
+}
+
+impl<T: Debug> RedactFields<'_, T> {
+    fn fmt_impl(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let mut writer = RedactWriter {
+            dst: f,
+            fields: self.fields,
+            tokens: Tokenizer::default(),
+            state: RedactState::Scan,
+            in_word: false,
+            matching: None,
+            spaces: 0,
+        };
+
+        if alternate {
+            write!(writer, "{:#?}", self.value)
+        } else {
+            write!(writer, "{:?}", self.value)
+        }
+    }
+}
+
+impl<T: Debug> Debug for RedactFields<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+impl<T: Debug> Display for RedactFields<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum RedactState {
+    /// Looking for the name of a field
+    Scan,
+    /// The name of a redacted field has just been written and `:` is expected
+    Name,
+    /// After `name:`, waiting for the start of the value
+    Colon,
+    /// Discarding a value, along with the number of brackets left open within it
+    Value(usize),
+}
+
+struct RedactWriter<'a, W> {
+    dst: W,
+    fields: &'a [&'a str],
+    tokens: Tokenizer,
+    state: RedactState,
+    /// If the last character was part of an identifier or string literal
+    in_word: bool,
+    /// The index of a field and the length of the prefix of it matched by the current word so far
+    matching: Option<(usize, usize)>,
+    /// Spaces discarded since the last character of a value
+    spaces: usize,
+}
+
+impl<W: Write> RedactWriter<'_, W> {
+    /// Extend the prefix matched by the current word with another character.
+    fn advance(&self, (field, len): (usize, usize), c: char) -> Option<(usize, usize)> {
+        let prefix = &self.fields[field][..len];
+        self.fields
+            .iter()
+            .position(|x| x.starts_with(prefix) && x[len..].starts_with(c))
+            .map(|index| (index, len + c.len_utf8()))
+    }
+
+    /// Start matching a new word, optionally beginning with a character.
+    fn start_word(&mut self, c: Option<char>) {
+        self.in_word = true;
+        self.matching = match c {
+            _ if self.fields.is_empty() => None,
+            Some(c) => self.advance((0, 0), c),
+            None => Some((0, 0)),
+        };
+    }
+
+    /// Returns true if the current word matches an entire field name.
+    fn matched(&self) -> bool {
+        matches!(self.matching, Some((field, len)) if self.fields[field].len() == len)
+    }
+
+    /// Follow the output while it is not being discarded. Returns the new state.
+    fn scan(&mut self, token: Token, c: char, was_literal: bool) -> RedactState {
+        if token == Token::Literal {
+            if !was_literal {
+                self.start_word(None);
+                if c != '"' {
+                    self.matching = None;
+                }
+            } else if self.tokens.in_literal() {
+                self.matching = self.matching.and_then(|x| self.advance(x, c));
+            } else {
+                self.in_word = false;
+                if self.matched() {
+                    return RedactState::Name;
+                }
+            }
+            return RedactState::Scan;
+        }
+
+        if c.is_alphanumeric() || c == '_' {
+            if self.in_word {
+                self.matching = self.matching.and_then(|x| self.advance(x, c));
+            } else {
+                self.start_word(Some(c));
+            }
+            return RedactState::Scan;
+        }
+
+        let name = self.in_word && self.matched();
+        self.in_word = false;
+        match c {
+            ':' if name => RedactState::Colon,
+            _ => RedactState::Scan,
+        }
+    }
+}
+
+impl<W: Write> Write for RedactWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            let was_literal = self.tokens.in_literal();
+            let token = self.tokens.next(c);
+
+            self.state = match self.state {
+                RedactState::Scan => self.scan(token, c, was_literal),
+                RedactState::Name if c == ':' => RedactState::Colon,
+                RedactState::Name => self.scan(token, c, was_literal),
+                RedactState::Colon if token == Token::Space => RedactState::Colon,
+                RedactState::Colon | RedactState::Value(_) => {
+                    let depth = match self.state {
+                        RedactState::Value(depth) => depth,
+                        _ => {
+                            self.dst.write_str(&s[flushed..index])?;
+                            self.dst.write_str("***")?;
+                            flushed = index;
+                            0
+                        }
+                    };
+
+                    let next = match token {
+                        Token::Open => Some(depth + 1),
+                        Token::Close | Token::Comma if depth == 0 => None,
+                        Token::Close => Some(depth - 1),
+                        Token::Other if depth == 0 && c == '\n' => None,
+                        _ => Some(depth),
+                    };
+
+                    // Discard this character, holding back spaces in case the value ends
+                    self.dst.write_str(&s[flushed..index])?;
+                    flushed = index + c.len_utf8();
+                    match next {
+                        Some(depth) => {
+                            self.spaces = match token {
+                                Token::Space if depth == 0 => self.spaces + 1,
+                                _ => 0,
+                            };
+                            RedactState::Value(depth)
+                        }
+                        None => {
+                            write_repeated(&mut self.dst, ' ', self.spaces)?;
+                            self.spaces = 0;
+                            flushed = index;
+                            RedactState::Scan
+                        }
+                    }
+                }
+            };
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mask, redact_fields, MaskStyle};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn mask_all() {
+        assert_eq!(format!("{}", mask("secret", MaskStyle::All)), "******");
+    }
+
+    #[test]
+    fn mask_all_but_first() {
+        let out = format!(
+            "{}",
+            mask(format_args!("{}-{}", "ab", "cd"), MaskStyle::AllButFirst(3))
+        );
+        assert_eq!(out, "ab-**");
+    }
+
+    #[test]
+    fn mask_multibyte() {
+        let out = format!("{}", mask("héllo wörld", MaskStyle::AllButLast(3)));
+        assert_eq!(out, "********rld");
+    }
+
+    #[test]
+    fn mask_short_input() {
+        assert_eq!(format!("{}", mask("abc", MaskStyle::AllButLast(4))), "abc");
+        let edges = MaskStyle::Edges { first: 2, last: 2 };
+        assert_eq!(format!("{}", mask("abc", edges)), "abc");
+    }
+
+    #[test]
+    fn mask_debug() {
+        let out = format!(
+            "{:?}",
+            mask("token", MaskStyle::Edges { first: 2, last: 1 })
+        );
+        assert_eq!(out, "\"t****\"");
+    }
+
+    #[test]
+    fn redact_nested_values() {
+        let mut map = BTreeMap::new();
+        map.insert("token", vec![(1, "a,]")]);
+        map.insert("tokens", Vec::new());
+        let value = (map, "token: x");
+
+        assert_eq!(
+            r#"({"token": ***, "tokens": []}, "token: x")"#,
+            format!("{:?}", redact_fields(&value, &["to", "token"]))
+        );
+        assert_eq!(
+            "(\n    {\n        \"token\": ***,\n        \"tokens\": [],\n    },\n    \"token: x\",\n)",
+            format!("{:#?}", redact_fields(&value, &["token"]))
+        );
+        assert_eq!(
+            format!("{:?}", value),
+            format!("{:?}", redact_fields(&value, &[]))
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use std::fmt;
-use std::fmt::{Debug, Display, Formatter, Write};
+use crate::truncate::TruncateWriter;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
 
 /// Replace a given pattern while formatting a value. No allocation is performed as
 /// part of this operation.
@@ -14,12 +15,21 @@ use std::fmt::{Debug, Display, Formatter, Write};
 /// let value = FooBar { a: "Bar".to_string() };
 /// assert_eq!("FooBiz { a: \"Biz\" }", format!("{:?}", replace(&value, "Bar", "Biz")));
 /// ```
+///
+/// A precision limits the output to that many characters after replacement, as it does for
+/// strings. Other format string arguments are not passed to the inner value.
+/// ```rust
+/// use fmttools::replace;
+///
+/// assert_eq!("a-b-", format!("{:.4}", replace("a b c", ' ', "-")));
+/// ```
+///
 /// ## Note
 /// The current implementation uses a relatively naive backtracking approach to string replacement
 /// and is only intended for small to medium inputs. For large inputs, you will likely see better
 /// performance using the standard library implementations.
 #[inline]
-pub fn replace<T, P>(value: T, pattern: P, replacement: &str) -> Replace<T, P> {
+pub const fn replace<T, P>(value: T, pattern: P, replacement: &str) -> Replace<'_, T, P> {
     Replace {
         value,
         pattern,
@@ -27,6 +37,7 @@ pub fn replace<T, P>(value: T, pattern: P, replacement: &str) -> Replace<T, P> {
     }
 }
 
+#[derive(Clone)]
 pub struct Replace<'a, T, P> {
     value: T,
     pattern: P,
@@ -40,8 +51,14 @@ where
 {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        self.pattern
-            .fmt_impl(self.replacement, f, |out| write!(out, "{:?}", self.value))
+        let func = |out: &mut dyn Write| write!(out, "{:?}", self.value);
+        match f.precision() {
+            Some(precision) => {
+                let out = TruncateWriter::new(f, precision, "");
+                self.pattern.fmt_impl(self.replacement, out, func)
+            }
+            None => self.pattern.fmt_impl(self.replacement, f, func),
+        }
     }
 }
 
@@ -52,8 +69,14 @@ where
 {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        self.pattern
-            .fmt_impl(self.replacement, f, |out| write!(out, "{}", self.value))
+        let func = |out: &mut dyn Write| write!(out, "{}", self.value);
+        match f.precision() {
+            Some(precision) => {
+                let out = TruncateWriter::new(f, precision, "");
+                self.pattern.fmt_impl(self.replacement, out, func)
+            }
+            None => self.pattern.fmt_impl(self.replacement, f, func),
+        }
     }
 }
 
@@ -119,12 +142,13 @@ impl<'a, W> StrReplacer<'a, W> {
             return 0;
         }
 
-        for offset in 0..self.withheld {
+        let withheld = &self.pattern.as_bytes()[..self.withheld];
+        for offset in 1..self.withheld {
             if !self.pattern.is_char_boundary(offset) {
                 continue;
             }
 
-            if self.pattern[..self.withheld - offset] == self.pattern[offset..self.withheld] {
+            if withheld[..self.withheld - offset] == withheld[offset..] {
                 return self.withheld - offset;
             }
         }
@@ -156,7 +180,9 @@ impl<'a, W: Write> Write for StrReplacer<'a, W> {
 
             // Greedily attempt to match as much as possible
             let overlap_len = s.len().min(self.pattern.len() - self.withheld);
-            if s.as_bytes()[..overlap_len] == self.pattern.as_bytes()[self.withheld..] {
+            if s.as_bytes()[..overlap_len]
+                == self.pattern.as_bytes()[self.withheld..self.withheld + overlap_len]
+            {
                 self.withheld += overlap_len;
                 s = &s[overlap_len..];
                 if self.withheld == self.pattern.len() {
@@ -194,7 +220,7 @@ impl<'a, W: Write> Write for EmptyReplacer<'a, W> {
     }
 }
 
-impl<'a> ReplacePattern for &'a str {
+impl ReplacePattern for &str {
     #[inline]
     fn fmt_impl<W, F>(&self, replacement: &str, out: W, func: F) -> fmt::Result
     where
@@ -245,6 +271,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::no_effect_replace)]
     fn replace_char_same() {
         let out = format!("{}", replace(".abc. defs ... fd.", '.', "."));
         assert_eq!(out, ".abc. defs ... fd.".replace('.', "."));
@@ -266,6 +293,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::no_effect_replace)]
     fn replace_str_same() {
         let out = format!("{}", replace(".abc. defs aaab...aba fda", "ab", "ab"));
         assert_eq!(out, ".abc. defs aaab...aba fda".replace("ab", "ab"));
@@ -313,6 +341,31 @@ mod tests {
         assert_eq!(out, "abcdefg".replace("", "."));
     }
 
+    #[test]
+    fn replace_str_split_across_writes() {
+        let out = format!(
+            "{}",
+            replace(format_args!("{:?}", [1, 23, 4]), "23, 4", "X")
+        );
+        assert_eq!(out, "[1, X]");
+
+        let out = format!(
+            "{}",
+            replace(format_args!("{}{}", "aba", "bac"), "abac", "X")
+        );
+        assert_eq!(out, "ababac".replace("abac", "X"));
+    }
+
+    #[test]
+    fn replace_multibyte_split_across_writes() {
+        let (a, b) = (String::from("ééa"), String::from("b"));
+        let out = format!("{}", replace(format_args!("{}{}", a, b), "éaa", "X"));
+        assert_eq!(out, "ééab");
+
+        let out = format!("{}", replace(format_args!("{}{}", a, "a"), "éaa", "X"));
+        assert_eq!(out, "éX");
+    }
+
     #[test]
     fn replace_str_complex() {
         let out = format!(
@@ -0,0 +1,53 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Display a [Result] without requiring [std::fmt::Debug]. `Ok` values are written directly, while
+/// errors are written after a prefix which defaults to `"error: "` and can be changed using
+/// [DisplayResult::err_prefix].
+/// ```rust
+/// use fmttools::display_result;
+///
+/// let ok: Result<u32, String> = Ok(200);
+/// let err: Result<u32, String> = Err("connection refused".to_string());
+///
+/// assert_eq!("status: 200", format!("status: {}", display_result(&ok)));
+/// assert_eq!("status: error: connection refused", format!("status: {}", display_result(&err)));
+/// assert_eq!("status: failed (connection refused)", format!(
+///     "status: {})",
+///     display_result(&err).err_prefix("failed (")
+/// ));
+/// ```
+#[inline]
+pub fn display_result<T, E>(result: &Result<T, E>) -> DisplayResult<'_, T, E> {
+    DisplayResult {
+        result,
+        prefix: "error: ",
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct DisplayResult<'a, T, E> {
+    result: &'a Result<T, E>,
+    prefix: &'a str,
+}
+
+impl<'a, T, E> DisplayResult<'a, T, E> {
+    /// Set the prefix written before errors.
+    #[inline]
+    pub fn err_prefix(self, prefix: &'a str) -> Self {
+        DisplayResult { prefix, ..self }
+    }
+}
+
+impl<T: Display, E: Display> Display for DisplayResult<'_, T, E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.result {
+            Ok(value) => value.fmt(f),
+            Err(err) => {
+                f.write_str(self.prefix)?;
+                err.fmt(f)
+            }
+        }
+    }
+}
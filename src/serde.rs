@@ -0,0 +1,911 @@
+//! Display any [Serialize](::serde::Serialize) value by serializing it directly into the
+//! formatter. Requires the `serde` feature.
+//!
+//! The adapters in this crate also implement [Serialize](::serde::Serialize) as strings using
+//! [collect_str](::serde::Serializer::collect_str), so they can be placed directly in serialized
+//! values without first being rendered to a [String].
+//!
+//! ```rust
+//! use std::collections::BTreeMap;
+//! use fmttools::serde::{serialize_as_display, Format};
+//!
+//! let mut ports = BTreeMap::new();
+//! ports.insert("http", vec![80, 8080]);
+//! ports.insert("https", vec![443]);
+//!
+//! assert_eq!(
+//!     r#"{"http":[80,8080],"https":[443]}"#,
+//!     format!("{}", serialize_as_display(&ports, Format::Json))
+//! );
+//! ```
+//!
+//! ```rust
+//! use fmttools::join;
+//! use fmttools::serde::{serialize_as_display, Format};
+//!
+//! let ports = [80, 443];
+//! assert_eq!(
+//!     r#"["ports","80, 443"]"#,
+//!     format!("{}", serialize_as_display(&("ports", join(&ports, ", ")), Format::Json))
+//! );
+//! ```
+use crate::json::{json_string, JsonEscaper, ToJson};
+use crate::num::{Float, Integer};
+use ::serde::ser::{self, Impossible, Serialize};
+use core::error;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// The format a value is serialized to by [serialize_as_display].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Compact JSON without any whitespace between tokens. Strings are escaped in the same way as
+    /// [crate::json], and floats which are infinite or NaN are written as `null`.
+    Json,
+}
+
+/// Display a value by serializing it in the given format. The value is serialized each time it
+/// is formatted, and no allocation is performed as part of this operation.
+///
+/// Map keys are written as JSON strings. Strings, characters, booleans, and numbers are accepted
+/// as keys in the same way as `serde_json`, with integers and finite floats written as quoted
+/// numbers.
+///
+/// # Panics
+/// Formatting fails if the value's [Serialize] implementation returns an error, or if a map has
+/// keys which can not be written as JSON strings, such as sequences or non-finite floats. The
+/// output written before the error is not removed, and [ToString::to_string] panics when
+/// formatting fails, so only use this with values which are known to serialize successfully.
+/// ```rust
+/// use fmttools::serde::{serialize_as_display, Format};
+///
+/// let event = ("login", Some(42), [1.5, f64::NAN]);
+/// assert_eq!(
+///     r#"login: ["login",42,[1.5,null]]"#,
+///     format!("{}: {}", event.0, serialize_as_display(&event, Format::Json))
+/// );
+/// ```
+#[inline]
+pub fn serialize_as_display<T: ?Sized>(value: &T, format: Format) -> SerializeDisplay<'_, T> {
+    SerializeDisplay { value, format }
+}
+
+pub struct SerializeDisplay<'a, T: ?Sized> {
+    value: &'a T,
+    format: Format,
+}
+
+impl<T: Serialize + ?Sized> Display for SerializeDisplay<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.format {
+            Format::Json => self
+                .value
+                .serialize(&mut JsonSerializer { out: f })
+                .map_err(|_| fmt::Error),
+        }
+    }
+}
+
+macro_rules! impl_serialize_display {
+    ($([$($generics:tt)*] $t:ty),* $(,)?) => {
+        $(impl<$($generics)*> Serialize for $t
+        where
+            Self: Display,
+        {
+            #[inline]
+            fn serialize<Ser: ser::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+                serializer.collect_str(self)
+            }
+        })*
+    };
+}
+
+impl_serialize_display!(
+    [T] crate::boxed::Boxed<'_, T>,
+    [T, const N: usize] crate::cached::Cached<T, N>,
+    [T] crate::case::Lowercase<T>,
+    [T] crate::case::Uppercase<T>,
+    [T] crate::center::CenterBlock<T>,
+    [R, const N: usize] crate::columns::Columns<'_, R, N>,
+    [T] crate::debug::CompactDebug<T>,
+    [T] crate::debug::HighlightDebug<T>,
+    [T] crate::debug::PrettyDebug<T>,
+    [I] crate::csv::CsvRow<I>,
+    [R] crate::csv::CsvRows<R>,
+    [L, R] crate::either::Either<L, R>,
+    [I] crate::env::EnvBlock<I>,
+    [E: ?Sized] crate::error::ErrorChain<'_, E>,
+    [E: ?Sized] crate::error::ErrorReport<'_, E>,
+    [T] crate::escape::Quoted<T>,
+    [T] crate::escape::Sanitize<'_, T>,
+    [T] crate::escape::ShellQuote<T>,
+    [T] crate::escape::SingleLine<'_, T>,
+    [F] crate::fmt_fn::FmtFn<F>,
+    [T: ?Sized, E: ?Sized] crate::fmt_with::FormatWith<'_, T, E>,
+    [R, const N: usize] crate::grid::Grid<'_, R, N>,
+    [T] crate::gutter::Comment<'_, T>,
+    [T] crate::gutter::Gutter<'_, T>,
+    [I] crate::join::Join<'_, I>,
+    [I, F, S] crate::join::JoinFmt<I, F, S>,
+    [I, F, S] crate::join::JoinFmtAll<I, F, S>,
+    [I] crate::join::JoinIntegers<'_, I>,
+    [I] crate::json::JsonArray<I>,
+    [I] crate::json::JsonObject<I>,
+    [T] crate::json::JsonString<T>,
+    [I] crate::logfmt::Logfmt<I>,
+    [I] crate::map::DefinitionList<I>,
+    [I] crate::map::FmtMap<I>,
+    [T] crate::option::DisplayIf<T>,
+    [T, D] crate::option::DisplayOr<T, D>,
+    [T, F] crate::option::DisplayOrElse<T, F>,
+    [] crate::snippet::Snippet<'_>,
+    [] crate::snapshot::LineDiff<'_>,
+    [T] crate::snapshot::NormalizeForSnapshot<T>,
+    [L, V] crate::prometheus::PromMetric<'_, L, V>,
+    [T] crate::redact::Mask<T>,
+    [T] crate::redact::RedactFields<'_, T>,
+    [T, P] crate::replace::Replace<'_, T, P>,
+    [T, E] crate::result::DisplayResult<'_, T, E>,
+    [T] crate::spec::WithSpec<T>,
+    [T, U] crate::style::Hyperlink<T, U>,
+    [T] crate::style::Styled<T>,
+    [P, T, S] crate::surround::Surround<P, T, S>,
+    [R, const N: usize] crate::table::Table<'_, R, N>,
+    [T] crate::tabs::ExpandTabs<T>,
+    [T] crate::tabs::Unexpand<T>,
+    [T] crate::truncate::TruncateChars<'_, T>,
+    [I] crate::url::PercentEncode<I>,
+    [I] crate::url::QueryString<I>,
+    [T] crate::wrap::Wrap<'_, T>,
+);
+
+impl_serialize_display!(
+    [] crate::bytes::Base32<'_>,
+    [] crate::bytes::Base58<'_>,
+    [] crate::bytes::Base64<'_>,
+    [] crate::bytes::Bits<'_, '_>,
+    [] crate::bytes::Hex<'_>,
+    [] crate::bytes::HexDump<'_>,
+    [] crate::bytes::HexGrouped<'_>,
+    [T] crate::bytes::QuotedPrintable<T>,
+    [] crate::bytes::QuotedPrintableBytes<'_>,
+    [] crate::bytes::Utf8Lossy<'_>,
+    [] crate::bytes::Uuid<'_>,
+    [] crate::chart::HBar,
+    [] crate::chart::Progress,
+    [I] crate::chart::Sparkline<I>,
+);
+
+impl_serialize_display!(
+    [] crate::num::ByteSize,
+    [T] crate::num::Currency<'_, T>,
+    [] crate::num::Engineering,
+    [T] crate::num::FixedPoint<T>,
+    [T] crate::num::FloatShortest<T>,
+    [T] crate::num::Grouped<T>,
+    [] crate::num::GroupedFloat,
+    [T] crate::num::Ordinal<T>,
+    [] crate::num::PerSecond,
+    [] crate::num::Percent,
+    [T] crate::num::Radix<'_, T>,
+    [] crate::num::Rate,
+    [T] crate::num::Roman<T>,
+    [T] crate::num::RustLiteral<'_, T>,
+    [] crate::num::Si<'_>,
+    [] crate::num::SigFigs,
+    [T] crate::num::TrimZeros<T>,
+    [] crate::time::ClockDuration,
+    [] crate::time::HumanDuration,
+    [] crate::time::HumantimeDuration,
+);
+
+#[cfg(feature = "locale")]
+impl_serialize_display!([I] crate::locale::JoinLocale<'_, I>);
+
+#[cfg(feature = "std")]
+impl_serialize_display!(
+    [] crate::path::PathDisplay<'_>,
+    [] crate::time::Ago,
+    [] crate::time::EpochTimestamp<'_>,
+    [] crate::time::Rfc3339,
+);
+
+#[cfg(all(feature = "std", feature = "alloc"))]
+impl_serialize_display!([C] crate::debug::SortedDebug<C>);
+
+/// An error while serializing. Any message is discarded, since formatting can only report that
+/// an error occurred.
+#[derive(Debug)]
+struct Error;
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to serialize value")
+    }
+}
+
+impl error::Error for Error {}
+
+impl ser::Error for Error {
+    #[inline]
+    fn custom<T: Display>(_: T) -> Self {
+        Error
+    }
+}
+
+impl From<fmt::Error> for Error {
+    #[inline]
+    fn from(_: fmt::Error) -> Self {
+        Error
+    }
+}
+
+struct JsonSerializer<W> {
+    out: W,
+}
+
+impl<W: Write> JsonSerializer<W> {
+    #[inline]
+    fn write_integer<I: Integer>(&mut self, value: I) -> Result<(), Error> {
+        Ok(value.write_decimal(&mut self.out)?)
+    }
+
+    #[inline]
+    fn write_float<F: ToJson>(&mut self, value: F) -> Result<(), Error> {
+        Ok(value.write_json(&mut self.out)?)
+    }
+
+    /// Start a compound value, writing the opening of an externally tagged variant if needed.
+    fn begin(
+        &mut self,
+        variant: Option<&'static str>,
+        open: char,
+    ) -> Result<Compound<'_, W>, Error> {
+        if let Some(variant) = variant {
+            write!(self.out, "{{{}:", json_string(variant))?;
+        }
+        self.out.write_char(open)?;
+        Ok(Compound {
+            ser: self,
+            first: true,
+            variant: variant.is_some(),
+        })
+    }
+}
+
+impl<'a, W: Write> ser::Serializer for &'a mut JsonSerializer<W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Compound<'a, W>;
+    type SerializeTuple = Compound<'a, W>;
+    type SerializeTupleStruct = Compound<'a, W>;
+    type SerializeTupleVariant = Compound<'a, W>;
+    type SerializeMap = Compound<'a, W>;
+    type SerializeStruct = Compound<'a, W>;
+    type SerializeStructVariant = Compound<'a, W>;
+
+    #[inline]
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        Ok(self.out.write_str(if v { "true" } else { "false" })?)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.write_integer(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.write_float(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.write_float(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        Ok(write!(self.out, "{}", json_string(v))?)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        Ok(write!(self.out, "{}", json_string(v))?)
+    }
+
+    /// Bytes are written as an array of numbers.
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        use ser::SerializeSeq;
+
+        let mut seq = self.serialize_seq(Some(v.len()))?;
+        for byte in v {
+            seq.serialize_element(byte)?;
+        }
+        seq.end()
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(self.out.write_str("null")?)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(self.out.write_str("null")?)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        write!(self.out, "{{{}:", json_string(variant))?;
+        value.serialize(&mut *self)?;
+        Ok(self.out.write_char('}')?)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Compound<'a, W>, Error> {
+        self.begin(None, '[')
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Compound<'a, W>, Error> {
+        self.begin(None, '[')
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, Error> {
+        self.begin(None, '[')
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, Error> {
+        self.begin(Some(variant), '[')
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Compound<'a, W>, Error> {
+        self.begin(None, '{')
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Compound<'a, W>, Error> {
+        self.begin(None, '{')
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Compound<'a, W>, Error> {
+        self.begin(Some(variant), '{')
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        Ok(write!(self.out, "{}", json_string(value))?)
+    }
+}
+
+/// Writes the elements of an array or the entries of an object.
+struct Compound<'a, W> {
+    ser: &'a mut JsonSerializer<W>,
+    first: bool,
+    /// If the value is wrapped in an object naming its variant
+    variant: bool,
+}
+
+impl<W: Write> Compound<'_, W> {
+    #[inline]
+    fn separator(&mut self) -> Result<(), Error> {
+        if !self.first {
+            self.ser.out.write_char(',')?;
+        }
+        self.first = false;
+        Ok(())
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.separator()?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.separator()?;
+        write!(self.ser.out, "{}:", json_string(key))?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn close(self, close: char) -> Result<(), Error> {
+        self.ser.out.write_char(close)?;
+        if self.variant {
+            self.ser.out.write_char('}')?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> ser::SerializeSeq for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close(']')
+    }
+}
+
+impl<W: Write> ser::SerializeTuple for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close(']')
+    }
+}
+
+impl<W: Write> ser::SerializeTupleStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close(']')
+    }
+}
+
+impl<W: Write> ser::SerializeTupleVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close(']')
+    }
+}
+
+impl<W: Write> ser::SerializeMap for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.separator()?;
+        key.serialize(KeySerializer {
+            out: &mut self.ser.out,
+        })?;
+        Ok(self.ser.out.write_char(':')?)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close('}')
+    }
+}
+
+impl<W: Write> ser::SerializeStruct for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close('}')
+    }
+}
+
+impl<W: Write> ser::SerializeStructVariant for Compound<'_, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.close('}')
+    }
+}
+
+/// Writes map keys, which must be strings in JSON. Numbers, booleans, and characters are written
+/// as strings, while any other type of key, or a float which is infinite or NaN, is an error.
+struct KeySerializer<'a, W> {
+    out: &'a mut W,
+}
+
+impl<W: Write> KeySerializer<'_, W> {
+    #[inline]
+    fn quoted<T: Display>(self, value: T) -> Result<(), Error> {
+        Ok(write!(self.out, "\"{}\"", value)?)
+    }
+
+    #[inline]
+    fn quoted_float<F: Float>(self, value: F) -> Result<(), Error> {
+        if !value.is_finite() {
+            return Err(Error);
+        }
+
+        self.out.write_char('"')?;
+        value.write_shortest(&mut *self.out)?;
+        Ok(self.out.write_char('"')?)
+    }
+}
+
+impl<W: Write> ser::Serializer for KeySerializer<'_, W> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), Error> {
+        self.quoted(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        self.quoted_float(v)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        self.quoted_float(v)
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        Ok(write!(self.out, "{}", json_string(v))?)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        Ok(write!(self.out, "{}", json_string(v))?)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(Error)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(Error)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<(), Error> {
+        Err(Error)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(Error)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Err(Error)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(Error)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(Error)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error)
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, value: &T) -> Result<(), Error> {
+        self.out.write_char('"')?;
+        write!(
+            JsonEscaper {
+                dst: &mut *self.out
+            },
+            "{}",
+            value
+        )?;
+        Ok(self.out.write_char('"')?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{serialize_as_display, Format};
+    use crate::{join, replace};
+    use ::serde::ser::{SerializeStruct, SerializeStructVariant, Serializer};
+    use ::serde::Serialize;
+    use std::collections::BTreeMap;
+
+    struct Point {
+        x: i32,
+        label: &'static str,
+    }
+
+    impl Serialize for Point {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut point = serializer.serialize_struct("Point", 2)?;
+            point.serialize_field("x", &self.x)?;
+            point.serialize_field("label", self.label)?;
+            point.end()
+        }
+    }
+
+    /// A map with keys which can not be placed in a [BTreeMap].
+    struct MapOf<'a, K, V>(&'a [(K, V)]);
+
+    impl<K: Serialize, V: Serialize> Serialize for MapOf<'_, K, V> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+        }
+    }
+
+    enum Shape {
+        Unit,
+        Moved(i32),
+        Line { from: Point },
+    }
+
+    impl Serialize for Shape {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Shape::Unit => serializer.serialize_unit_variant("Shape", 0, "Unit"),
+                Shape::Moved(by) => serializer.serialize_newtype_variant("Shape", 1, "Moved", by),
+                Shape::Line { from } => {
+                    let mut line = serializer.serialize_struct_variant("Shape", 2, "Line", 1)?;
+                    line.serialize_field("from", from)?;
+                    line.end()
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn structs_and_variants() {
+        let shapes = [
+            Shape::Unit,
+            Shape::Moved(-3),
+            Shape::Line {
+                from: Point {
+                    x: 1,
+                    label: "a\"b",
+                },
+            },
+        ];
+        assert_eq!(
+            r#"["Unit",{"Moved":-3},{"Line":{"from":{"x":1,"label":"a\"b"}}}]"#,
+            serialize_as_display(&shapes, Format::Json).to_string()
+        );
+    }
+
+    #[test]
+    fn map_keys() {
+        let mut map = BTreeMap::new();
+        map.insert(1u8, ());
+        map.insert(2u8, ());
+        let output = serialize_as_display(&map, Format::Json).to_string();
+        assert_eq!(r#"{"1":null,"2":null}"#, output);
+
+        let map = [(1.5f64, 'a'), (-2.0, 'b')];
+        let output = serialize_as_display(&MapOf(&map), Format::Json).to_string();
+        assert_eq!(r#"{"1.5":"a","-2.0":"b"}"#, output);
+
+        let map = [(0.25f32, true)];
+        let output = serialize_as_display(&MapOf(&map), Format::Json).to_string();
+        assert_eq!(r#"{"0.25":true}"#, output);
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_keys_not_strings() {
+        let mut map = BTreeMap::new();
+        map.insert(vec![1], 1);
+        let _ = serialize_as_display(&map, Format::Json).to_string();
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_keys_not_finite() {
+        let map = [(f64::NAN, 1)];
+        let _ = serialize_as_display(&MapOf(&map), Format::Json).to_string();
+    }
+
+    #[test]
+    fn adapters_serialize_as_strings() {
+        let names = ["a\"b", "c"];
+        let value = (replace(&"x-y", '-', "\n"), join(&names, ", "));
+        assert_eq!(
+            r#"["x\ny","a\"b, c"]"#,
+            serialize_as_display(&value, Format::Json).to_string()
+        );
+    }
+}
@@ -0,0 +1,613 @@
+//! Helpers for comparing formatted output in snapshot and golden tests.
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::str;
+
+/// Replace fragments of a value's output which change between runs with stable placeholders, so
+/// the output can be compared against a saved snapshot. No allocation is performed as part of this
+/// operation.
+///
+/// The following fragments are recognized, and each can be turned off using the builder methods:
+///  - Hex addresses, as written by `{:p}`, are replaced with `[address]`. Only `0x` followed by 8
+///    to 16 hex digits is recognized, so short hex numbers are left alone.
+///  - ISO 8601 date-times such as `2024-05-01T12:30:00.25+02:00` are replaced with `[timestamp]`.
+///    A space may be used in place of the `T`, but dates without a time are not recognized.
+///  - Paths within the Unix temporary directories `/tmp`, `/var/tmp`, and the `/var/folders`
+///    directories used on macOS are replaced with `[temp]` up to the end of the first component
+///    after the temporary directory, so `/tmp/.tmpA1b2C3/out.txt` becomes `[temp]/out.txt`.
+///
+/// Fragments must not be part of a longer word. For example, `v2024-05-01T12:30:00` is left alone.
+/// ```rust
+/// use fmttools::snapshot::normalize_for_snapshot;
+///
+/// let value = 5;
+/// let output = format_args!(
+///     "{:p} saved to \"/tmp/.tmpA1b2C3/out.txt\" at 2024-05-01T12:30:00.25Z",
+///     &value
+/// );
+/// assert_eq!(
+///     "[address] saved to \"[temp]/out.txt\" at [timestamp]",
+///     format!("{}", normalize_for_snapshot(output))
+/// );
+/// assert_eq!(
+///     "0x0000ffff at [timestamp]",
+///     format!("{}", normalize_for_snapshot("0x0000ffff at 2024-05-01 12:30:00").addresses(false))
+/// );
+/// ```
+///
+/// ## Note
+/// Possible matches are held in a buffer of 128 bytes while they are being recognized. Longer
+/// fragments, such as temporary paths with very long names, may not be replaced.
+#[inline]
+pub fn normalize_for_snapshot<T>(value: T) -> NormalizeForSnapshot<T> {
+    NormalizeForSnapshot {
+        value,
+        recognizers: Recognizers {
+            addresses: true,
+            timestamps: true,
+            temp_paths: true,
+        },
+    }
+}
+
+pub struct NormalizeForSnapshot<T> {
+    value: T,
+    recognizers: Recognizers,
+}
+
+impl<T> NormalizeForSnapshot<T> {
+    /// Set whether hex addresses are replaced with `[address]`. Defaults to true.
+    #[inline]
+    pub fn addresses(mut self, enabled: bool) -> Self {
+        self.recognizers.addresses = enabled;
+        self
+    }
+
+    /// Set whether ISO 8601 date-times are replaced with `[timestamp]`. Defaults to true.
+    #[inline]
+    pub fn timestamps(mut self, enabled: bool) -> Self {
+        self.recognizers.timestamps = enabled;
+        self
+    }
+
+    /// Set whether paths within temporary directories are replaced with `[temp]`. Defaults to true.
+    #[inline]
+    pub fn temp_paths(mut self, enabled: bool) -> Self {
+        self.recognizers.temp_paths = enabled;
+        self
+    }
+
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut NormalizeWriter<&mut Formatter<'_>>) -> fmt::Result,
+    {
+        let mut writer = NormalizeWriter {
+            dst: f,
+            recognizers: self.recognizers,
+            buffer: [0; BUFFER_LEN],
+            len: 0,
+            matched: None,
+            previous: ' ',
+        };
+        func(&mut writer)?;
+
+        while writer.len > 0 {
+            writer.resolve(true)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Debug> Debug for NormalizeForSnapshot<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for NormalizeForSnapshot<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Recognizers {
+    addresses: bool,
+    timestamps: bool,
+    temp_paths: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Fragment {
+    Address,
+    Timestamp,
+    TempPath,
+}
+
+impl Fragment {
+    fn placeholder(self) -> &'static str {
+        match self {
+            Fragment::Address => "[address]",
+            Fragment::Timestamp => "[timestamp]",
+            Fragment::TempPath => "[temp]",
+        }
+    }
+}
+
+/// How a piece of text compares to a fragment.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Scan {
+    /// The text can not be extended to match
+    Invalid,
+    /// The text does not match, but more text could make it match
+    Partial,
+    /// The text matches, and more text may also match
+    Complete,
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Returns true for characters which can be part of a path written in plain text.
+fn is_path_char(c: char) -> bool {
+    !c.is_whitespace()
+        && !matches!(
+            c,
+            '"' | '\''
+                | '`'
+                | '\\'
+                | ','
+                | ';'
+                | ':'
+                | '='
+                | '('
+                | ')'
+                | '['
+                | ']'
+                | '{'
+                | '}'
+                | '<'
+                | '>'
+        )
+}
+
+fn scan_address(text: &str) -> Scan {
+    let digits = match text.strip_prefix("0x") {
+        Some(digits) => digits,
+        None if text == "0" => return Scan::Partial,
+        None => return Scan::Invalid,
+    };
+
+    if !digits.bytes().all(|x| x.is_ascii_hexdigit()) {
+        return Scan::Invalid;
+    }
+
+    match digits.len() {
+        0..=7 => Scan::Partial,
+        8..=16 => Scan::Complete,
+        _ => Scan::Invalid,
+    }
+}
+
+/// Compare text to a template where `0` stands for any ASCII digit and `T` for either `T` or a
+/// space.
+fn scan_template(text: &[u8], template: &[u8]) -> Scan {
+    let matches = text
+        .iter()
+        .zip(template)
+        .all(|(&x, &expected)| match expected {
+            b'0' => x.is_ascii_digit(),
+            b'T' => x == b'T' || x == b' ',
+            _ => x == expected,
+        });
+
+    match (matches, text.len().cmp(&template.len())) {
+        (false, _) | (_, Ordering::Greater) => Scan::Invalid,
+        (true, Ordering::Less) => Scan::Partial,
+        (true, Ordering::Equal) => Scan::Complete,
+    }
+}
+
+fn scan_timestamp(text: &str) -> Scan {
+    const DATE_TIME: &[u8] = b"0000-00-00T00:00:00";
+
+    let bytes = text.as_bytes();
+    if bytes.len() <= DATE_TIME.len() {
+        return scan_template(bytes, DATE_TIME);
+    } else if scan_template(&bytes[..DATE_TIME.len()], DATE_TIME) != Scan::Complete {
+        return Scan::Invalid;
+    }
+
+    let mut rest = &bytes[DATE_TIME.len()..];
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let digits = fraction.iter().take_while(|x| x.is_ascii_digit()).count();
+        if fraction.is_empty() {
+            return Scan::Partial;
+        } else if digits == 0 {
+            return Scan::Invalid;
+        }
+        rest = &fraction[digits..];
+    }
+
+    match rest {
+        [] | [b'Z'] => Scan::Complete,
+        [b'+', offset @ ..] | [b'-', offset @ ..] => scan_template(offset, b"00:00"),
+        _ => Scan::Invalid,
+    }
+}
+
+fn scan_temp_path(text: &str) -> Scan {
+    /// Temporary directories, along with the number of components after them which are replaced
+    const TEMP_ROOTS: &[(&str, usize)] = &[
+        ("/tmp/", 1),
+        ("/var/tmp/", 1),
+        ("/var/folders/", 4),
+        ("/private/var/folders/", 4),
+    ];
+
+    let mut result = Scan::Invalid;
+    for &(root, components) in TEMP_ROOTS {
+        let scan = match text.strip_prefix(root) {
+            None if root.starts_with(text) => Scan::Partial,
+            None => Scan::Invalid,
+            Some(rest) if !rest.chars().all(is_path_char) => Scan::Invalid,
+            Some(rest) => {
+                let count = rest.split('/').count();
+                let empty = rest.split('/').rev().skip(1).any(str::is_empty);
+                match count.cmp(&components) {
+                    _ if empty => Scan::Invalid,
+                    Ordering::Less => Scan::Partial,
+                    Ordering::Equal if rest.is_empty() || rest.ends_with('/') => Scan::Partial,
+                    Ordering::Equal => Scan::Complete,
+                    Ordering::Greater => Scan::Invalid,
+                }
+            }
+        };
+
+        if scan == Scan::Complete {
+            return scan;
+        } else if scan == Scan::Partial {
+            result = scan;
+        }
+    }
+
+    result
+}
+
+const BUFFER_LEN: usize = 128;
+
+struct NormalizeWriter<W> {
+    dst: W,
+    recognizers: Recognizers,
+    /// Text held back while it may be the start of a fragment
+    buffer: [u8; BUFFER_LEN],
+    len: usize,
+    /// The length and kind of the longest fragment at the start of the buffer
+    matched: Option<(usize, Fragment)>,
+    /// The last character before the buffer
+    previous: char,
+}
+
+impl<W: Write> NormalizeWriter<W> {
+    /// Check the buffered text against each fragment. Returns whether the text could still be
+    /// extended to a match, and the fragment it currently matches, if any.
+    fn scan(&self, text: &str) -> (bool, Option<Fragment>) {
+        let scans = [
+            (
+                Fragment::Address,
+                self.recognizers.addresses && !is_word(self.previous),
+                scan_address as fn(&str) -> Scan,
+            ),
+            (
+                Fragment::Timestamp,
+                self.recognizers.timestamps && !is_word(self.previous),
+                scan_timestamp,
+            ),
+            (
+                Fragment::TempPath,
+                self.recognizers.temp_paths && !is_path_char(self.previous),
+                scan_temp_path,
+            ),
+        ];
+
+        let mut alive = false;
+        let mut matched = None;
+        for &(fragment, enabled, scan) in &scans {
+            match scan(text) {
+                _ if !enabled => {}
+                Scan::Invalid => {}
+                Scan::Partial => alive = true,
+                Scan::Complete => {
+                    alive = true;
+                    matched = Some(fragment);
+                }
+            }
+        }
+
+        (alive, matched)
+    }
+
+    fn push(&mut self, c: char) -> fmt::Result {
+        if self.len == 0 && !c.is_ascii_digit() && c != '/' {
+            self.previous = c;
+            return self.dst.write_char(c);
+        }
+
+        while self.len + c.len_utf8() > BUFFER_LEN {
+            self.resolve(false)?;
+        }
+
+        c.encode_utf8(&mut self.buffer[self.len..]);
+        self.len += c.len_utf8();
+
+        let text = str::from_utf8(&self.buffer[..self.len]).expect("only chars are buffered");
+        match self.scan(text) {
+            (_, Some(fragment)) => self.matched = Some((self.len, fragment)),
+            (true, None) => {}
+            (false, None) => self.resolve(false)?,
+        }
+        Ok(())
+    }
+
+    /// Write the start of the buffer, either as the placeholder for a matched fragment or as a
+    /// single character, then process the rest of the buffer again. A fragment which reaches the
+    /// end of the buffer is only accepted at the end of the output, since it may continue.
+    fn resolve(&mut self, at_end: bool) -> fmt::Result {
+        let buffer = self.buffer;
+        let text = str::from_utf8(&buffer[..self.len]).expect("only chars are buffered");
+        self.len = 0;
+
+        let matched = self.matched.take().filter(|&(len, _)| {
+            text[len..]
+                .chars()
+                .next()
+                .map_or(at_end, |next| !is_word(next))
+        });
+
+        let rest = match matched {
+            Some((len, fragment)) => {
+                self.dst.write_str(fragment.placeholder())?;
+                self.previous = text[..len].chars().next_back().unwrap_or(self.previous);
+                &text[len..]
+            }
+            None => {
+                let mut chars = text.chars();
+                if let Some(c) = chars.next() {
+                    self.dst.write_char(c)?;
+                    self.previous = c;
+                }
+                chars.as_str()
+            }
+        };
+
+        for c in rest.chars() {
+            self.push(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for NormalizeWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            if self.len == 0 && !c.is_ascii_digit() && c != '/' {
+                self.previous = c;
+                continue;
+            }
+
+            self.dst.write_str(&s[flushed..index])?;
+            flushed = index + c.len_utf8();
+            self.push(c)?;
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+/// Show the differences between two pieces of text line by line. Lines only in `expected` start
+/// with `- `, lines only in `actual` start with `+ `, and lines in both start with two spaces. No
+/// allocation is performed as part of this operation. This is used by [crate::assert_fmt_eq] to report
+/// mismatches.
+///
+/// Lines are split on `\n` only, so a missing trailing line break shows up as an extra empty line.
+/// ```rust
+/// use fmttools::snapshot::line_diff;
+///
+/// let diff = line_diff("a\nb\nc\nd", "a\nB\nc\nd\ne");
+/// assert_eq!("  a\n- b\n+ B\n  c\n  d\n+ e", format!("{}", diff));
+/// ```
+///
+/// ## Note
+/// After each difference, the nearest pair of equal lines is searched for by comparing lines
+/// again from the start of the difference. This takes time quadratic in the size of the difference
+/// and does not always find the smallest diff, so it is only intended for test output.
+#[inline]
+pub fn line_diff<'a>(expected: &'a str, actual: &'a str) -> LineDiff<'a> {
+    LineDiff { expected, actual }
+}
+
+pub struct LineDiff<'a> {
+    expected: &'a str,
+    actual: &'a str,
+}
+
+impl LineDiff<'_> {
+    /// Find the number of lines to skip in each of `expected` and `actual` to reach the nearest
+    /// pair of equal lines. If there is none, all of the remaining lines are skipped.
+    fn resync<'a, I>(expected: &I, actual: &I) -> (usize, usize)
+    where
+        I: Iterator<Item = &'a str> + Clone,
+    {
+        let expected_len = expected.clone().count();
+        let actual_len = actual.clone().count();
+
+        for distance in 1..expected_len + actual_len {
+            for skip_expected in distance.saturating_sub(actual_len)..=distance.min(expected_len) {
+                let skip_actual = distance - skip_expected;
+                let line = expected.clone().nth(skip_expected);
+                if line.is_some() && line == actual.clone().nth(skip_actual) {
+                    return (skip_expected, skip_actual);
+                }
+            }
+        }
+
+        (expected_len, actual_len)
+    }
+}
+
+impl Display for LineDiff<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut expected = self.expected.split('\n');
+        let mut actual = self.actual.split('\n');
+        let mut first = true;
+        let mut write_line = |f: &mut Formatter<'_>, marker: &str, line: &str| {
+            if !first {
+                f.write_char('\n')?;
+            }
+            first = false;
+            write!(f, "{}{}", marker, line)
+        };
+
+        loop {
+            match (expected.clone().next(), actual.clone().next()) {
+                (None, None) => return Ok(()),
+                (Some(a), Some(b)) if a == b => {
+                    write_line(f, "  ", a)?;
+                    expected.next();
+                    actual.next();
+                }
+                _ => {
+                    let (skip_expected, skip_actual) = Self::resync(&expected, &actual);
+                    for line in expected.by_ref().take(skip_expected) {
+                        write_line(f, "- ", line)?;
+                    }
+                    for line in actual.by_ref().take(skip_actual) {
+                        write_line(f, "+ ", line)?;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Assert that the [Display] output of a value is equal to an expected string. On failure, the
+/// panic message shows the differences line by line using [line_diff], instead of two escaped
+/// strings. Like [assert_eq], a custom message can be given after the expected string.
+/// ```rust
+/// use fmttools::{assert_fmt_eq, join};
+///
+/// assert_fmt_eq!(join(&[1, 2, 3], "\n"), "1\n2\n3");
+///
+/// // Other formats can be checked using format_args!
+/// assert_fmt_eq!(format_args!("{:?}", Some("a")), r#"Some("a")"#, "while checking {}", "Some");
+/// ```
+///
+/// The panic message of a failed assertion looks like this:
+/// ```text
+/// formatted output does not match (- expected, + actual)
+///   1
+/// - 2
+/// + 20
+///   3
+/// ```
+#[macro_export]
+macro_rules! assert_fmt_eq {
+    ($value:expr, $expected:expr $(,)?) => {
+        match (&$value, &$expected) {
+            (value, expected) => {
+                let actual = ::std::string::ToString::to_string(value);
+                let expected: &str = ::std::convert::AsRef::as_ref(expected);
+                if actual != expected {
+                    ::std::panic!(
+                        "formatted output does not match (- expected, + actual)\n{}",
+                        $crate::snapshot::line_diff(expected, &actual)
+                    );
+                }
+            }
+        }
+    };
+    ($value:expr, $expected:expr, $($arg:tt)+) => {
+        match (&$value, &$expected) {
+            (value, expected) => {
+                let actual = ::std::string::ToString::to_string(value);
+                let expected: &str = ::std::convert::AsRef::as_ref(expected);
+                if actual != expected {
+                    ::std::panic!(
+                        "{}\nformatted output does not match (- expected, + actual)\n{}",
+                        ::std::format_args!($($arg)+),
+                        $crate::snapshot::line_diff(expected, &actual)
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_diff, normalize_for_snapshot};
+
+    fn normalize(text: &str) -> String {
+        normalize_for_snapshot(text).to_string()
+    }
+
+    #[test]
+    fn recognize_fragments() {
+        assert_eq!(
+            "[address] 0x1f 0x12345678901234567",
+            normalize("0x7ffd5e8c1a2c 0x1f 0x12345678901234567")
+        );
+        assert_eq!(
+            "a0x7ffd5e8c1a2c [address],",
+            normalize("a0x7ffd5e8c1a2c 0x7FFD5E8C,")
+        );
+        assert_eq!(
+            "[timestamp] [timestamp]. [timestamp]+01 2024-05-01",
+            normalize("2024-05-01T12:30:00+02:00 1999-12-31 23:59:59.999. 2024-05-01T00:00:00+01 2024-05-01")
+        );
+        assert_eq!(
+            "[temp]/a [temp] /home/tmp/x [temp]/b \"/tmp/\"",
+            normalize("/tmp/.tmpX1/a /var/tmp/y /home/tmp/x /private/var/folders/ab/cd/T/.tmpZ/b \"/tmp/\"")
+        );
+    }
+
+    #[test]
+    fn split_across_writes() {
+        let value = format_args!("{}{}{}", "at 2024-05-01T1", "2:30:0", "0Z on /tm");
+        assert_eq!(
+            "at [timestamp] on /tm",
+            normalize_for_snapshot(value).to_string()
+        );
+
+        let long = format!("/tmp/{}", "x".repeat(200));
+        assert_eq!(long, normalize(&long));
+    }
+
+    #[test]
+    fn diff_resyncs_after_changes() {
+        let diff = |a, b| line_diff(a, b).to_string();
+        assert_eq!("  a\n- b\n- c\n  d\n+ e", diff("a\nb\nc\nd", "a\nd\ne"));
+        assert_eq!("- a\n+ b\n+ c", diff("a", "b\nc"));
+        assert_eq!("  a\n+ ", diff("a", "a\n"));
+        assert_eq!("  ", diff("", ""));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "case 2\nformatted output does not match (- expected, + actual)\n  1\n- 2\n+ 20"
+    )]
+    fn assert_shows_diff() {
+        assert_fmt_eq!(
+            crate::lazy_fmt!("1\n{}", 20),
+            String::from("1\n2"),
+            "case {}",
+            2
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::width::display_width;
+use crate::writer::{measure, write_repeated};
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+use core::ops::Range;
+
+/// Show the lines of `source` covered by a byte range, with line numbers in a gutter and the range
+/// underlined with `^`. The message is written after the last underline. No allocation is
+/// performed as part of this operation.
+///
+/// Underlines are positioned using [crate::width], and tabs before the range are repeated in the
+/// underline so it stays aligned however tabs are displayed. The range is clamped to the length of
+/// the source and moved back to the nearest character boundaries. An empty range, or one which
+/// only covers a line break, is shown with a single `^`.
+/// ```rust
+/// use fmttools::snippet::snippet;
+///
+/// let source = "fn main() {\n    let x = foo(;\n}";
+/// let expected = [
+///     "2 |     let x = foo(;",
+///     "  |                 ^ expected an expression",
+/// ];
+/// assert_eq!(expected.join("\n"), format!("{}", snippet(source, 28..29, "expected an expression")));
+///
+/// let expected = [
+///     "1 | fn main() {",
+///     "  |    ^^^^^^^^",
+///     "2 |     let x = foo(;",
+///     "  | ^^^^^^^^^^^^^^^^^ unclosed",
+/// ];
+/// assert_eq!(expected.join("\n"), format!("{}", snippet(source, 3..29, "unclosed")));
+/// ```
+#[inline]
+pub fn snippet<'a>(source: &'a str, range: Range<usize>, message: &'a str) -> Snippet<'a> {
+    Snippet {
+        source,
+        range,
+        message,
+    }
+}
+
+pub struct Snippet<'a> {
+    source: &'a str,
+    range: Range<usize>,
+    message: &'a str,
+}
+
+impl Snippet<'_> {
+    /// The range clamped to the source and moved back to character boundaries.
+    fn clamped_range(&self) -> Range<usize> {
+        let mut end = self.range.end.min(self.source.len());
+        while !self.source.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let mut start = self.range.start.min(end);
+        while !self.source.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        start..end
+    }
+}
+
+impl Display for Snippet<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Range { start, end } = self.clamped_range();
+
+        // Find the first and last lines touched by the range
+        let first_line_start = self.source[..start].rfind('\n').map_or(0, |x| x + 1);
+        let first_number = 1 + self.source[..first_line_start].matches('\n').count();
+        let last_number = first_number + self.source[start..end].matches('\n').count()
+            - usize::from(end > start && self.source[..end].ends_with('\n'));
+        let number_width = measure(last_number);
+
+        let mut line_start = first_line_start;
+        for number in first_number..=last_number {
+            let line_end = self.source[line_start..]
+                .find('\n')
+                .map_or(self.source.len(), |x| line_start + x);
+            let line = &self.source[line_start..line_end];
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if number > first_number {
+                f.write_char('\n')?;
+            }
+            write!(f, "{:>width$} |", number, width = number_width)?;
+            if !line.is_empty() {
+                write!(f, " {}", line)?;
+            }
+
+            // The part of this line which is underlined
+            let from = (start.max(line_start) - line_start).min(line.len());
+            let to = (end.min(line_start + line.len()) - line_start).max(from);
+            let carets = display_width(&line[from..to]).max(1);
+            let is_last = number == last_number;
+            if line.is_empty() && !is_last {
+                line_start = line_end + 1;
+                continue;
+            }
+
+            write!(f, "\n{:width$} | ", "", width = number_width)?;
+            for c in line[..from].chars() {
+                if c == '\t' {
+                    f.write_char('\t')?;
+                } else {
+                    write_repeated(f, ' ', display_width(c))?;
+                }
+            }
+            write_repeated(f, '^', carets)?;
+
+            if is_last && !self.message.is_empty() {
+                write!(f, " {}", self.message)?;
+            }
+            line_start = line_end + 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::snippet;
+
+    #[test]
+    fn edges_of_source() {
+        let source = "a\tbc\n\nd";
+        assert_eq!("1 | a\tbc\n  |  \t^", snippet(source, 2..2, "").to_string());
+        assert_eq!(
+            "1 | a\tbc\n  |  \t  ^ eol",
+            snippet(source, 4..5, "eol").to_string()
+        );
+        assert_eq!(
+            "1 | a\tbc\n  |  \t^^\n2 |\n3 | d\n  | ^ x",
+            snippet(source, 2..7, "x").to_string()
+        );
+        assert_eq!(
+            "1 | ab\n  |   ^ cr",
+            snippet("ab\r\n", 2..3, "cr").to_string()
+        );
+        assert_eq!(
+            "3 | d\n  |  ^ end",
+            snippet(source, 50..60, "end").to_string()
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::fmt_fn::fmt_fn;
+use crate::table::Align;
+use crate::width::display_width;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Formatting parameters which are chosen at runtime. See [with_spec].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Spec {
+    /// The minimum width of the output, measured using [crate::width]. Defaults to `None`.
+    pub width: Option<usize>,
+    /// The precision passed on to the value, as with `{:.N}`. Defaults to `None`.
+    pub precision: Option<usize>,
+    /// The character used to pad the output to `width`. Defaults to a space.
+    pub fill: char,
+    /// Where the output is placed when it is padded. Defaults to [Align::Left].
+    pub align: Align,
+    /// Whether the sign of numbers is always written. Defaults to [Sign::Negative].
+    pub sign: Sign,
+}
+
+impl Default for Spec {
+    #[inline]
+    fn default() -> Self {
+        Spec {
+            width: None,
+            precision: None,
+            fill: ' ',
+            align: Align::Left,
+            sign: Sign::Negative,
+        }
+    }
+}
+
+/// When the sign of a number is written.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Sign {
+    /// Only write the sign of negative numbers
+    #[default]
+    Negative,
+    /// Always write the sign, as with `{:+}`
+    Always,
+}
+
+/// Format a value using parameters chosen at runtime. Unlike format strings, which can only take
+/// the width and precision from arguments, the fill character, alignment, and sign can also be
+/// decided at runtime.
+///
+/// The precision and sign are passed on to the value, while padding is written by this adapter.
+/// When a width is set, the value is formatted twice, first to measure it and then to write it.
+/// Values which can only be formatted once, such as a [join](crate::join()), then panic and should
+/// be wrapped in [reusable](crate::reusable) first.
+/// Unlike format strings, values are aligned to the left by default regardless of their type.
+/// ```rust
+/// use fmttools::spec::{with_spec, Sign, Spec};
+/// use fmttools::table::Align;
+///
+/// let spec = Spec {
+///     width: Some(9),
+///     precision: Some(2),
+///     fill: '.',
+///     align: Align::Right,
+///     sign: Sign::Always,
+/// };
+/// assert_eq!("....+3.14", format!("{}", with_spec(3.14159, spec)));
+///
+/// let spec = Spec { width: Some(7), fill: '*', align: Align::Center, ..Spec::default() };
+/// assert_eq!("**abc**", format!("{}", with_spec("abc", spec)));
+/// assert_eq!("*\"abc\"*", format!("{:?}", with_spec("abc", spec)));
+/// ```
+#[inline]
+pub fn with_spec<T>(value: T, spec: Spec) -> WithSpec<T> {
+    WithSpec { value, spec }
+}
+
+pub struct WithSpec<T> {
+    value: T,
+    spec: Spec,
+}
+
+impl<T> WithSpec<T> {
+    fn fmt_impl<V: Display>(&self, f: &mut Formatter<'_>, value: V) -> fmt::Result {
+        let width = match self.spec.width {
+            Some(width) => width,
+            None => return self.write_value(f, &value),
+        };
+
+        let padding =
+            width.saturating_sub(display_width(fmt_fn(|out| self.write_value(out, &value))));
+        let before = match self.spec.align {
+            Align::Left => 0,
+            Align::Right => padding,
+            Align::Center => padding / 2,
+        };
+
+        write_repeated(f, self.spec.fill, before)?;
+        self.write_value(f, &value)?;
+        write_repeated(f, self.spec.fill, padding - before)
+    }
+
+    fn write_value<W: Write + ?Sized, V: Display>(&self, out: &mut W, value: V) -> fmt::Result {
+        match (self.spec.sign, self.spec.precision) {
+            (Sign::Negative, None) => write!(out, "{}", value),
+            (Sign::Negative, Some(precision)) => write!(out, "{:.*}", precision, value),
+            (Sign::Always, None) => write!(out, "{:+}", value),
+            (Sign::Always, Some(precision)) => write!(out, "{:+.*}", precision, value),
+        }
+    }
+}
+
+impl<T: Debug> Debug for WithSpec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, DebugAsDisplay(&self.value))
+    }
+}
+
+impl<T: Display> Display for WithSpec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, &self.value)
+    }
+}
+
+/// Forwards to a value's [Debug] implementation, including the formatter's parameters.
+struct DebugAsDisplay<'a, T>(&'a T);
+
+impl<T: Debug> Display for DebugAsDisplay<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{with_spec, Sign, Spec};
+    use crate::table::Align;
+
+    #[test]
+    fn precision_truncates_strings() {
+        let spec = Spec {
+            width: Some(4),
+            precision: Some(2),
+            align: Align::Right,
+            ..Spec::default()
+        };
+        assert_eq!("  ab", format!("{}", with_spec("abc", spec)));
+        assert_eq!("  -1", format!("{}", with_spec(-1, spec)));
+
+        let spec = Spec {
+            sign: Sign::Always,
+            ..Spec::default()
+        };
+        assert_eq!("+0", format!("{}", with_spec(0, spec)));
+
+        let narrow = Spec {
+            width: Some(3),
+            ..spec
+        };
+        assert_eq!("toolong", format!("{}", with_spec("toolong", narrow)));
+    }
+}
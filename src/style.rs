@@ -0,0 +1,583 @@
+//! ANSI terminal styling for formatted values. Styles are applied by emitting SGR (Select Graphic
+//! Rendition) escape codes around the inner output, followed by a reset.
+//!
+//! ```rust
+//! use fmttools::style::{styled, Style};
+//!
+//! let warning = styled("warning", Style::new().red().bold());
+//! assert_eq!("\x1b[1;31mwarning\x1b[0m", format!("{}", warning));
+//! ```
+//!
+//! Styles may be nested. When an inner styled value finishes, the enclosing style is restored
+//! rather than resetting the terminal back to its default.
+//! ```rust
+//! use fmttools::style::{styled, Style};
+//!
+//! let inner = styled("b", Style::new().blue());
+//! let outer = format!("{}", styled(format_args!("a{}c", inner), Style::new().red()));
+//! assert_eq!("\x1b[31ma\x1b[34mb\x1b[0m\x1b[31mc\x1b[0m", outer);
+//! ```
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// The escape sequence used to reset all styling back to the terminal default.
+pub const RESET: &str = "\x1b[0m";
+
+/// A terminal color. In addition to the standard 16 color palette, colors may be selected from
+/// the 256 color palette using [Color::Fixed] or specified directly using [Color::Rgb] on
+/// terminals with truecolor support.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A color from the 256 color palette.
+    Fixed(u8),
+    /// A 24-bit truecolor value.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Write the SGR parameters for this color. Background colors are offset by 10 from their
+    /// foreground equivalents.
+    fn write_sgr<W: Write + ?Sized>(self, out: &mut W, background: bool) -> fmt::Result {
+        let extended = if background { "48" } else { "38" };
+        let code = match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::BrightBlack => 90,
+            Color::BrightRed => 91,
+            Color::BrightGreen => 92,
+            Color::BrightYellow => 93,
+            Color::BrightBlue => 94,
+            Color::BrightMagenta => 95,
+            Color::BrightCyan => 96,
+            Color::BrightWhite => 97,
+            Color::Fixed(index) => return write!(out, "{};5;{}", extended, index),
+            Color::Rgb(r, g, b) => return write!(out, "{};2;{};{};{}", extended, r, g, b),
+        };
+
+        write!(out, "{}", if background { code + 10 } else { code })
+    }
+}
+
+const BOLD: u8 = 1 << 0;
+const DIMMED: u8 = 1 << 1;
+const ITALIC: u8 = 1 << 2;
+const UNDERLINE: u8 = 1 << 3;
+const BLINK: u8 = 1 << 4;
+const REVERSED: u8 = 1 << 5;
+const HIDDEN: u8 = 1 << 6;
+const STRIKETHROUGH: u8 = 1 << 7;
+
+/// Attribute flags paired with their SGR parameters in the order they are emitted.
+const ATTRIBUTE_CODES: [(u8, &str); 8] = [
+    (BOLD, "1"),
+    (DIMMED, "2"),
+    (ITALIC, "3"),
+    (UNDERLINE, "4"),
+    (BLINK, "5"),
+    (REVERSED, "7"),
+    (HIDDEN, "8"),
+    (STRIKETHROUGH, "9"),
+];
+
+/// A combination of foreground color, background color, and text attributes. Styles are built
+/// up using chained method calls starting from [Style::new].
+/// ```rust
+/// use fmttools::style::{Color, Style};
+///
+/// let style = Style::new().fg(Color::BrightWhite).on(Color::Blue).underline();
+/// assert!(!style.is_plain());
+/// assert!(Style::new().is_plain());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    attributes: u8,
+}
+
+impl Style {
+    /// Create a style which does not apply any formatting.
+    #[inline]
+    pub const fn new() -> Self {
+        Style {
+            foreground: None,
+            background: None,
+            attributes: 0,
+        }
+    }
+
+    /// Returns true if this style does not apply any formatting.
+    #[inline]
+    pub const fn is_plain(&self) -> bool {
+        self.foreground.is_none() && self.background.is_none() && self.attributes == 0
+    }
+
+    /// Set the foreground color.
+    #[inline]
+    pub const fn fg(self, color: Color) -> Self {
+        Style {
+            foreground: Some(color),
+            ..self
+        }
+    }
+
+    /// Set the background color.
+    #[inline]
+    pub const fn on(self, color: Color) -> Self {
+        Style {
+            background: Some(color),
+            ..self
+        }
+    }
+
+    #[inline]
+    const fn with_attribute(self, attribute: u8) -> Self {
+        Style {
+            attributes: self.attributes | attribute,
+            ..self
+        }
+    }
+
+    #[inline]
+    pub const fn black(self) -> Self {
+        self.fg(Color::Black)
+    }
+
+    #[inline]
+    pub const fn red(self) -> Self {
+        self.fg(Color::Red)
+    }
+
+    #[inline]
+    pub const fn green(self) -> Self {
+        self.fg(Color::Green)
+    }
+
+    #[inline]
+    pub const fn yellow(self) -> Self {
+        self.fg(Color::Yellow)
+    }
+
+    #[inline]
+    pub const fn blue(self) -> Self {
+        self.fg(Color::Blue)
+    }
+
+    #[inline]
+    pub const fn magenta(self) -> Self {
+        self.fg(Color::Magenta)
+    }
+
+    #[inline]
+    pub const fn cyan(self) -> Self {
+        self.fg(Color::Cyan)
+    }
+
+    #[inline]
+    pub const fn white(self) -> Self {
+        self.fg(Color::White)
+    }
+
+    #[inline]
+    pub const fn bold(self) -> Self {
+        self.with_attribute(BOLD)
+    }
+
+    #[inline]
+    pub const fn dimmed(self) -> Self {
+        self.with_attribute(DIMMED)
+    }
+
+    #[inline]
+    pub const fn italic(self) -> Self {
+        self.with_attribute(ITALIC)
+    }
+
+    #[inline]
+    pub const fn underline(self) -> Self {
+        self.with_attribute(UNDERLINE)
+    }
+
+    #[inline]
+    pub const fn blink(self) -> Self {
+        self.with_attribute(BLINK)
+    }
+
+    #[inline]
+    pub const fn reversed(self) -> Self {
+        self.with_attribute(REVERSED)
+    }
+
+    #[inline]
+    pub const fn hidden(self) -> Self {
+        self.with_attribute(HIDDEN)
+    }
+
+    #[inline]
+    pub const fn strikethrough(self) -> Self {
+        self.with_attribute(STRIKETHROUGH)
+    }
+
+    /// Write the escape sequence which enables this style. Nothing is written for a plain style.
+    pub(crate) fn write_prefix<W: Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        if self.is_plain() {
+            return Ok(());
+        }
+
+        out.write_str("\x1b[")?;
+        let mut separator = "";
+
+        for (flag, code) in ATTRIBUTE_CODES {
+            if self.attributes & flag != 0 {
+                out.write_str(separator)?;
+                out.write_str(code)?;
+                separator = ";";
+            }
+        }
+
+        if let Some(color) = self.foreground {
+            out.write_str(separator)?;
+            color.write_sgr(out, false)?;
+            separator = ";";
+        }
+
+        if let Some(color) = self.background {
+            out.write_str(separator)?;
+            color.write_sgr(out, true)?;
+        }
+
+        out.write_str("m")
+    }
+}
+
+/// Apply a [Style] to a value while formatting. The style is reset once the value has been
+/// written. No allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::style::{styled, Style};
+///
+/// assert_eq!("\x1b[32m\"ok\"\x1b[0m", format!("{:?}", styled("ok", Style::new().green())));
+/// ```
+///
+/// Since [styled] is a `const fn`, styled values can also be declared as constants.
+/// ```rust
+/// use fmttools::style::{styled, Style, Styled};
+///
+/// const WARNING: Styled<&str> = styled("warning", Style::new().yellow().bold());
+/// assert_eq!("\x1b[1;33mwarning\x1b[0m", format!("{}", WARNING));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+///
+/// Resets emitted by nested [Styled] values are detected in the inner output and followed by this
+/// style's escape sequence so the enclosing style continues to apply. Since every level of
+/// nesting wraps the writer of the level above it, the restored styles are re-applied from the
+/// outermost inwards. Only the canonical reset sequence ([RESET]) is recognized.
+#[inline]
+pub const fn styled<T>(value: T, style: Style) -> Styled<T> {
+    Styled { value, style }
+}
+
+/// Apply a [Style] to a value only if `enabled` is true. When disabled, the value is written
+/// directly to the formatter without any additional processing.
+/// ```rust
+/// use fmttools::style::{colors_enabled, styled_if, Style};
+///
+/// let style = Style::new().red();
+/// assert_eq!("\x1b[31merror\x1b[0m", format!("{}", styled_if(true, "error", style)));
+/// assert_eq!("error", format!("{}", styled_if(false, "error", style)));
+///
+/// // Follow the process-wide setting
+/// let message = format!("{}", styled_if(colors_enabled(), "error", style));
+/// ```
+#[inline]
+pub const fn styled_if<T>(enabled: bool, value: T, style: Style) -> Styled<T> {
+    let style = if enabled { style } else { Style::new() };
+    Styled { value, style }
+}
+
+const COLORS_UNKNOWN: u8 = 0;
+const COLORS_ENABLED: u8 = 1;
+const COLORS_DISABLED: u8 = 2;
+
+static COLORS: AtomicU8 = AtomicU8::new(COLORS_UNKNOWN);
+
+/// Returns whether colored output is enabled for this process. Unless overridden using
+/// [set_colors_enabled], this is decided on first use by checking the `NO_COLOR` environment
+/// variable. Colors are disabled if `NO_COLOR` is present and not empty (see
+/// <https://no-color.org>). Without the `std` feature, colors are enabled by default.
+pub fn colors_enabled() -> bool {
+    match COLORS.load(Ordering::Relaxed) {
+        COLORS_ENABLED => true,
+        COLORS_DISABLED => false,
+        _ => {
+            #[cfg(feature = "std")]
+            let enabled = match std::env::var_os("NO_COLOR") {
+                Some(value) => value.is_empty(),
+                None => true,
+            };
+            #[cfg(not(feature = "std"))]
+            let enabled = true;
+            let state = if enabled {
+                COLORS_ENABLED
+            } else {
+                COLORS_DISABLED
+            };
+
+            // If another thread set the value first, defer to it
+            match COLORS.compare_exchange(
+                COLORS_UNKNOWN,
+                state,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => enabled,
+                Err(existing) => existing == COLORS_ENABLED,
+            }
+        }
+    }
+}
+
+/// Override whether colored output is enabled for this process. See [colors_enabled].
+pub fn set_colors_enabled(enabled: bool) {
+    let state = if enabled {
+        COLORS_ENABLED
+    } else {
+        COLORS_DISABLED
+    };
+    COLORS.store(state, Ordering::Relaxed);
+}
+
+pub struct Styled<T> {
+    value: T,
+    style: Style,
+}
+
+impl<T> Styled<T> {
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut dyn Write) -> fmt::Result,
+    {
+        if self.style.is_plain() {
+            return func(f);
+        }
+
+        self.style.write_prefix(f)?;
+        let mut writer = NestedStyleWriter {
+            dst: &mut *f,
+            style: &self.style,
+            matched: 0,
+        };
+        func(&mut writer)?;
+        f.write_str(RESET)
+    }
+}
+
+/// Forwards output unchanged, but restores `style` after every reset written by a nested style.
+struct NestedStyleWriter<'a, W> {
+    dst: W,
+    style: &'a Style,
+    /// How many bytes of [RESET] have been matched so far. Resets may be split across writes.
+    matched: usize,
+}
+
+impl<W: Write> Write for NestedStyleWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let reset = RESET.as_bytes();
+        let bytes = s.as_bytes();
+        let mut flushed = 0;
+        let mut index = 0;
+
+        while index < bytes.len() {
+            if self.matched == 0 {
+                // Skip ahead to the next escape character
+                match bytes[index..].iter().position(|&byte| byte == reset[0]) {
+                    Some(offset) => index += offset,
+                    None => break,
+                }
+            }
+
+            if bytes[index] == reset[self.matched] {
+                self.matched += 1;
+            } else {
+                self.matched = usize::from(bytes[index] == reset[0]);
+            }
+            index += 1;
+
+            if self.matched == reset.len() {
+                self.dst.write_str(&s[flushed..index])?;
+                self.style.write_prefix(&mut self.dst)?;
+                flushed = index;
+                self.matched = 0;
+            }
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+impl<T: Debug> Debug for Styled<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for Styled<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+/// Wrap text in an OSC 8 escape sequence so that terminals which support it display the text as
+/// a clickable link. If colors are disabled (see [colors_enabled]), only the text is written.
+/// ```rust
+/// use fmttools::style::hyperlink;
+///
+/// println!("See {} for details", hyperlink("the docs", "https://docs.rs/fmttools"));
+/// ```
+#[inline]
+pub fn hyperlink<T, U>(text: T, url: U) -> Hyperlink<T, U> {
+    hyperlink_if(colors_enabled(), text, url)
+}
+
+/// Wrap text in an OSC 8 hyperlink only if `enabled` is true. See [hyperlink].
+/// ```rust
+/// use fmttools::style::hyperlink_if;
+///
+/// let link = hyperlink_if(true, "docs", "https://docs.rs");
+/// assert_eq!("\x1b]8;;https://docs.rs\x1b\\docs\x1b]8;;\x1b\\", format!("{}", link));
+/// assert_eq!("docs", format!("{}", hyperlink_if(false, "docs", "https://docs.rs")));
+/// ```
+#[inline]
+pub const fn hyperlink_if<T, U>(enabled: bool, text: T, url: U) -> Hyperlink<T, U> {
+    Hyperlink { text, url, enabled }
+}
+
+pub struct Hyperlink<T, U> {
+    text: T,
+    url: U,
+    enabled: bool,
+}
+
+impl<T: Display, U: Display> Display for Hyperlink<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.enabled {
+            return write!(f, "{}", self.text);
+        }
+
+        write!(f, "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", self.url, self.text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hyperlink_if, styled, styled_if, Color, Style};
+    use crate::replace;
+
+    #[test]
+    fn plain_style_is_passthrough() {
+        assert_eq!(format!("{}", styled("abc", Style::new())), "abc");
+    }
+
+    #[test]
+    fn attributes_before_colors() {
+        let style = Style::new()
+            .on(Color::BrightBlue)
+            .underline()
+            .yellow()
+            .bold();
+        let out = format!("{}", styled(12, style));
+        assert_eq!(out, "\x1b[1;4;33;104m12\x1b[0m");
+    }
+
+    #[test]
+    fn styled_debug() {
+        let out = format!("{:?}", styled("a\nb", Style::new().italic()));
+        assert_eq!(out, "\x1b[3m\"a\\nb\"\x1b[0m");
+    }
+
+    #[test]
+    fn extended_colors() {
+        let style = Style::new().fg(Color::Fixed(208)).on(Color::Rgb(1, 2, 3));
+        let out = format!("{}", styled("x", style));
+        assert_eq!(out, "\x1b[38;5;208;48;2;1;2;3mx\x1b[0m");
+    }
+
+    #[test]
+    fn nested_styles_restore_enclosing() {
+        let inner = styled("c", Style::new().green());
+        let out = format!(
+            "{}",
+            styled(
+                format_args!(
+                    "a{}a",
+                    styled(format_args!("b{}b", inner), Style::new().bold())
+                ),
+                Style::new().red()
+            )
+        );
+
+        let expected = concat!(
+            "\x1b[31ma",
+            "\x1b[1mb",
+            "\x1b[32mc\x1b[0m\x1b[31m\x1b[1m",
+            "b\x1b[0m\x1b[31m",
+            "a\x1b[0m",
+        );
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn reset_split_across_writes() {
+        // Replacing "|" with the second half of a reset splits it across two writes
+        let inner = replace("\x1b[|", '|', "0m");
+        let out = format!("{}", styled(inner, Style::new().blue()));
+        assert_eq!(out, "\x1b[34m\x1b[0m\x1b[34m\x1b[0m");
+    }
+
+    #[test]
+    fn disabled_style_is_passthrough() {
+        let inner = styled("b", Style::new().green());
+        let out = format!(
+            "{}",
+            styled_if(false, format_args!("a{}c", inner), Style::new().red())
+        );
+        assert_eq!(out, "a\x1b[32mb\x1b[0mc");
+    }
+
+    #[test]
+    fn styled_hyperlink() {
+        let link = hyperlink_if(true, styled("x", Style::new().underline()), "file:///tmp");
+        let out = format!("{}", link);
+        assert_eq!(
+            out,
+            "\x1b]8;;file:///tmp\x1b\\\x1b[4mx\x1b[0m\x1b]8;;\x1b\\"
+        );
+    }
+}
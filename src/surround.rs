@@ -0,0 +1,89 @@
+use crate::writer::measure;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+
+/// Write a prefix, a value, and then a suffix.
+/// ```rust
+/// use fmttools::surround;
+///
+/// assert_eq!("[42]", format!("{}", surround('[', 42, ']')));
+/// assert_eq!("(a, b)", format!("{}", surround("(", fmttools::join(&["a", "b"], ", "), ")")));
+/// ```
+#[inline]
+pub fn surround<P, T, S>(prefix: P, value: T, suffix: S) -> Surround<P, T, S> {
+    Surround {
+        prefix,
+        value,
+        suffix,
+        skip_empty: false,
+    }
+}
+
+pub struct Surround<P, T, S> {
+    prefix: P,
+    value: T,
+    suffix: S,
+    skip_empty: bool,
+}
+
+impl<P, T, S> Surround<P, T, S> {
+    /// If true, nothing is written when the value's formatted output is empty. The value is
+    /// formatted an additional time to check if it is empty, so values which can only be formatted
+    /// once, such as a [join](crate::join()), panic unless they are wrapped in
+    /// [reusable](crate::reusable).
+    /// ```rust
+    /// use fmttools::surround;
+    ///
+    /// let label = |note: &str| format!("item{}", surround(" (", note, ")").skip_empty(true));
+    /// assert_eq!("item", label(""));
+    /// assert_eq!("item (sale)", label("sale"));
+    /// ```
+    #[inline]
+    pub fn skip_empty(self, skip_empty: bool) -> Self {
+        Surround { skip_empty, ..self }
+    }
+}
+
+impl<P, T, S> Display for Surround<P, T, S>
+where
+    P: Display,
+    T: Display,
+    S: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.skip_empty && measure(&self.value) == 0 {
+            return Ok(());
+        }
+
+        write!(f, "{}{}{}", self.prefix, self.value, self.suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::surround;
+    use crate::join::{join, reusable};
+
+    #[test]
+    fn skip_empty_only_when_empty() {
+        assert_eq!(surround("<", "", ">").to_string(), "<>");
+        assert_eq!(surround("<", "", ">").skip_empty(true).to_string(), "");
+        assert_eq!(surround("<", ' ', ">").skip_empty(true).to_string(), "< >");
+    }
+
+    #[test]
+    fn skip_empty_with_reusable_join() {
+        let value = surround("[", reusable(join(1..=3, ",")), "]").skip_empty(true);
+        assert_eq!(value.to_string(), "[1,2,3]");
+        let empty = surround("[", reusable(join(0..0, ",")), "]").skip_empty(true);
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "Join can only be used once")]
+    fn skip_empty_formats_twice() {
+        surround("[", join(1..=3, ","), "]")
+            .skip_empty(true)
+            .to_string();
+    }
+}
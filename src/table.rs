@@ -0,0 +1,359 @@
+//! Format rows of cells as a table with aligned columns.
+//!
+//! Tables are rendered in two passes over the rows. The first pass measures every cell to find
+//! the width of each column, and the second writes the cells padded to those widths. As a result,
+//! each cell is formatted twice. Widths are measured using [crate::display_width], so styled
+//! cells are aligned according to their visible text.
+//!
+//! ```rust
+//! use fmttools::table::{table, Align, Border};
+//!
+//! let rows = [
+//!     ["crate", "version", "downloads"],
+//!     ["fmttools", "0.2.2", "1024"],
+//!     ["itoa", "1.0.9", "208359"],
+//! ];
+//!
+//! let output = table(&rows)
+//!     .header(true)
+//!     .align(&[Align::Left, Align::Center, Align::Right])
+//!     .border(Border::Unicode)
+//!     .to_string();
+//!
+//! assert_eq!(output, "\
+//! ┌──────────┬─────────┬───────────┐
+//! │ crate    │ version │ downloads │
+//! ├──────────┼─────────┼───────────┤
+//! │ fmttools │  0.2.2  │      1024 │
+//! │ itoa     │  1.0.9  │    208359 │
+//! └──────────┴─────────┴───────────┘");
+//! ```
+use crate::width::display_width;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// The horizontal alignment of cells within a column.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    /// Center the cell, placing any odd space on the right
+    Center,
+}
+
+/// The lines drawn around and between cells.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Border {
+    /// No lines are drawn. Columns are separated by padding only and the header separator is made
+    /// of dashes.
+    #[default]
+    None,
+    /// Lines drawn using `+`, `-`, and `|`
+    Ascii,
+    /// Lines drawn using Unicode box-drawing characters
+    Unicode,
+}
+
+/// The characters used to draw a [Border].
+struct BorderChars {
+    horizontal: char,
+    vertical: char,
+    /// The left, middle, and right junctions of the top, separator, and bottom lines
+    top: [char; 3],
+    separator: [char; 3],
+    bottom: [char; 3],
+}
+
+const ASCII: BorderChars = BorderChars {
+    horizontal: '-',
+    vertical: '|',
+    top: ['+', '+', '+'],
+    separator: ['+', '+', '+'],
+    bottom: ['+', '+', '+'],
+};
+
+const UNICODE: BorderChars = BorderChars {
+    horizontal: '─',
+    vertical: '│',
+    top: ['┌', '┬', '┐'],
+    separator: ['├', '┼', '┤'],
+    bottom: ['└', '┴', '┘'],
+};
+
+/// Format rows of cells as a table. See [crate::table] for more information.
+///
+/// The rows are iterated twice, so they must be [Clone]. This is the case for references to
+/// arrays, slices, and most collections. Rows may have different numbers of cells, in which case
+/// the missing cells are left empty.
+/// ```rust
+/// use fmttools::table::table;
+///
+/// let rows = vec![vec!["a", "bb", "c"], vec!["ddd", "e"]];
+/// assert_eq!("a    bb  c\nddd  e", table(&rows).to_string());
+/// ```
+#[inline]
+pub fn table<R>(rows: R) -> Table<'static, R> {
+    Table::new(rows)
+}
+
+/// A table of rows created by [table]. The widths of the first `N` columns are measured once, while
+/// the width of any further column is measured again for each cell written in it, so those columns
+/// iterate over the rows once more per cell. A different limit can be chosen using [Table::new].
+pub struct Table<'a, R, const N: usize = 16> {
+    rows: R,
+    align: &'a [Align],
+    border: Border,
+    header: bool,
+    padding: usize,
+}
+
+impl<R, const N: usize> Table<'static, R, N> {
+    /// Create a table which measures the widths of up to `N` columns in a single pass.
+    /// ```rust
+    /// use fmttools::table::Table;
+    ///
+    /// let rows = [[1, 20, 300]];
+    /// assert_eq!("1  20  300", Table::<_, 3>::new(&rows).to_string());
+    /// ```
+    #[inline]
+    pub fn new(rows: R) -> Self {
+        Table {
+            rows,
+            align: &[],
+            border: Border::None,
+            header: false,
+            padding: 1,
+        }
+    }
+}
+
+impl<'a, R, const N: usize> Table<'a, R, N> {
+    /// Set the alignment of each column. Columns without an alignment are aligned to the left.
+    #[inline]
+    pub fn align(self, align: &[Align]) -> Table<'_, R, N> {
+        Table {
+            rows: self.rows,
+            align,
+            border: self.border,
+            header: self.header,
+            padding: self.padding,
+        }
+    }
+
+    /// Set the lines drawn around and between cells.
+    #[inline]
+    pub fn border(self, border: Border) -> Self {
+        Table { border, ..self }
+    }
+
+    /// If true, the first row is treated as a header and followed by a separator line.
+    #[inline]
+    pub fn header(self, header: bool) -> Self {
+        Table { header, ..self }
+    }
+
+    /// Set the number of spaces written on each side of a cell. Defaults to 1.
+    #[inline]
+    pub fn padding(self, padding: usize) -> Self {
+        Table { padding, ..self }
+    }
+}
+
+impl<R, const N: usize> Table<'_, R, N> {
+    #[inline]
+    fn column_align(&self, column: usize) -> Align {
+        self.align.get(column).copied().unwrap_or_default()
+    }
+
+    fn write_line<W: Write, F: Fn(usize) -> usize>(
+        &self,
+        f: &mut W,
+        width: F,
+        columns: usize,
+        chars: &BorderChars,
+        junctions: [char; 3],
+    ) -> fmt::Result {
+        f.write_char(junctions[0])?;
+        for column in 0..columns {
+            if column > 0 {
+                f.write_char(junctions[1])?;
+            }
+            write_repeated(f, chars.horizontal, width(column) + 2 * self.padding)?;
+        }
+        f.write_char(junctions[2])
+    }
+
+    fn write_cell<W: Write, T: Display>(
+        &self,
+        f: &mut W,
+        cell: T,
+        column: usize,
+        width: usize,
+        last: bool,
+    ) -> fmt::Result {
+        let space = width.saturating_sub(display_width(&cell));
+        let (before, after) = match self.column_align(column) {
+            Align::Left => (0, space),
+            Align::Right => (space, 0),
+            Align::Center => (space / 2, space - space / 2),
+        };
+
+        // Borderless tables do not pad the outer edges, so no line has trailing whitespace
+        let outer = self.border != Border::None;
+        let padding_before = if column > 0 || outer { self.padding } else { 0 };
+        let padding_after = if !last || outer { self.padding } else { 0 };
+        let after = if last && !outer { 0 } else { after };
+
+        write_repeated(f, ' ', padding_before + before)?;
+        write!(f, "{}", cell)?;
+        write_repeated(f, ' ', after + padding_after)
+    }
+}
+
+impl<R, const N: usize> Table<'_, R, N>
+where
+    R: IntoIterator + Clone,
+    R::Item: IntoIterator,
+    <R::Item as IntoIterator>::Item: Display,
+{
+    /// Measure the width of a column past the first `N` by iterating over the rows again.
+    fn measure_column(&self, column: usize) -> usize {
+        self.rows
+            .clone()
+            .into_iter()
+            .filter_map(|row| row.into_iter().nth(column))
+            .map(|cell| display_width(&cell))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<R, const N: usize> Display for Table<'_, R, N>
+where
+    R: IntoIterator + Clone,
+    R::Item: IntoIterator,
+    <R::Item as IntoIterator>::Item: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut widths = [0; N];
+        let mut columns = 0;
+        for row in self.rows.clone() {
+            let mut count = 0;
+            for (column, cell) in row.into_iter().enumerate() {
+                if let Some(width) = widths.get_mut(column) {
+                    *width = (*width).max(display_width(&cell));
+                }
+                count = column + 1;
+            }
+            columns = columns.max(count);
+        }
+
+        let width = |column: usize| match widths.get(column) {
+            Some(width) => *width,
+            None => self.measure_column(column),
+        };
+
+        let chars = match self.border {
+            Border::None => None,
+            Border::Ascii => Some(&ASCII),
+            Border::Unicode => Some(&UNICODE),
+        };
+
+        if let Some(chars) = chars {
+            self.write_line(f, width, columns, chars, chars.top)?;
+            f.write_char('\n')?;
+        }
+
+        for (index, row) in self.rows.clone().into_iter().enumerate() {
+            if index > 0 {
+                f.write_char('\n')?;
+            }
+
+            if let Some(chars) = chars {
+                f.write_char(chars.vertical)?;
+            }
+
+            let mut written = 0;
+            let mut cells = row.into_iter().enumerate().peekable();
+            while let Some((column, cell)) = cells.next() {
+                // Missing cells are only written when there is a border to draw around them
+                let last = column + 1 == columns || (chars.is_none() && cells.peek().is_none());
+                self.write_cell(f, cell, column, width(column), last)?;
+                if let Some(chars) = chars {
+                    f.write_char(chars.vertical)?;
+                }
+                written = column + 1;
+            }
+
+            if let Some(chars) = chars {
+                for column in written..columns {
+                    self.write_cell(f, "", column, width(column), column + 1 == columns)?;
+                    f.write_char(chars.vertical)?;
+                }
+            }
+
+            if self.header && index == 0 {
+                f.write_char('\n')?;
+                match chars {
+                    Some(chars) => self.write_line(f, width, columns, chars, chars.separator)?,
+                    None => {
+                        for column in 0..columns {
+                            if column > 0 {
+                                write_repeated(f, ' ', 2 * self.padding)?;
+                            }
+                            write_repeated(f, '-', width(column))?;
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(chars) = chars {
+            f.write_char('\n')?;
+            self.write_line(f, width, columns, chars, chars.bottom)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{table, Align, Border, Table};
+
+    #[test]
+    fn borderless_header() {
+        let rows = [["name", "size"], ["a", "10"], ["long name", "5"]];
+        let output = table(&rows)
+            .header(true)
+            .align(&[Align::Left, Align::Right])
+            .to_string();
+        assert_eq!(
+            output,
+            "name       size\n---------  ----\na            10\nlong name     5"
+        );
+    }
+
+    #[test]
+    fn ascii_ragged_rows() {
+        let rows = vec![vec!["a", "b"], vec!["c"], vec![]];
+        let output = table(&rows).border(Border::Ascii).padding(0).to_string();
+        assert_eq!(output, "+-+-+\n|a|b|\n|c| |\n| | |\n+-+-+");
+    }
+
+    #[test]
+    fn columns_past_limit() {
+        let rows = [["a", "bb", "c"], ["ddd", "e", "ffff"]];
+        let output = Table::<_, 1>::new(&rows).border(Border::Ascii).to_string();
+        assert_eq!(
+            output,
+            "+-----+----+------+\n| a   | bb | c    |\n| ddd | e  | ffff |\n+-----+----+------+"
+        );
+        assert_eq!(
+            "a    bb  c\nddd  e   ffff",
+            Table::<_, 0>::new(&rows).to_string()
+        );
+    }
+}
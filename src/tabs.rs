@@ -0,0 +1,234 @@
+use crate::width::WidthCounter;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Replace each tab in a value's output with spaces up to the next tab stop, where tab stops are
+/// placed every `tab_width` columns. Columns are measured using [crate::width] from the start of
+/// each line. No allocation is performed as part of this operation.
+///
+/// Expanding tabs is needed before the output can be measured, wrapped, or aligned, since the
+/// width of a tab depends on where it is placed. A `tab_width` of 0 removes tabs.
+/// ```rust
+/// use fmttools::expand_tabs;
+///
+/// assert_eq!("a   bc  d\n    e", format!("{}", expand_tabs("a\tbc\td\n\te", 4)));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn expand_tabs<T>(value: T, tab_width: usize) -> ExpandTabs<T> {
+    ExpandTabs { value, tab_width }
+}
+
+pub struct ExpandTabs<T> {
+    value: T,
+    tab_width: usize,
+}
+
+impl<T> ExpandTabs<T> {
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut TabWriter<&mut Formatter<'_>>) -> fmt::Result,
+    {
+        let mut writer = TabWriter {
+            dst: f,
+            tab_width: self.tab_width,
+            column: WidthCounter::new(),
+        };
+        func(&mut writer)
+    }
+}
+
+impl<T: Debug> Debug for ExpandTabs<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for ExpandTabs<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+struct TabWriter<W> {
+    dst: W,
+    tab_width: usize,
+    /// Measures the current column, counted from the start of the line
+    column: WidthCounter,
+}
+
+impl<W: Write> Write for TabWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, c) in s.char_indices() {
+            if c != '\t' && c != '\n' {
+                continue;
+            }
+
+            let text = &s[flushed..index];
+            self.dst.write_str(text)?;
+            self.column.write_str(text)?;
+            flushed = index + 1;
+
+            if c == '\n' {
+                self.dst.write_char('\n')?;
+                self.column = WidthCounter::new();
+            } else if self.tab_width > 0 {
+                let spaces = self.tab_width - self.column.width() % self.tab_width;
+                for _ in 0..spaces {
+                    self.dst.write_char(' ')?;
+                    self.column.write_char(' ')?;
+                }
+            }
+        }
+
+        let text = &s[flushed..];
+        self.dst.write_str(text)?;
+        self.column.write_str(text)
+    }
+}
+
+/// Replace the spaces at the start of each line of a value's output with tabs, where tab stops
+/// are placed every `tab_width` columns. Spaces which do not reach a tab stop are kept, and tabs
+/// already present in the leading whitespace are preserved. Like the `unexpand` utility, spaces
+/// after the first non-whitespace character of a line are not changed. A `tab_width` of 0 leaves
+/// the output unchanged.
+/// ```rust
+/// use fmttools::tabs::unexpand;
+///
+/// let rule = "all:\n        cargo build\n          --release";
+/// assert_eq!("all:\n\tcargo build\n\t  --release", format!("{}", unexpand(rule, 8)));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn unexpand<T>(value: T, tab_width: usize) -> Unexpand<T> {
+    Unexpand { value, tab_width }
+}
+
+pub struct Unexpand<T> {
+    value: T,
+    tab_width: usize,
+}
+
+impl<T> Unexpand<T> {
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut UnexpandWriter<&mut Formatter<'_>>) -> fmt::Result,
+    {
+        let mut writer = UnexpandWriter {
+            dst: f,
+            tab_width: self.tab_width,
+            leading: true,
+            column: 0,
+        };
+        func(&mut writer)?;
+        writer.flush_indent()
+    }
+}
+
+impl<T: Debug> Debug for Unexpand<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for Unexpand<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+/// Holds back the leading whitespace of each line until its end is found.
+struct UnexpandWriter<W> {
+    dst: W,
+    tab_width: usize,
+    /// If only whitespace has been written on the current line
+    leading: bool,
+    /// The column reached by the leading whitespace which has been held back
+    column: usize,
+}
+
+impl<W: Write> UnexpandWriter<W> {
+    /// Write the held back whitespace as tabs followed by any remaining spaces.
+    fn flush_indent(&mut self) -> fmt::Result {
+        if self.column == 0 {
+            return Ok(());
+        }
+
+        write_repeated(&mut self.dst, '\t', self.column / self.tab_width)?;
+        write_repeated(&mut self.dst, ' ', self.column % self.tab_width)?;
+        self.column = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for UnexpandWriter<W> {
+    fn write_str(&mut self, mut s: &str) -> fmt::Result {
+        if self.tab_width == 0 {
+            return self.dst.write_str(s);
+        }
+
+        while !s.is_empty() {
+            if !self.leading {
+                match s.find('\n') {
+                    None => return self.dst.write_str(s),
+                    Some(index) => {
+                        self.dst.write_str(&s[..=index])?;
+                        s = &s[index + 1..];
+                        self.leading = true;
+                        continue;
+                    }
+                }
+            }
+
+            let indent = s.len() - s.trim_start_matches([' ', '\t']).len();
+            for c in s[..indent].chars() {
+                self.column += match c {
+                    '\t' => self.tab_width - self.column % self.tab_width,
+                    _ => 1,
+                };
+            }
+
+            s = &s[indent..];
+            if !s.is_empty() {
+                self.flush_indent()?;
+                self.leading = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_tabs, unexpand};
+
+    #[test]
+    fn column_tracked_across_writes() {
+        let value = format_args!("{}{}{}", "ab", "c\t", "\x1b[1m\td\t\n\t");
+        assert_eq!(
+            "abc     \x1b[1m        d       \n        ",
+            format!("{}", expand_tabs(value, 8))
+        );
+        assert_eq!("ab", format!("{}", expand_tabs("\ta\tb", 0)));
+        assert_eq!("\"\\t\"", format!("{:?}", expand_tabs("\t", 4)));
+    }
+
+    #[test]
+    fn unexpand_across_writes() {
+        let value = format_args!("{}{}{}", "  ", "  \t  x  y\n ", "   \n");
+        assert_eq!("\t\t  x  y\n\t\n", format!("{}", unexpand(value, 4)));
+        assert_eq!("   a", format!("{}", unexpand("   a", 0)));
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, SystemTime};
+
+/// Values which can be described relative to the current time by [ago].
+pub trait RelativeTime {
+    /// Get the distance from now along with whether that point is in the future.
+    fn relative_offset(self) -> (Duration, bool);
+}
+
+/// A duration is treated as an amount of time in the past.
+impl RelativeTime for Duration {
+    #[inline]
+    fn relative_offset(self) -> (Duration, bool) {
+        (self, false)
+    }
+}
+
+/// The system time is compared against [SystemTime::now] when [ago] is called.
+impl RelativeTime for SystemTime {
+    #[inline]
+    fn relative_offset(self) -> (Duration, bool) {
+        relative_offset(self, SystemTime::now())
+    }
+}
+
+#[inline]
+fn relative_offset(time: SystemTime, now: SystemTime) -> (Duration, bool) {
+    match now.duration_since(time) {
+        Ok(past) => (past, false),
+        Err(err) => (err.duration(), true),
+    }
+}
+
+/// Describe a point in time relative to now, such as `3 minutes ago` or `in 2 days`. The largest
+/// fitting unit is chosen and the count is truncated. Times less than a second away are written
+/// as `just now`.
+/// ```rust
+/// use std::time::{Duration, SystemTime};
+/// use fmttools::time::ago;
+///
+/// assert_eq!("3 minutes ago", format!("{}", ago(Duration::from_secs(200))));
+/// assert_eq!("1 hour ago", format!("{}", ago(Duration::from_secs(3600))));
+///
+/// let deadline = SystemTime::now() + Duration::from_secs(2 * 86_400 + 60);
+/// assert_eq!("in 2 days", format!("{}", ago(deadline)));
+/// ```
+///
+/// ## Note
+/// Months and years are approximated as 30 and 365 days respectively.
+#[inline]
+pub fn ago<T: RelativeTime>(time: T) -> Ago {
+    let (offset, future) = time.relative_offset();
+    Ago { offset, future }
+}
+
+/// Describe a point in time relative to a given reference time. See [ago].
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use fmttools::time::ago_from;
+///
+/// let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+/// assert_eq!("in 1 week", format!("{}", ago_from(now + Duration::from_secs(8 * 86_400), now)));
+/// ```
+#[inline]
+pub fn ago_from(time: SystemTime, now: SystemTime) -> Ago {
+    let (offset, future) = relative_offset(time, now);
+    Ago { offset, future }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ago {
+    offset: Duration,
+    future: bool,
+}
+
+/// Units paired with their length in seconds, from largest to smallest.
+const UNITS: [(&str, u64); 7] = [
+    ("year", 365 * 86_400),
+    ("month", 30 * 86_400),
+    ("week", 7 * 86_400),
+    ("day", 86_400),
+    ("hour", 3_600),
+    ("minute", 60),
+    ("second", 1),
+];
+
+impl Display for Ago {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let secs = self.offset.as_secs();
+        let (name, count) = match UNITS.iter().find(|(_, length)| secs >= *length) {
+            Some((name, length)) => (*name, secs / length),
+            None => return f.write_str("just now"),
+        };
+
+        let plural = if count == 1 { "" } else { "s" };
+        if self.future {
+            write!(f, "in {} {}{}", count, name, plural)
+        } else {
+            write!(f, "{} {}{} ago", count, name, plural)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ago, ago_from};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn just_now() {
+        assert_eq!(format!("{}", ago(Duration::from_millis(999))), "just now");
+    }
+
+    #[test]
+    fn unit_boundaries() {
+        assert_eq!(
+            format!("{}", ago(Duration::from_secs(59))),
+            "59 seconds ago"
+        );
+        assert_eq!(format!("{}", ago(Duration::from_secs(60))), "1 minute ago");
+        let days = Duration::from_secs(29 * 86_400);
+        assert_eq!(format!("{}", ago(days)), "4 weeks ago");
+        let years = Duration::from_secs(800 * 86_400);
+        assert_eq!(format!("{}", ago(years)), "2 years ago");
+    }
+
+    #[test]
+    fn relative_to_reference() {
+        let now = UNIX_EPOCH + Duration::from_secs(10_000);
+        let earlier = UNIX_EPOCH + Duration::from_secs(2_800);
+        assert_eq!(format!("{}", ago_from(earlier, now)), "2 hours ago");
+        assert_eq!(format!("{}", ago_from(now, earlier)), "in 2 hours");
+    }
+}
@@ -0,0 +1,98 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::time::Duration;
+
+/// Format a [Duration] as a clock reading in the form `hh:mm:ss`. Hours are only written when the
+/// duration is at least one hour long unless [ClockDuration::always_hours] is set. Fractional
+/// seconds can be shown using [ClockDuration::fraction] or the precision of the format string, and
+/// are truncated rather than rounded.
+/// ```rust
+/// use std::time::Duration;
+/// use fmttools::time::duration_clock;
+///
+/// assert_eq!("01:02:03", format!("{}", duration_clock(Duration::from_secs(3723))));
+/// assert_eq!("02:03", format!("{}", duration_clock(Duration::from_secs(123))));
+/// assert_eq!("02:03.450", format!("{:.3}", duration_clock(Duration::from_millis(123_450))));
+///
+/// let clock = duration_clock(Duration::from_millis(123_450)).always_hours(true).fraction(1);
+/// assert_eq!("00:02:03.4", format!("{}", clock));
+/// ```
+#[inline]
+pub fn duration_clock(duration: Duration) -> ClockDuration {
+    ClockDuration {
+        duration,
+        always_hours: false,
+        fraction: 0,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ClockDuration {
+    duration: Duration,
+    always_hours: bool,
+    fraction: usize,
+}
+
+impl ClockDuration {
+    /// Write the hours even when they are zero.
+    #[inline]
+    pub fn always_hours(self, always_hours: bool) -> Self {
+        ClockDuration {
+            always_hours,
+            ..self
+        }
+    }
+
+    /// Set the number of fractional digits shown for the seconds, up to a maximum of 9. This is
+    /// overridden by the precision of the format string if one is given.
+    #[inline]
+    pub fn fraction(self, fraction: usize) -> Self {
+        ClockDuration { fraction, ..self }
+    }
+}
+
+impl Display for ClockDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let total_secs = self.duration.as_secs();
+        let hours = total_secs / 3600;
+        let minutes = total_secs / 60 % 60;
+        let seconds = total_secs % 60;
+
+        if hours > 0 || self.always_hours {
+            write!(f, "{:02}:", hours)?;
+        }
+        write!(f, "{:02}:{:02}", minutes, seconds)?;
+
+        let fraction = f.precision().unwrap_or(self.fraction).min(9);
+        if fraction > 0 {
+            let nanos = self.duration.subsec_nanos() / 10u32.pow(9 - fraction as u32);
+            write!(f, ".{:0width$}", nanos, width = fraction)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::duration_clock;
+    use std::time::Duration;
+
+    #[test]
+    fn clock_zero() {
+        assert_eq!(format!("{}", duration_clock(Duration::ZERO)), "00:00");
+    }
+
+    #[test]
+    fn clock_many_hours() {
+        let out = duration_clock(Duration::from_secs(100 * 3600 + 59));
+        assert_eq!(format!("{}", out), "100:00:59");
+    }
+
+    #[test]
+    fn clock_fraction_truncates() {
+        let out = duration_clock(Duration::from_nanos(1_999_999_999)).fraction(2);
+        assert_eq!(format!("{}", out), "00:01.99");
+        assert_eq!(format!("{:.12}", out), "00:01.999999999");
+    }
+}
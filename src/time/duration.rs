@@ -0,0 +1,168 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::time::Duration;
+
+/// A unit of time, ordered from largest to smallest.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TimeUnit {
+    Days,
+    Hours,
+    Minutes,
+    Seconds,
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimeUnit {
+    const ALL: [TimeUnit; 7] = [
+        TimeUnit::Days,
+        TimeUnit::Hours,
+        TimeUnit::Minutes,
+        TimeUnit::Seconds,
+        TimeUnit::Millis,
+        TimeUnit::Micros,
+        TimeUnit::Nanos,
+    ];
+
+    /// The abbreviated suffix used for this unit.
+    #[inline]
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            TimeUnit::Days => "d",
+            TimeUnit::Hours => "h",
+            TimeUnit::Minutes => "m",
+            TimeUnit::Seconds => "s",
+            TimeUnit::Millis => "ms",
+            TimeUnit::Micros => "µs",
+            TimeUnit::Nanos => "ns",
+        }
+    }
+
+    /// The number of nanoseconds in one of this unit.
+    #[inline]
+    pub const fn nanos(self) -> u128 {
+        match self {
+            TimeUnit::Days => 86_400_000_000_000,
+            TimeUnit::Hours => 3_600_000_000_000,
+            TimeUnit::Minutes => 60_000_000_000,
+            TimeUnit::Seconds => 1_000_000_000,
+            TimeUnit::Millis => 1_000_000,
+            TimeUnit::Micros => 1_000,
+            TimeUnit::Nanos => 1,
+        }
+    }
+}
+
+/// Format a [Duration] in a compact human readable form such as `2h 13m 5s` or `451ms`. Starting
+/// from the largest non-zero unit, up to 3 units are written by default. Smaller units are
+/// truncated.
+/// ```rust
+/// use std::time::Duration;
+/// use fmttools::time::{duration, TimeUnit};
+///
+/// assert_eq!("2h 13m 5s", format!("{}", duration(Duration::from_millis(7_985_123))));
+/// assert_eq!("451ms", format!("{}", duration(Duration::from_millis(451))));
+/// assert_eq!("1m 30s 250ms", format!("{}", duration(Duration::from_millis(90_250))));
+///
+/// let rounded = duration(Duration::from_millis(90_250)).smallest(TimeUnit::Seconds);
+/// assert_eq!("1m 30s", format!("{}", rounded));
+///
+/// let single = duration(Duration::from_millis(90_250)).max_units(1);
+/// assert_eq!("1m", format!("{}", single));
+/// ```
+#[inline]
+pub fn duration(duration: Duration) -> HumanDuration {
+    HumanDuration {
+        duration,
+        smallest: TimeUnit::Nanos,
+        max_units: 3,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HumanDuration {
+    duration: Duration,
+    smallest: TimeUnit,
+    max_units: usize,
+}
+
+impl HumanDuration {
+    /// Set the smallest unit which may be written. Durations shorter than this unit are written
+    /// as zero of this unit.
+    #[inline]
+    pub fn smallest(self, smallest: TimeUnit) -> Self {
+        HumanDuration { smallest, ..self }
+    }
+
+    /// Set the maximum number of units which are written. At least one unit is always written.
+    #[inline]
+    pub fn max_units(self, max_units: usize) -> Self {
+        HumanDuration { max_units, ..self }
+    }
+}
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut remaining = self.duration.as_nanos();
+        let mut written = 0;
+
+        for unit in TimeUnit::ALL {
+            if unit > self.smallest || written >= self.max_units.max(1) {
+                break;
+            }
+
+            let count = remaining / unit.nanos();
+            remaining %= unit.nanos();
+
+            // Zero units are skipped, but once a unit is written the following units are counted
+            // against the limit so that only adjacent units are shown
+            if count == 0 && written == 0 {
+                continue;
+            }
+
+            if count != 0 {
+                if written != 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{}{}", count, unit.suffix())?;
+            }
+            written += 1;
+        }
+
+        if written == 0 {
+            write!(f, "0{}", self.smallest.suffix())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration, TimeUnit};
+    use std::time::Duration;
+
+    #[test]
+    fn zero_duration() {
+        assert_eq!(format!("{}", duration(Duration::ZERO)), "0ns");
+        let out = duration(Duration::from_millis(999)).smallest(TimeUnit::Seconds);
+        assert_eq!(format!("{}", out), "0s");
+    }
+
+    #[test]
+    fn skips_zero_units_within_limit() {
+        let out = duration(Duration::from_secs(86_400 + 5));
+        assert_eq!(format!("{}", out), "1d");
+        let out = duration(Duration::from_secs(3_600 + 5)).max_units(3);
+        assert_eq!(format!("{}", out), "1h 5s");
+    }
+
+    #[test]
+    fn sub_millisecond() {
+        assert_eq!(
+            format!("{}", duration(Duration::from_nanos(1_500))),
+            "1µs 500ns"
+        );
+    }
+}
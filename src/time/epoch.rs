@@ -0,0 +1,99 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+use super::rfc3339::unix_timestamp;
+
+/// Format a [SystemTime] as the number of whole seconds since the unix epoch.
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use fmttools::time::epoch_secs;
+///
+/// let time = UNIX_EPOCH + Duration::from_millis(1_714_998_896_789);
+/// assert_eq!("1714998896", format!("{}", epoch_secs(time)));
+///
+/// // Times before the epoch are negative unless a fallback is given
+/// let before = UNIX_EPOCH - Duration::from_millis(1500);
+/// assert_eq!("-2", format!("{}", epoch_secs(before)));
+/// assert_eq!("null", format!("{}", epoch_secs(before).fallback("null")));
+/// ```
+#[inline]
+pub fn epoch_secs(time: SystemTime) -> EpochTimestamp<'static> {
+    EpochTimestamp {
+        time,
+        millis: false,
+        fallback: None,
+    }
+}
+
+/// Format a [SystemTime] as the number of whole milliseconds since the unix epoch. See
+/// [epoch_secs].
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use fmttools::time::epoch_millis;
+///
+/// let time = UNIX_EPOCH + Duration::from_micros(1_714_998_896_789_999);
+/// assert_eq!("1714998896789", format!("{}", epoch_millis(time)));
+/// ```
+#[inline]
+pub fn epoch_millis(time: SystemTime) -> EpochTimestamp<'static> {
+    EpochTimestamp {
+        time,
+        millis: true,
+        fallback: None,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EpochTimestamp<'a> {
+    time: SystemTime,
+    millis: bool,
+    fallback: Option<&'a str>,
+}
+
+impl EpochTimestamp<'_> {
+    /// Write the given text instead of a negative timestamp for times before the unix epoch.
+    #[inline]
+    pub fn fallback(self, fallback: &str) -> EpochTimestamp<'_> {
+        EpochTimestamp {
+            time: self.time,
+            millis: self.millis,
+            fallback: Some(fallback),
+        }
+    }
+}
+
+impl Display for EpochTimestamp<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Timestamps are rounded down so the values before the epoch stay monotonic
+        let (secs, nanos) = unix_timestamp(self.time);
+
+        match self.fallback {
+            Some(fallback) if secs < 0 => f.write_str(fallback),
+            _ if self.millis => {
+                let millis = i128::from(secs) * 1000 + i128::from(nanos / 1_000_000);
+                write!(f, "{}", millis)
+            }
+            _ => write!(f, "{}", secs),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{epoch_millis, epoch_secs};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn epoch_is_zero() {
+        assert_eq!(format!("{}", epoch_secs(UNIX_EPOCH).fallback("-")), "0");
+        assert_eq!(format!("{}", epoch_millis(UNIX_EPOCH)), "0");
+    }
+
+    #[test]
+    fn millis_before_epoch() {
+        let before = UNIX_EPOCH - Duration::from_micros(1500);
+        assert_eq!(format!("{}", epoch_millis(before)), "-2");
+        assert_eq!(format!("{}", epoch_millis(before).fallback("")), "");
+    }
+}
@@ -0,0 +1,105 @@
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::time::Duration;
+
+/// Format a [Duration] using the exact grammar of the `humantime` crate (ex: `1day 2h 3m 4s 5ms`)
+/// so the output can be parsed back by `humantime::parse_duration` and the config parsers built
+/// on it. Unlike [crate::time::duration], every non-zero unit is written and nothing is truncated.
+/// ```rust
+/// use std::time::Duration;
+/// use fmttools::time::duration_humantime;
+///
+/// let value = Duration::new(86_400 + 2 * 3600 + 3 * 60 + 4, 5_000_000);
+/// assert_eq!("1day 2h 3m 4s 5ms", format!("{}", duration_humantime(value)));
+/// assert_eq!("0s", format!("{}", duration_humantime(Duration::ZERO)));
+/// ```
+///
+/// ## Note
+/// As in `humantime`, a year is 365.25 days and a month is 30.44 days.
+#[inline]
+pub fn duration_humantime(duration: Duration) -> HumantimeDuration {
+    HumantimeDuration(duration)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HumantimeDuration(Duration);
+
+const SECONDS_PER_YEAR: u64 = 31_557_600;
+const SECONDS_PER_MONTH: u64 = 2_630_016;
+
+impl Display for HumantimeDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let nanos = self.0.subsec_nanos();
+
+        if secs == 0 && nanos == 0 {
+            return f.write_str("0s");
+        }
+
+        let year_secs = secs % SECONDS_PER_YEAR;
+        let month_secs = year_secs % SECONDS_PER_MONTH;
+        let day_secs = month_secs % 86_400;
+
+        let mut started = false;
+        write_item(f, &mut started, "year", secs / SECONDS_PER_YEAR, true)?;
+        write_item(
+            f,
+            &mut started,
+            "month",
+            year_secs / SECONDS_PER_MONTH,
+            true,
+        )?;
+        write_item(f, &mut started, "day", month_secs / 86_400, true)?;
+        write_item(f, &mut started, "h", day_secs / 3600, false)?;
+        write_item(f, &mut started, "m", day_secs % 3600 / 60, false)?;
+        write_item(f, &mut started, "s", day_secs % 60, false)?;
+        write_item(f, &mut started, "ms", u64::from(nanos / 1_000_000), false)?;
+        write_item(f, &mut started, "us", u64::from(nanos / 1000 % 1000), false)?;
+        write_item(f, &mut started, "ns", u64::from(nanos % 1000), false)
+    }
+}
+
+#[inline]
+fn write_item(
+    f: &mut Formatter<'_>,
+    started: &mut bool,
+    name: &str,
+    value: u64,
+    plural: bool,
+) -> fmt::Result {
+    if value == 0 {
+        return Ok(());
+    }
+
+    if *started {
+        f.write_str(" ")?;
+    }
+    write!(f, "{}{}", value, name)?;
+    if plural && value > 1 {
+        f.write_str("s")?;
+    }
+
+    *started = true;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::duration_humantime;
+    use std::time::Duration;
+
+    #[test]
+    fn plural_units() {
+        let value = Duration::from_secs(2 * 31_557_600 + 3 * 2_630_016 + 2 * 86_400);
+        assert_eq!(
+            format!("{}", duration_humantime(value)),
+            "2years 3months 2days"
+        );
+    }
+
+    #[test]
+    fn sub_second_units() {
+        let value = Duration::new(0, 1_002_003);
+        assert_eq!(format!("{}", duration_humantime(value)), "1ms 2us 3ns");
+    }
+}
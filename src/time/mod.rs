@@ -0,0 +1,22 @@
+//! Human readable formatting of durations and timestamps.
+//!
+//! Adapters which take a [SystemTime](std::time::SystemTime) require the `std` feature.
+#[cfg(feature = "std")]
+mod ago;
+mod clock;
+mod duration;
+#[cfg(feature = "std")]
+mod epoch;
+mod humantime;
+#[cfg(feature = "std")]
+mod rfc3339;
+
+#[cfg(feature = "std")]
+pub use ago::{ago, ago_from, Ago, RelativeTime};
+pub use clock::{duration_clock, ClockDuration};
+pub use duration::{duration, HumanDuration, TimeUnit};
+#[cfg(feature = "std")]
+pub use epoch::{epoch_millis, epoch_secs, EpochTimestamp};
+pub use humantime::{duration_humantime, HumantimeDuration};
+#[cfg(feature = "std")]
+pub use rfc3339::{rfc3339, Rfc3339};
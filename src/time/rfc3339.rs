@@ -0,0 +1,129 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Format a [SystemTime] as an RFC 3339 timestamp in UTC such as `2024-05-06T12:34:56.789Z`.
+/// Milliseconds are shown by default. The number of fractional digits can be set using
+/// [Rfc3339::fraction] or the precision of the format string.
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use fmttools::time::rfc3339;
+///
+/// let time = UNIX_EPOCH + Duration::from_millis(1_714_998_896_789);
+/// assert_eq!("2024-05-06T12:34:56.789Z", format!("{}", rfc3339(time)));
+/// assert_eq!("2024-05-06T12:34:56Z", format!("{:.0}", rfc3339(time)));
+/// assert_eq!("2024-05-06T12:34:56.789000Z", format!("{}", rfc3339(time).fraction(6)));
+/// ```
+///
+/// ## Note
+/// RFC 3339 only allows for years between 0000 and 9999. Times outside of this range are written
+/// with a longer or negative year.
+#[inline]
+pub fn rfc3339(time: SystemTime) -> Rfc3339 {
+    Rfc3339 { time, fraction: 3 }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rfc3339 {
+    time: SystemTime,
+    fraction: usize,
+}
+
+impl Rfc3339 {
+    /// Set the number of fractional digits shown for the seconds, up to a maximum of 9. This is
+    /// overridden by the precision of the format string if one is given.
+    #[inline]
+    pub fn fraction(self, fraction: usize) -> Self {
+        Rfc3339 { fraction, ..self }
+    }
+}
+
+impl Display for Rfc3339 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (secs, nanos) = unix_timestamp(self.time);
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        let seconds_of_day = secs.rem_euclid(86_400);
+
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            seconds_of_day / 3600,
+            seconds_of_day / 60 % 60,
+            seconds_of_day % 60
+        )?;
+
+        let fraction = f.precision().unwrap_or(self.fraction).min(9);
+        if fraction > 0 {
+            let truncated = nanos / 10u32.pow(9 - fraction as u32);
+            write!(f, ".{:0width$}", truncated, width = fraction)?;
+        }
+
+        f.write_str("Z")
+    }
+}
+
+/// Get the number of whole seconds since the unix epoch (rounded towards negative infinity) and
+/// the nanoseconds elapsed since that second.
+pub(crate) fn unix_timestamp(time: SystemTime) -> (i64, u32) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => (since.as_secs() as i64, since.subsec_nanos()),
+        Err(err) => {
+            let before = err.duration();
+            match before.subsec_nanos() {
+                0 => (-(before.as_secs() as i64), 0),
+                nanos => (-(before.as_secs() as i64) - 1, 1_000_000_000 - nanos),
+            }
+        }
+    }
+}
+
+/// Convert a number of days since the unix epoch to a year, month, and day in the proleptic
+/// Gregorian calendar. See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, rfc3339};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn epoch() {
+        assert_eq!(
+            format!("{}", rfc3339(UNIX_EPOCH)),
+            "1970-01-01T00:00:00.000Z"
+        );
+    }
+
+    #[test]
+    fn before_epoch() {
+        let time = UNIX_EPOCH - Duration::from_millis(1);
+        assert_eq!(format!("{}", rfc3339(time)), "1969-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn leap_days() {
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(19_783), (2024, 3, 1));
+    }
+}
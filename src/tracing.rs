@@ -0,0 +1,50 @@
+//! Record adapters as fields of [tracing](::tracing) spans and events. Requires the `tracing`
+//! feature.
+//!
+//! The [Value](::tracing::field::Value) trait is sealed, so adapters can not implement it
+//! directly. Instead, [display] and [debug] work like their counterparts in
+//! [tracing::field](::tracing::field), but wrap the value in [reusable] so that adapters which
+//! can only be formatted once, such as [crate::join], can be visited by any number of layers.
+//! Nothing is formatted unless the event is enabled and recorded.
+//! ```rust
+//! use fmttools::join;
+//! use fmttools::tracing::display;
+//!
+//! let ports = [80, 443];
+//! tracing::info!(ports = display(join(&ports, ", ")), "listening");
+//! ```
+use crate::join::{reusable, Reusable};
+use ::tracing::field::{DebugValue, DisplayValue};
+use core::fmt::{Debug, Display};
+
+/// Record a value as a field using its [Display] implementation. The value is cloned each time it
+/// is recorded.
+#[inline]
+pub fn display<T: Clone + Display>(value: T) -> DisplayValue<Reusable<T>> {
+    ::tracing::field::display(reusable(value))
+}
+
+/// Record a value as a field using its [Debug] implementation. The value is cloned each time it is
+/// recorded.
+#[inline]
+pub fn debug<T: Clone + Debug>(value: T) -> DebugValue<Reusable<T>> {
+    ::tracing::field::debug(reusable(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{debug, display};
+    use crate::join::join;
+
+    #[test]
+    pub fn display_repeatedly() {
+        let field = display(join(&[1, 2, 3], "-"));
+        assert_eq!(format!("{:?} {:?}", field, field), "1-2-3 1-2-3");
+    }
+
+    #[test]
+    pub fn debug_repeatedly() {
+        let field = debug(join(&["a", "b"], ", "));
+        assert_eq!(format!("{:?} {:?}", field, field), r#""a", "b" "a", "b""#);
+    }
+}
@@ -0,0 +1,217 @@
+//! Format hierarchical data as a tree drawn with box-drawing characters.
+//!
+//! ```rust
+//! use std::fmt::{self, Display, Formatter};
+//! use fmttools::tree::tree;
+//!
+//! struct Crate {
+//!     name: &'static str,
+//!     deps: Vec<Crate>,
+//! }
+//!
+//! impl Display for Crate {
+//!     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+//!         f.write_str(self.name)
+//!     }
+//! }
+//!
+//! let leaf = |name| Crate { name, deps: Vec::new() };
+//! let root = Crate {
+//!     name: "app",
+//!     deps: vec![
+//!         Crate { name: "serde", deps: vec![leaf("serde_derive")] },
+//!         leaf("log"),
+//!     ],
+//! };
+//!
+//! let output = tree(&root, |c| c.deps.iter()).to_string();
+//! assert_eq!(output, "\
+//! app
+//! ├── serde
+//! │   └── serde_derive
+//! └── log");
+//! ```
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+/// The strings drawn before each node, each occupying the same number of columns.
+struct Branches {
+    /// Before a node which has siblings after it
+    tee: &'static str,
+    /// Before the last node among its siblings
+    corner: &'static str,
+    /// Below a node which has siblings after it
+    bar: &'static str,
+    /// Below the last node among its siblings
+    blank: &'static str,
+}
+
+const UNICODE: Branches = Branches {
+    tee: "├── ",
+    corner: "└── ",
+    bar: "│   ",
+    blank: "    ",
+};
+
+const ASCII: Branches = Branches {
+    tee: "|-- ",
+    corner: "`-- ",
+    bar: "|   ",
+    blank: "    ",
+};
+
+/// Format a tree starting from `root`, where the children of each node are found using
+/// `children`. Each node is written on its own line using its [Display] implementation. Lines are
+/// separated by `\n`, without a trailing line break.
+/// ```rust
+/// use std::path::Path;
+/// use fmttools::tree::tree;
+///
+/// let files = ["src", "src/lib.rs", "src/tree", "src/tree/mod.rs"];
+/// let output = tree(&files[0], |dir| {
+///     files.iter().filter(move |f| Path::new(f).parent() == Some(Path::new(dir)))
+/// });
+///
+/// assert_eq!(
+///     output.ascii(true).to_string(),
+///     "src\n|-- src/lib.rs\n`-- src/tree\n    `-- src/tree/mod.rs"
+/// );
+/// ```
+#[inline]
+pub fn tree<'a, T, C, I>(root: &'a T, children: C) -> Tree<'a, T, C>
+where
+    C: Fn(&'a T) -> I,
+    I: IntoIterator<Item = &'a T>,
+{
+    Tree {
+        root,
+        children,
+        max_depth: None,
+        ascii: false,
+    }
+}
+
+pub struct Tree<'a, T, C> {
+    root: &'a T,
+    children: C,
+    max_depth: Option<usize>,
+    ascii: bool,
+}
+
+impl<'a, T, C> Tree<'a, T, C> {
+    /// Only write nodes up to the given depth below the root. A depth of 0 writes only the root.
+    #[inline]
+    pub fn max_depth(self, max_depth: usize) -> Self {
+        Tree {
+            max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    /// If true, draw the tree using ASCII characters instead of box-drawing characters.
+    #[inline]
+    pub fn ascii(self, ascii: bool) -> Self {
+        Tree { ascii, ..self }
+    }
+}
+
+/// An ancestor of the node being written. Ancestors are kept on the stack while recursing, with
+/// each level pointing to the one above it.
+struct Level<'p> {
+    parent: Option<&'p Level<'p>>,
+    /// If this ancestor was the last among its siblings
+    last: bool,
+}
+
+impl<'a, T, C, I> Tree<'a, T, C>
+where
+    T: Display + 'a,
+    C: Fn(&'a T) -> I,
+    I: IntoIterator<Item = &'a T>,
+{
+    #[inline]
+    fn branches(&self) -> &'static Branches {
+        if self.ascii {
+            &ASCII
+        } else {
+            &UNICODE
+        }
+    }
+
+    fn write_prefix(&self, f: &mut Formatter<'_>, level: Option<&Level<'_>>) -> fmt::Result {
+        match level {
+            None => Ok(()),
+            Some(level) => {
+                self.write_prefix(f, level.parent)?;
+                let branches = self.branches();
+                f.write_str(if level.last {
+                    branches.blank
+                } else {
+                    branches.bar
+                })
+            }
+        }
+    }
+
+    fn write_children(
+        &self,
+        f: &mut Formatter<'_>,
+        node: &'a T,
+        depth: usize,
+        level: Option<&Level<'_>>,
+    ) -> fmt::Result {
+        if self.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+            return Ok(());
+        }
+
+        let mut children = (self.children)(node).into_iter().peekable();
+        while let Some(child) = children.next() {
+            let last = children.peek().is_none();
+            let branches = self.branches();
+
+            f.write_char('\n')?;
+            self.write_prefix(f, level)?;
+            f.write_str(if last { branches.corner } else { branches.tee })?;
+            write!(f, "{}", child)?;
+
+            let child_level = Level {
+                parent: level,
+                last,
+            };
+            self.write_children(f, child, depth + 1, Some(&child_level))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T, C, I> Display for Tree<'a, T, C>
+where
+    T: Display + 'a,
+    C: Fn(&'a T) -> I,
+    I: IntoIterator<Item = &'a T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)?;
+        self.write_children(f, self.root, 0, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tree;
+
+    #[test]
+    fn continuation_bars() {
+        // Each node is a number whose children are given by the table below
+        let children: [&[usize]; 7] = [&[1, 4], &[2, 3], &[], &[], &[5], &[6], &[]];
+        let nodes = [0, 1, 2, 3, 4, 5, 6];
+        let output = tree(&nodes[0], |n| children[*n].iter().map(|c| &nodes[*c]));
+
+        assert_eq!(
+            output.to_string(),
+            "0\n├── 1\n│   ├── 2\n│   └── 3\n└── 4\n    └── 5\n        └── 6"
+        );
+        assert_eq!(output.max_depth(1).to_string(), "0\n├── 1\n└── 4");
+    }
+}
@@ -0,0 +1,130 @@
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+
+/// Limit the output of a value to at most `max_chars` characters, discarding the rest. No
+/// allocation is performed as part of this operation.
+/// ```rust
+/// use fmttools::truncate::truncate_chars;
+///
+/// assert_eq!("héllo", format!("{}", truncate_chars("héllo, world", 5)));
+/// assert_eq!("short", format!("{}", truncate_chars("short", 5)));
+///
+/// // A marker can be written after truncated output
+/// assert_eq!("héllo…", format!("{}", truncate_chars("héllo, world", 5).marker("…")));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn truncate_chars<T>(value: T, max_chars: usize) -> TruncateChars<'static, T> {
+    TruncateChars {
+        value,
+        max_chars,
+        marker: "",
+    }
+}
+
+pub struct TruncateChars<'a, T> {
+    value: T,
+    max_chars: usize,
+    marker: &'a str,
+}
+
+impl<T> TruncateChars<'_, T> {
+    /// Set a marker which is written after the output if any of it was discarded. The marker does
+    /// not count towards the character limit. Defaults to no marker.
+    #[inline]
+    pub fn marker(self, marker: &str) -> TruncateChars<'_, T> {
+        TruncateChars {
+            value: self.value,
+            max_chars: self.max_chars,
+            marker,
+        }
+    }
+
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut TruncateWriter<'_, &mut Formatter<'_>>) -> fmt::Result,
+    {
+        func(&mut TruncateWriter::new(f, self.max_chars, self.marker))
+    }
+}
+
+impl<T: Debug> Debug for TruncateChars<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for TruncateChars<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+/// Forwards at most a fixed number of characters, then writes the marker once and discards the
+/// remaining output.
+pub(crate) struct TruncateWriter<'a, W> {
+    dst: W,
+    remaining: usize,
+    marker: &'a str,
+    truncated: bool,
+}
+
+impl<'a, W> TruncateWriter<'a, W> {
+    #[inline]
+    pub(crate) fn new(dst: W, max_chars: usize, marker: &'a str) -> Self {
+        TruncateWriter {
+            dst,
+            remaining: max_chars,
+            marker,
+            truncated: false,
+        }
+    }
+}
+
+impl<W: Write> Write for TruncateWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated || s.is_empty() {
+            return Ok(());
+        }
+
+        match s.char_indices().nth(self.remaining) {
+            Some((index, _)) => {
+                self.remaining = 0;
+                self.truncated = true;
+                self.dst.write_str(&s[..index])?;
+                self.dst.write_str(self.marker)
+            }
+            None => {
+                self.remaining -= s.chars().count();
+                self.dst.write_str(s)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_chars;
+
+    #[test]
+    fn split_across_writes() {
+        let value = format_args!("{}{}{}", "ab", "", "cd");
+        assert_eq!(
+            "abc...",
+            format!("{}", truncate_chars(value, 3).marker("..."))
+        );
+        assert_eq!(
+            "abcd",
+            format!("{}", truncate_chars(value, 4).marker("..."))
+        );
+        assert_eq!(
+            "\"a...",
+            format!("{:?}", truncate_chars("abc", 2).marker("..."))
+        );
+        assert_eq!("", format!("{}", truncate_chars("abc", 0)));
+    }
+}
@@ -0,0 +1,174 @@
+//! Percent-encoding and query strings for building URLs.
+//!
+//! ```rust
+//! use fmttools::url::{query_string, QueryValue};
+//!
+//! let page: Option<u32> = None;
+//! let params = [
+//!     ("q", &"fmt tools" as &dyn QueryValue),
+//!     ("page", &page),
+//!     ("lang", &"en-US"),
+//! ];
+//! assert_eq!(
+//!     "https://example.com/search?q=fmt%20tools&lang=en-US",
+//!     format!("https://example.com/search?{}", query_string(params.iter().copied()))
+//! );
+//! ```
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Percent-encode the formatted output of a value, so it can be used as a component of a URL.
+/// Every byte except the unreserved characters of RFC 3986 (ASCII letters, digits, `-`, `.`, `_`,
+/// and `~`) is written as `%` followed by two uppercase hexadecimal digits. No allocation is
+/// performed as part of this operation.
+/// ```rust
+/// use fmttools::url::percent_encode;
+///
+/// assert_eq!("a%2Fb%20c~%C3%A9", format!("{}", percent_encode("a/b c~é")));
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn percent_encode<T>(value: T) -> PercentEncode<T> {
+    PercentEncode { value }
+}
+
+pub struct PercentEncode<T> {
+    value: T,
+}
+
+impl<T: Display> Display for PercentEncode<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(PercentWriter { dst: f }, "{}", self.value)
+    }
+}
+
+/// Values which can be written in a query string. A value of `None` causes its pair to be
+/// skipped.
+pub trait QueryValue {
+    /// Returns the value to write, or `None` if the pair should be skipped.
+    fn query_value(&self) -> Option<&dyn Display>;
+}
+
+macro_rules! impl_query_value {
+    ($($t:ty),*) => {
+        $(impl QueryValue for $t {
+            #[inline]
+            fn query_value(&self) -> Option<&dyn Display> {
+                Some(self)
+            }
+        })*
+    };
+}
+
+impl_query_value!(&str, char, bool, f32, f64);
+impl_query_value!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl_query_value!(alloc::string::String);
+
+impl<T: QueryValue> QueryValue for Option<T> {
+    #[inline]
+    fn query_value(&self) -> Option<&dyn Display> {
+        self.as_ref().and_then(QueryValue::query_value)
+    }
+}
+
+impl<T: QueryValue + ?Sized> QueryValue for &T {
+    #[inline]
+    fn query_value(&self) -> Option<&dyn Display> {
+        (**self).query_value()
+    }
+}
+
+/// Write key-value pairs as a URL query string, such as `a=1&b=2`. Keys and values are
+/// [percent encoded](percent_encode), and pairs whose value is `None` are skipped. The leading
+/// `?` is not written. The pairs are iterated each time the query string is formatted, so they
+/// must be [Clone].
+/// ```rust
+/// use fmttools::url::query_string;
+///
+/// let pairs = [("id", Some(7)), ("limit", None), ("offset", Some(20))];
+/// assert_eq!("id=7&offset=20", format!("{}", query_string(pairs.iter().copied())));
+/// ```
+#[inline]
+pub fn query_string<I>(pairs: I) -> QueryString<I> {
+    QueryString { pairs }
+}
+
+pub struct QueryString<I> {
+    pairs: I,
+}
+
+impl<I, K, V> Display for QueryString<I>
+where
+    I: IntoIterator<Item = (K, V)> + Clone,
+    K: Display,
+    V: QueryValue,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for (key, value) in self.pairs.clone() {
+            let value = match value.query_value() {
+                Some(value) => value,
+                None => continue,
+            };
+
+            if !first {
+                f.write_char('&')?;
+            }
+            first = false;
+            write!(f, "{}={}", percent_encode(key), percent_encode(value))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct PercentWriter<W> {
+    dst: W,
+}
+
+impl<W: Write> Write for PercentWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut flushed = 0;
+
+        for (index, byte) in s.bytes().enumerate() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+                continue;
+            }
+
+            // Multi-byte characters are encoded one byte at a time, so only slice between them
+            if flushed < index {
+                self.dst.write_str(&s[flushed..index])?;
+            }
+            self.dst.write_char('%')?;
+            self.dst
+                .write_char(HEX_DIGITS[(byte >> 4) as usize] as char)?;
+            self.dst
+                .write_char(HEX_DIGITS[(byte & 0xf) as usize] as char)?;
+            flushed = index + 1;
+        }
+
+        self.dst.write_str(&s[flushed..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{percent_encode, query_string};
+
+    #[test]
+    fn encodes_keys_and_values() {
+        let pairs = [("a b", Some("x&y=z")), ("skip", None), ("c", Some(""))];
+        assert_eq!(
+            "a%20b=x%26y%3Dz&c=",
+            format!("{}", query_string(pairs.iter().copied()))
+        );
+        assert_eq!("%00%7F", format!("{}", percent_encode("\0\x7f")));
+    }
+}
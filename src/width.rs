@@ -0,0 +1,156 @@
+//! Measurement of the on-screen width of formatted output. ANSI escape sequences (such as those
+//! emitted by [crate::style]) do not occupy any space on screen and are skipped.
+//!
+//! With the `unicode-width` feature enabled, characters are measured according to their East Asian
+//! Width so that wide characters are counted as two columns. Otherwise, every non-control
+//! character is counted as a single column.
+//!
+//! ```rust
+//! use fmttools::style::{styled, Style};
+//! use fmttools::width::display_width;
+//!
+//! assert_eq!(5, display_width(styled("hello", Style::new().bold())));
+//! ```
+use crate::writer::feed;
+use core::fmt;
+use core::fmt::{Display, Write};
+
+/// Compute the number of columns a value occupies when written to a terminal. Formatting is
+/// performed without allocation.
+/// ```rust
+/// use fmttools::width::display_width;
+///
+/// assert_eq!(7, display_width(format_args!("\x1b[31m{}\x1b[0m", "abc-def")));
+/// ```
+#[inline]
+pub fn display_width<T: Display>(value: T) -> usize {
+    let mut counter = WidthCounter::new();
+    feed(&mut counter, value);
+    counter.width()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum EscapeState {
+    /// Regular text which is being measured
+    Text,
+    /// An escape character was found, but the type of sequence is not known yet
+    Escape,
+    /// Control Sequence Introducer, terminated by a byte in the range `0x40..=0x7E`
+    Csi,
+    /// Operating System Command, terminated by either BEL or ST (`ESC \`)
+    Osc,
+    /// An escape character was found inside an OSC sequence, which may begin ST
+    OscEscape,
+}
+
+/// A writer which discards its input while tallying the number of columns it would occupy on a
+/// terminal. Escape sequences may be split across multiple writes.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::width::WidthCounter;
+///
+/// let mut counter = WidthCounter::new();
+/// write!(counter, "\x1b[1").unwrap();
+/// write!(counter, "mabc").unwrap();
+/// assert_eq!(3, counter.width());
+/// ```
+#[derive(Debug, Clone)]
+pub struct WidthCounter {
+    width: usize,
+    state: EscapeState,
+}
+
+impl WidthCounter {
+    #[inline]
+    pub const fn new() -> Self {
+        WidthCounter {
+            width: 0,
+            state: EscapeState::Text,
+        }
+    }
+
+    /// The number of columns counted so far.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl Default for WidthCounter {
+    #[inline]
+    fn default() -> Self {
+        WidthCounter::new()
+    }
+}
+
+impl Write for WidthCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.state = match (self.state, c) {
+                (EscapeState::Text, '\x1b') => EscapeState::Escape,
+                (EscapeState::Text, c) => {
+                    self.width += char_width(c);
+                    EscapeState::Text
+                }
+                (EscapeState::Escape, '[') => EscapeState::Csi,
+                (EscapeState::Escape, ']') => EscapeState::Osc,
+                // Any other escape consists of only a single character following the escape
+                (EscapeState::Escape, _) => EscapeState::Text,
+                (EscapeState::Csi, '\x40'..='\x7e') => EscapeState::Text,
+                (EscapeState::Csi, _) => EscapeState::Csi,
+                (EscapeState::Osc, '\x07') => EscapeState::Text,
+                (EscapeState::Osc, '\x1b') => EscapeState::OscEscape,
+                (EscapeState::Osc, _) => EscapeState::Osc,
+                (EscapeState::OscEscape, '\\') => EscapeState::Text,
+                (EscapeState::OscEscape, _) => EscapeState::Osc,
+            };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "unicode-width")]
+#[inline]
+fn char_width(c: char) -> usize {
+    unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+}
+
+#[cfg(not(feature = "unicode-width"))]
+#[inline]
+fn char_width(c: char) -> usize {
+    usize::from(!c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::display_width;
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(display_width("abc def"), 7);
+        assert_eq!(display_width(""), 0);
+    }
+
+    #[test]
+    fn skips_sgr_sequences() {
+        assert_eq!(display_width("\x1b[1;38;5;208mab\x1b[0mc"), 3);
+    }
+
+    #[test]
+    fn skips_osc_sequences() {
+        let link = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07";
+        assert_eq!(display_width(link), 4);
+    }
+
+    #[test]
+    fn control_characters_have_no_width() {
+        assert_eq!(display_width("a\rb\x07"), 2);
+    }
+
+    #[cfg(feature = "unicode-width")]
+    #[test]
+    fn wide_characters() {
+        assert_eq!(display_width("日本語"), 6);
+    }
+}
@@ -0,0 +1,418 @@
+use crate::width::WidthCounter;
+use crate::writer::write_repeated;
+use core::fmt;
+use core::fmt::{Debug, Display, Formatter, Write};
+use core::str;
+
+/// The number of bytes of a single word which are held back while deciding where to place it.
+/// Longer words are written as soon as the buffer is full.
+const WORD_BUFFER: usize = 256;
+
+/// Wrap the output of a value so that lines do not exceed `width` columns. Lines are broken at
+/// whitespace, and existing line breaks are preserved. Whitespace at the point of a break is
+/// removed, as is any whitespace at the end of a line.
+///
+/// Words are measured using [crate::width], so styled text is wrapped according to its visible
+/// width. By default, words which are longer than a line are written on a line of their own,
+/// exceeding the width. See [Wrap::word_break] for alternatives. Words longer than 256 bytes are
+/// placed based on their first 256 bytes.
+/// ```rust
+/// use fmttools::wrap;
+///
+/// let text = "The quick brown fox jumps over the lazy dog.";
+/// assert_eq!(
+///     "The quick brown\nfox jumps over\nthe lazy dog.",
+///     format!("{}", wrap(text, 15))
+/// );
+///
+/// // Wrapped lines can be indented to hang below the first line
+/// assert_eq!(
+///     "-v, --verbose  Print\n    additional\n    output",
+///     format!("{}", wrap("-v, --verbose  Print additional output", 20).indent(4))
+/// );
+/// ```
+///
+/// ## Note
+/// Format string arguments are not passed to the inner value.
+#[inline]
+pub fn wrap<T>(value: T, width: usize) -> Wrap<'static, T> {
+    Wrap {
+        value,
+        width,
+        indent: 0,
+        word_break: WordBreak::Overflow,
+        break_chars: &[],
+    }
+}
+
+/// How words which are longer than a line are handled by [Wrap].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum WordBreak {
+    /// Write the word on a line of its own, exceeding the width
+    #[default]
+    Overflow,
+    /// Break the word at the end of each line
+    Break,
+    /// Break the word at the end of each line, ending each broken line with `-`
+    Hyphenate,
+}
+
+pub struct Wrap<'a, T> {
+    value: T,
+    width: usize,
+    indent: usize,
+    word_break: WordBreak,
+    break_chars: &'a [char],
+}
+
+impl<'a, T> Wrap<'a, T> {
+    /// Indent lines created by wrapping by the given number of spaces. Lines following an existing
+    /// line break are not indented. The indent counts towards the width of the line.
+    #[inline]
+    pub fn indent(self, indent: usize) -> Self {
+        Wrap { indent, ..self }
+    }
+
+    /// Set how words which are longer than a line are handled. Long words start on the current
+    /// line, filling the remaining space, when they are broken.
+    /// ```rust
+    /// use fmttools::wrap::{wrap, WordBreak};
+    ///
+    /// let text = "see abcdefghijkl";
+    /// assert_eq!("see\nabcdefghijkl", wrap(text, 8).to_string());
+    /// assert_eq!("see abcd\nefghijkl", wrap(text, 8).word_break(WordBreak::Break).to_string());
+    /// assert_eq!(
+    ///     "see abc-\ndefghij-\nkl",
+    ///     wrap(text, 8).word_break(WordBreak::Hyphenate).to_string()
+    /// );
+    /// ```
+    #[inline]
+    pub fn word_break(self, word_break: WordBreak) -> Self {
+        Wrap { word_break, ..self }
+    }
+
+    /// Allow lines to be broken after any of the given characters, in addition to whitespace.
+    /// The character is kept at the end of the line.
+    /// ```rust
+    /// use fmttools::wrap;
+    ///
+    /// let text = "fetching https://example.com/files/archive.tar.gz";
+    /// assert_eq!(
+    ///     "fetching https://\nexample.com/files/\narchive.tar.gz",
+    ///     wrap(text, 20).break_chars(&['/', '-']).to_string()
+    /// );
+    /// ```
+    #[inline]
+    pub fn break_chars(self, break_chars: &[char]) -> Wrap<'_, T> {
+        Wrap {
+            value: self.value,
+            width: self.width,
+            indent: self.indent,
+            word_break: self.word_break,
+            break_chars,
+        }
+    }
+
+    fn fmt_impl<F>(&self, f: &mut Formatter<'_>, func: F) -> fmt::Result
+    where
+        F: FnOnce(&mut WrapWriter<'_, &mut Formatter<'_>>) -> fmt::Result,
+    {
+        let mut writer = WrapWriter {
+            dst: f,
+            width: self.width,
+            indent: self.indent,
+            word_break: self.word_break,
+            break_chars: self.break_chars,
+            column: 0,
+            line_has_text: false,
+            measure: WidthCounter::new(),
+            buf: [0; WORD_BUFFER],
+            space_len: 0,
+            space_width: 0,
+            len: 0,
+            word_width: 0,
+            streaming: false,
+        };
+
+        func(&mut writer)?;
+        writer.commit()
+    }
+}
+
+impl<T: Debug> Debug for Wrap<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{:?}", self.value))
+    }
+}
+
+impl<T: Display> Display for Wrap<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_impl(f, |out| write!(out, "{}", self.value))
+    }
+}
+
+/// Holds back whitespace and the following word until the end of the word is found, then
+/// decides whether the word fits on the current line.
+struct WrapWriter<'a, W> {
+    dst: W,
+    width: usize,
+    indent: usize,
+    word_break: WordBreak,
+    break_chars: &'a [char],
+    /// The number of columns written to the current line
+    column: usize,
+    /// If anything other than the indent has been written to the current line
+    line_has_text: bool,
+    measure: WidthCounter,
+    /// Pending whitespace followed by the pending word
+    buf: [u8; WORD_BUFFER],
+    space_len: usize,
+    space_width: usize,
+    /// The total length of the pending whitespace and word
+    len: usize,
+    word_width: usize,
+    /// If the current word did not fit in the buffer, so the rest of it is being written directly
+    streaming: bool,
+}
+
+impl<W: Write> WrapWriter<'_, W> {
+    /// Measure the width of a character, taking escape sequences into account.
+    #[inline]
+    fn char_width(&mut self, c: char) -> usize {
+        let before = self.measure.width();
+        let _ = self.measure.write_char(c);
+        self.measure.width() - before
+    }
+
+    /// Append a character to the buffer, returning false if there is no room.
+    #[inline]
+    fn push(&mut self, c: char) -> bool {
+        let len = c.len_utf8();
+        if self.len + len > WORD_BUFFER {
+            return false;
+        }
+
+        c.encode_utf8(&mut self.buf[self.len..self.len + len]);
+        self.len += len;
+        true
+    }
+
+    #[inline]
+    fn new_line(&mut self) -> fmt::Result {
+        self.dst.write_char('\n')?;
+        write_repeated(&mut self.dst, ' ', self.indent)?;
+        self.column = self.indent;
+        self.line_has_text = false;
+        Ok(())
+    }
+
+    /// Write a character of a word which is being broken across lines, starting a new line first
+    /// if it does not fit. Room is left for a hyphen if more of the word may follow.
+    fn write_broken_char(&mut self, c: char, width: usize, more: bool) -> fmt::Result {
+        let hyphen = usize::from(self.word_break == WordBreak::Hyphenate && more);
+        if self.line_has_text && self.column + width + hyphen > self.width {
+            if self.word_break == WordBreak::Hyphenate {
+                self.dst.write_char('-')?;
+            }
+            self.new_line()?;
+        }
+
+        self.dst.write_char(c)?;
+        self.column += width;
+        self.line_has_text = true;
+        Ok(())
+    }
+
+    /// Write the pending word, starting a new line first if it does not fit on the current one.
+    /// Pending whitespace is only written if the word is placed on the same line.
+    fn commit(&mut self) -> fmt::Result {
+        if self.len == self.space_len {
+            return Ok(());
+        }
+
+        let fits = self.column + self.space_width + self.word_width <= self.width;
+        let too_long = self.word_width > self.width.saturating_sub(self.indent);
+
+        if !fits && too_long && self.word_break != WordBreak::Overflow {
+            // Only start the word on this line if at least one character fits after the space
+            if self.line_has_text && self.column + self.space_width + 2 > self.width {
+                self.new_line()?;
+            } else {
+                self.write_pending(0, self.space_len)?;
+                self.column += self.space_width;
+            }
+
+            let mut measure = WidthCounter::new();
+            let mut index = self.space_len;
+            while index < self.len {
+                let c = self.pending_char(index)?;
+                let before = measure.width();
+                let _ = measure.write_char(c);
+                index += c.len_utf8();
+                self.write_broken_char(c, measure.width() - before, index < self.len)?;
+            }
+        } else {
+            let start = if self.line_has_text && !fits {
+                self.new_line()?;
+                self.space_len
+            } else {
+                self.column += self.space_width;
+                0
+            };
+
+            self.write_pending(start, self.len)?;
+            self.column += self.word_width;
+        }
+
+        self.line_has_text = true;
+        self.len = 0;
+        self.space_len = 0;
+        self.space_width = 0;
+        self.word_width = 0;
+        Ok(())
+    }
+
+    #[inline]
+    fn write_pending(&mut self, start: usize, end: usize) -> fmt::Result {
+        // Only whole characters are ever added to the buffer
+        let pending = str::from_utf8(&self.buf[start..end]).map_err(|_| fmt::Error)?;
+        self.dst.write_str(pending)
+    }
+
+    #[inline]
+    fn pending_char(&self, index: usize) -> Result<char, fmt::Error> {
+        let pending = str::from_utf8(&self.buf[index..self.len]).map_err(|_| fmt::Error)?;
+        pending.chars().next().ok_or(fmt::Error)
+    }
+
+    /// Write a character of a word which was too long to hold back.
+    #[inline]
+    fn write_streamed_char(&mut self, c: char, width: usize) -> fmt::Result {
+        if self.word_break == WordBreak::Overflow {
+            self.dst.write_char(c)?;
+            self.column += width;
+            Ok(())
+        } else {
+            self.write_broken_char(c, width, true)
+        }
+    }
+
+    /// Discard pending whitespace which was not followed by a word.
+    #[inline]
+    fn discard_space(&mut self) {
+        self.len = 0;
+        self.space_len = 0;
+        self.space_width = 0;
+    }
+}
+
+impl<W: Write> Write for WrapWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '\n' {
+                self.commit()?;
+                self.discard_space();
+                self.dst.write_char('\n')?;
+                self.column = 0;
+                self.line_has_text = false;
+                self.streaming = false;
+                continue;
+            }
+
+            let width = self.char_width(c);
+
+            // Carriage returns are kept with the preceding word so `\r\n` is not split
+            if c.is_whitespace() && c != '\r' {
+                if self.streaming {
+                    self.streaming = false;
+                } else if self.len > self.space_len {
+                    self.commit()?;
+                }
+
+                // Runs of whitespace longer than the buffer are shortened
+                if self.push(c) {
+                    self.space_len = self.len;
+                    self.space_width += width;
+                }
+                continue;
+            }
+
+            let is_break = self.break_chars.contains(&c);
+
+            if self.streaming {
+                self.write_streamed_char(c, width)?;
+                // Anything after a break character can be placed on its own
+                self.streaming = !is_break;
+                continue;
+            }
+
+            if !self.push(c) {
+                // The word is too long to hold back, so place it based on what we have seen so far
+                self.commit()?;
+                self.streaming = !is_break;
+                self.write_streamed_char(c, width)?;
+                continue;
+            }
+
+            self.word_width += width;
+            if is_break {
+                self.commit()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wrap, WordBreak, WORD_BUFFER};
+
+    #[test]
+    fn preserves_line_breaks() {
+        let text = format_args!("{}{}", "aaa bb\n  cc dd", " eeee\r\nf   ");
+        assert_eq!("aaa\nbb\n  cc\ndd\neeee\r\nf", format!("{}", wrap(text, 5)));
+    }
+
+    #[test]
+    fn long_words() {
+        assert_eq!("a\nabcdefgh\nb", format!("{}", wrap("a abcdefgh b", 4)));
+
+        let long = "x".repeat(WORD_BUFFER + 10);
+        let expected = format!("a\n{}\nb", long);
+        assert_eq!(
+            expected,
+            format!("{}", wrap(format_args!("a {} b", long), 80))
+        );
+    }
+
+    #[test]
+    fn break_long_words() {
+        let long = "x".repeat(WORD_BUFFER + 10);
+        let text = format_args!("ab {}", long);
+        let output = wrap(text, 100).word_break(WordBreak::Break).to_string();
+        assert!(output.lines().all(|line| line.len() <= 100));
+        assert_eq!(output.replace('\n', ""), format!("ab {}", long));
+
+        let output = wrap("a-b-c abcdef", 4)
+            .word_break(WordBreak::Hyphenate)
+            .break_chars(&['-'])
+            .to_string();
+        assert_eq!("a-b-\nc a-\nbcd-\nef", output);
+    }
+
+    #[test]
+    fn break_in_narrow_width() {
+        let output = wrap("x abcdefghij", 3)
+            .indent(2)
+            .word_break(WordBreak::Hyphenate)
+            .to_string();
+        let expected = "x\n  a-\n  b-\n  c-\n  d-\n  e-\n  f-\n  g-\n  h-\n  i-\n  j";
+        assert_eq!(expected, output);
+
+        let output = wrap("abc def", 0).word_break(WordBreak::Break).to_string();
+        assert_eq!("a\nb\nc\nd\ne\nf", output);
+    }
+}
@@ -0,0 +1,160 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::{Display, Write};
+
+/// The number of bytes of output compared per pass
+const WINDOW: usize = 256;
+
+/// Returns true if two values produce the same formatted output, without allocating.
+/// ```rust
+/// use fmttools::writer::fmt_eq;
+///
+/// assert!(fmt_eq(12, "12"));
+/// assert!(fmt_eq(format_args!("{}-{}", 'a', 1), "a-1"));
+/// assert!(!fmt_eq(1.5, "1.50"));
+/// ```
+///
+/// ## Note
+/// Output is compared in blocks of 256 bytes, and both values are formatted once for every block.
+/// Values which can only be formatted once, such as [crate::join] of an iterator, should not be
+/// compared if their output may be longer than this.
+#[inline]
+pub fn fmt_eq<A: Display, B: Display>(a: A, b: B) -> bool {
+    fmt_cmp(a, b) == Ordering::Equal
+}
+
+/// Compare the formatted output of two values byte by byte, without allocating. This gives the
+/// same result as comparing the strings produced by `to_string`. See [fmt_eq] for details.
+/// ```rust
+/// use std::cmp::Ordering;
+/// use fmttools::writer::fmt_cmp;
+///
+/// assert_eq!(Ordering::Less, fmt_cmp(10, 9));
+/// assert_eq!(Ordering::Greater, fmt_cmp("abc", "ab"));
+/// ```
+pub fn fmt_cmp<A: Display, B: Display>(a: A, b: B) -> Ordering {
+    let mut offset = 0;
+
+    loop {
+        let mut window = Capture {
+            skip: offset,
+            buf: [0; WINDOW],
+            len: 0,
+            more: false,
+        };
+        // Errors are only used to stop formatting early, and the captured output is used either way
+        let _ = write!(window, "{}", a);
+
+        let mut compare = Compare {
+            skip: offset,
+            expected: &window.buf[..window.len],
+            pos: 0,
+            result: None,
+        };
+        let _ = write!(compare, "{}", b);
+
+        match compare.result {
+            Some(Ordering::Equal) => offset += WINDOW,
+            Some(ordering) => return ordering,
+            None if compare.pos < window.len => return Ordering::Greater,
+            None if window.more => return Ordering::Greater,
+            None => return Ordering::Equal,
+        }
+    }
+}
+
+/// Records the bytes of output which fall within a window, stopping once it is full.
+struct Capture {
+    skip: usize,
+    buf: [u8; WINDOW],
+    len: usize,
+    /// If there was output after the end of the window
+    more: bool,
+}
+
+impl Write for Capture {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut bytes = s.as_bytes();
+
+        let skipped = self.skip.min(bytes.len());
+        self.skip -= skipped;
+        bytes = &bytes[skipped..];
+
+        let taken = bytes.len().min(WINDOW - self.len);
+        self.buf[self.len..self.len + taken].copy_from_slice(&bytes[..taken]);
+        self.len += taken;
+
+        if taken < bytes.len() {
+            self.more = true;
+            return Err(fmt::Error);
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares output against the bytes captured for the same window. Once the outcome is known, the
+/// ordering of the captured output relative to this output is set and formatting is stopped.
+/// `Some(Ordering::Equal)` means the window matched and there is further output to compare.
+struct Compare<'a> {
+    skip: usize,
+    expected: &'a [u8],
+    pos: usize,
+    result: Option<Ordering>,
+}
+
+impl Write for Compare<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut bytes = s.as_bytes();
+
+        let skipped = self.skip.min(bytes.len());
+        self.skip -= skipped;
+        bytes = &bytes[skipped..];
+
+        for &byte in bytes {
+            let ordering = match self.expected.get(self.pos) {
+                Some(&expected) => expected.cmp(&byte),
+                // The window is full, so the rest is compared in the next pass
+                None if self.expected.len() == WINDOW => Ordering::Equal,
+                // The other output ended first
+                None => Ordering::Less,
+            };
+
+            if self.pos == self.expected.len() || ordering != Ordering::Equal {
+                self.result = Some(ordering);
+                return Err(fmt::Error);
+            }
+
+            self.pos += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fmt_cmp, WINDOW};
+
+    #[test]
+    fn matches_string_comparison() {
+        let long = "x".repeat(WINDOW * 2);
+        let cases = [
+            ("", ""),
+            ("", "a"),
+            ("abc", "abd"),
+            ("é", "e"),
+            ("aé", "ae"),
+            (&long[..WINDOW], &long[..WINDOW + 1]),
+            (&long[..WINDOW * 2], &long[..WINDOW * 2]),
+            (&long[..WINDOW * 2 - 1], &long[..WINDOW * 2]),
+        ];
+
+        for (a, b) in cases.iter() {
+            let (start, end) = a.split_at(a.chars().count() / 2);
+            let chunked = format_args!("{}{}", start, end);
+            assert_eq!(a.cmp(b), fmt_cmp(chunked, b), "{:?} {:?}", a, b);
+            assert_eq!(b.cmp(a), fmt_cmp(b, a), "{:?} {:?}", b, a);
+        }
+    }
+}
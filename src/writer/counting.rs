@@ -0,0 +1,81 @@
+use crate::writer::feed;
+use core::fmt;
+use core::fmt::{Display, Write};
+
+/// Compute the length in bytes of a value's formatted output without allocating. This is the
+/// length of the string which would be produced by `value.to_string()`.
+/// ```rust
+/// use fmttools::writer::measure;
+///
+/// assert_eq!(5, measure(12345));
+/// assert_eq!(6, measure("héllo"));
+/// ```
+#[inline]
+pub fn measure<T: Display>(value: T) -> usize {
+    let mut counter = CountingWriter::new();
+    feed(&mut counter, value);
+    counter.bytes()
+}
+
+/// A writer which discards its input while tallying the number of bytes and characters written.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::writer::CountingWriter;
+///
+/// let mut counter = CountingWriter::new();
+/// write!(counter, "{}-{}", "ab", 'é').unwrap();
+/// assert_eq!(5, counter.bytes());
+/// assert_eq!(4, counter.chars());
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct CountingWriter {
+    bytes: usize,
+    chars: usize,
+}
+
+impl CountingWriter {
+    #[inline]
+    pub const fn new() -> Self {
+        CountingWriter { bytes: 0, chars: 0 }
+    }
+
+    /// The number of bytes written so far.
+    #[inline]
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// The number of characters written so far.
+    #[inline]
+    pub fn chars(&self) -> usize {
+        self.chars
+    }
+}
+
+impl Write for CountingWriter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.bytes += s.len();
+        self.chars += s.chars().count();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.bytes += c.len_utf8();
+        self.chars += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::measure;
+
+    #[test]
+    fn measure_matches_to_string() {
+        let value = format_args!("{:>8}|{:.2}|{:?}", "ä", 1.0 / 3.0, "\n");
+        assert_eq!(measure(value), value.to_string().len());
+        assert_eq!(measure(""), 0);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::writer::feed;
+use core::fmt;
+use core::fmt::{Display, Write};
+use core::hash::Hasher;
+
+/// Feed the formatted output of a value into a [Hasher] without allocating. The resulting hash is
+/// the same as hashing `value.to_string()`, as long as the hasher does not depend on how its input
+/// is split across calls to [Hasher::write].
+/// ```rust
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::{Hash, Hasher};
+/// use fmttools::writer::hash_fmt;
+///
+/// let mut a = DefaultHasher::new();
+/// hash_fmt(format_args!("{}-{}", 12, 'x'), &mut a);
+///
+/// let mut b = DefaultHasher::new();
+/// "12-x".hash(&mut b);
+/// assert_eq!(a.finish(), b.finish());
+/// ```
+#[inline]
+pub fn hash_fmt<T: Display, H: Hasher + ?Sized>(value: T, hasher: &mut H) {
+    feed(&mut FmtHasher::new(&mut *hasher), value);
+    // Match the terminator written by the `Hash` implementation of `str`
+    hasher.write_u8(0xff);
+}
+
+/// A writer which passes the bytes of its input to a [Hasher].
+/// ```rust
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::fmt::Write;
+/// use std::hash::Hasher;
+/// use fmttools::writer::FmtHasher;
+///
+/// let mut writer = FmtHasher::new(DefaultHasher::new());
+/// write!(writer, "{:?}", [1, 2, 3]).unwrap();
+/// let hash = writer.into_inner().finish();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FmtHasher<H> {
+    hasher: H,
+}
+
+impl<H> FmtHasher<H> {
+    #[inline]
+    pub fn new(hasher: H) -> Self {
+        FmtHasher { hasher }
+    }
+
+    /// Get a reference to the underlying hasher.
+    #[inline]
+    pub fn get_ref(&self) -> &H {
+        &self.hasher
+    }
+
+    /// Unwrap this writer, returning the underlying hasher.
+    #[inline]
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+}
+
+impl<H: Hasher> Write for FmtHasher<H> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.hasher.write(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use core::fmt;
+use core::fmt::Write;
+
+/// A writer which forwards at most `max_bytes` bytes of output to another writer. Once the limit
+/// is exceeded, the marker is written and any remaining output is discarded. Truncation never
+/// splits a character, so slightly less than `max_bytes` may be forwarded before the marker.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::writer::LimitWriter;
+///
+/// let mut writer = LimitWriter::new(String::new(), 8, "…");
+/// write!(writer, "{}", "a very long message").unwrap();
+/// assert_eq!(11, writer.discarded());
+/// assert_eq!("a very l…", writer.into_inner());
+/// ```
+#[derive(Debug, Clone)]
+pub struct LimitWriter<'a, W> {
+    dst: W,
+    remaining: usize,
+    marker: &'a str,
+    discarded: usize,
+}
+
+impl<'a, W> LimitWriter<'a, W> {
+    #[inline]
+    pub fn new(dst: W, max_bytes: usize, marker: &'a str) -> Self {
+        LimitWriter {
+            dst,
+            remaining: max_bytes,
+            marker,
+            discarded: 0,
+        }
+    }
+
+    /// The number of bytes of output which were discarded, excluding the marker.
+    #[inline]
+    pub fn discarded(&self) -> usize {
+        self.discarded
+    }
+
+    /// Returns true if the limit was exceeded and the marker has been written.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.discarded > 0
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Unwrap this writer, returning the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W: Write> Write for LimitWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.is_truncated() {
+            self.discarded += s.len();
+            return Ok(());
+        }
+
+        if s.len() <= self.remaining {
+            self.remaining -= s.len();
+            return self.dst.write_str(s);
+        }
+
+        let mut end = self.remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        self.remaining = 0;
+        self.discarded = s.len() - end;
+        self.dst.write_str(&s[..end])?;
+        self.dst.write_str(self.marker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LimitWriter;
+    use std::fmt::Write;
+
+    #[test]
+    fn limit_across_writes() {
+        let mut writer = LimitWriter::new(String::new(), 6, "...");
+        writer.write_str("abc").unwrap();
+        writer.write_str("dé").unwrap();
+        assert!(!writer.is_truncated());
+        writer.write_str("éf").unwrap();
+        writer.write_str("gh").unwrap();
+        assert_eq!(5, writer.discarded());
+        assert_eq!("abcdé...", writer.into_inner());
+
+        let mut writer = LimitWriter::new(String::new(), 4, "~");
+        writer.write_str("abcé").unwrap();
+        assert_eq!("abc~", writer.into_inner());
+    }
+}
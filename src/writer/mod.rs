@@ -0,0 +1,40 @@
+//! Reusable implementations of [std::fmt::Write] which inspect or redirect formatted output.
+mod compare;
+mod counting;
+mod hash;
+mod limit;
+mod position;
+mod search;
+mod slice;
+mod stats;
+mod tee;
+
+pub use compare::{fmt_cmp, fmt_eq};
+pub use counting::{measure, CountingWriter};
+pub use hash::{hash_fmt, FmtHasher};
+pub use limit::LimitWriter;
+pub use position::PositionWriter;
+pub use search::{fmt_contains, fmt_ends_with, fmt_starts_with};
+pub use slice::{write_to_slice, Truncated};
+pub use stats::{FmtStats, Stats};
+pub use tee::TeeWriter;
+
+use core::fmt;
+use core::fmt::{Display, Write};
+
+/// Write a character `count` times, such as when padding output to a width.
+#[inline]
+pub(crate) fn write_repeated<W: Write + ?Sized>(f: &mut W, c: char, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// Write a value to a writer which never fails on its own, such as one which only measures or
+/// hashes its input. An error could then only come from the value itself, so it is ignored and
+/// the output written before it is kept.
+#[inline]
+pub(crate) fn feed<W: Write, T: Display>(writer: &mut W, value: T) {
+    let _ = write!(writer, "{}", value);
+}
@@ -0,0 +1,97 @@
+use core::fmt;
+use core::fmt::Write;
+
+/// A writer which forwards output unchanged to another writer while tracking the line and column
+/// where the next character will be written. Lines and columns are counted from zero and columns
+/// are counted in characters. `\n`, `\r\n` and `\r` are each treated as a single line break, even
+/// when `\r\n` is split across writes.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::writer::PositionWriter;
+///
+/// let mut writer = PositionWriter::new(String::new());
+/// write!(writer, "error: unexpected token\n  let x = ").unwrap();
+/// assert_eq!((1, 10), (writer.line(), writer.column()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PositionWriter<W> {
+    dst: W,
+    line: usize,
+    column: usize,
+    /// If the last character written was `\r`, so a following `\n` does not start another line
+    after_cr: bool,
+}
+
+impl<W> PositionWriter<W> {
+    #[inline]
+    pub fn new(dst: W) -> Self {
+        PositionWriter {
+            dst,
+            line: 0,
+            column: 0,
+            after_cr: false,
+        }
+    }
+
+    /// The line the next character will be written to.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column the next character will be written to.
+    #[inline]
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Unwrap this writer, returning the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W: Write> Write for PositionWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.dst.write_str(s)?;
+
+        for c in s.chars() {
+            let after_cr = self.after_cr;
+            self.after_cr = c == '\r';
+
+            match c {
+                '\n' if after_cr => {}
+                '\n' | '\r' => {
+                    self.line += 1;
+                    self.column = 0;
+                }
+                _ => self.column += 1,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PositionWriter;
+    use std::fmt::Write;
+
+    #[test]
+    fn crlf_split_across_writes() {
+        let mut writer = PositionWriter::new(String::new());
+        writer.write_str("ab\r").unwrap();
+        assert_eq!((writer.line(), writer.column()), (1, 0));
+        writer.write_str("\ncd\r\r").unwrap();
+        assert_eq!((writer.line(), writer.column()), (3, 0));
+        assert_eq!(writer.into_inner(), "ab\r\ncd\r\r");
+    }
+}
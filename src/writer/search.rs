@@ -0,0 +1,129 @@
+use crate::replace::ReplacePattern;
+use crate::writer::{measure, CountingWriter, TeeWriter};
+use core::fmt;
+use core::fmt::{Display, Write};
+
+/// Returns true if the formatted output of a value contains a pattern, without allocating.
+/// ```rust
+/// use fmttools::writer::fmt_contains;
+///
+/// assert!(fmt_contains(format_args!("{:?}", [1, 23, 4]), "23, 4"));
+/// assert!(!fmt_contains(3.25, "3.3"));
+/// ```
+pub fn fmt_contains<T: Display>(value: T, pattern: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+
+    // Remove every occurrence of the pattern and check if the output got any shorter
+    let mut total = CountingWriter::new();
+    let mut remaining = CountingWriter::new();
+    let _ = pattern.fmt_impl("", &mut remaining, |out| {
+        write!(TeeWriter::new(&mut total, out), "{}", value)
+    });
+
+    remaining.bytes() < total.bytes()
+}
+
+/// Returns true if the formatted output of a value starts with a pattern, without allocating.
+/// Formatting stops as soon as the result is known.
+/// ```rust
+/// use fmttools::writer::fmt_starts_with;
+///
+/// assert!(fmt_starts_with(format_args!("{}: {}", "error", 5), "error:"));
+/// assert!(!fmt_starts_with(12, "123"));
+/// ```
+pub fn fmt_starts_with<T: Display>(value: T, pattern: &str) -> bool {
+    let mut prefix = Prefix {
+        skip: 0,
+        remaining: pattern.as_bytes(),
+        matches: true,
+    };
+    let _ = write!(prefix, "{}", value);
+    prefix.matches && prefix.remaining.is_empty()
+}
+
+/// Returns true if the formatted output of a value ends with a pattern, without allocating.
+/// ```rust
+/// use fmttools::writer::fmt_ends_with;
+///
+/// assert!(fmt_ends_with(format_args!("{}.{}", "archive", "tar.gz"), ".tar.gz"));
+/// assert!(!fmt_ends_with(12, "012"));
+/// ```
+///
+/// ## Note
+/// The value is formatted twice, once to find the length of the output and once to compare its
+/// end against the pattern.
+pub fn fmt_ends_with<T: Display>(value: T, pattern: &str) -> bool {
+    let len = measure(&value);
+    if pattern.len() > len {
+        return false;
+    }
+
+    let mut suffix = Prefix {
+        skip: len - pattern.len(),
+        remaining: pattern.as_bytes(),
+        matches: true,
+    };
+    let _ = write!(suffix, "{}", value);
+    suffix.matches && suffix.remaining.is_empty()
+}
+
+/// Checks that the output following the first `skip` bytes starts with the remaining bytes,
+/// stopping once either a difference is found or all of the bytes have been matched.
+struct Prefix<'a> {
+    skip: usize,
+    remaining: &'a [u8],
+    matches: bool,
+}
+
+impl Write for Prefix<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut bytes = s.as_bytes();
+
+        let skipped = self.skip.min(bytes.len());
+        self.skip -= skipped;
+        bytes = &bytes[skipped..];
+
+        let overlap = bytes.len().min(self.remaining.len());
+        if bytes[..overlap] != self.remaining[..overlap] {
+            self.matches = false;
+            return Err(fmt::Error);
+        }
+
+        self.remaining = &self.remaining[overlap..];
+        if self.remaining.is_empty() {
+            return Err(fmt::Error);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fmt_contains, fmt_ends_with, fmt_starts_with};
+
+    #[test]
+    fn matches_str_methods() {
+        let text = "abacaabab, ababc";
+        let patterns = [
+            "",
+            "a",
+            "ab",
+            "ababc",
+            "bab,",
+            "c",
+            "abc ",
+            "abacaabab, ababc!",
+        ];
+
+        for pattern in patterns.iter() {
+            let (start, end) = text.split_at(7);
+            let chunked = format_args!("{}{}", start, end);
+            assert_eq!(text.contains(pattern), fmt_contains(chunked, pattern));
+            assert_eq!(text.starts_with(pattern), fmt_starts_with(chunked, pattern));
+            assert_eq!(text.ends_with(pattern), fmt_ends_with(chunked, pattern));
+        }
+    }
+}
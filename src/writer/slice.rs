@@ -0,0 +1,120 @@
+use core::error::Error;
+use core::fmt;
+use core::fmt::{Display, Formatter, Write};
+use core::str;
+
+/// Format a value directly into a byte buffer, returning the written text without allocating.
+///
+/// If the output does not fit, the buffer is filled with as much of the output as possible without
+/// splitting a character and [Truncated] is returned holding the text which was written.
+/// ```rust
+/// use fmttools::writer::write_to_slice;
+///
+/// let mut buf = [0u8; 8];
+/// assert_eq!(Ok("x = 42"), write_to_slice(&mut buf, format_args!("x = {}", 42)));
+///
+/// let err = write_to_slice(&mut buf, "ünïcödé").unwrap_err();
+/// assert_eq!("ünïcö", err.as_str());
+/// ```
+pub fn write_to_slice<T: Display>(buf: &mut [u8], value: T) -> Result<&str, Truncated<'_>> {
+    let mut writer = SliceWriter::new(buf);
+    // Errors from the value itself are reported the same way, as the output is incomplete
+    let complete = write!(writer, "{}", value).is_ok();
+    let text = writer.into_str();
+
+    if complete {
+        Ok(text)
+    } else {
+        Err(Truncated { written: text })
+    }
+}
+
+/// Error returned by [write_to_slice] when the formatted output did not fit in the buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Truncated<'a> {
+    written: &'a str,
+}
+
+impl<'a> Truncated<'a> {
+    /// The prefix of the output which was written to the buffer. This always ends on a character
+    /// boundary.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.written
+    }
+}
+
+impl Display for Truncated<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "formatted output truncated after {} bytes",
+            self.written.len()
+        )
+    }
+}
+
+impl Error for Truncated<'_> {}
+
+/// A writer which fills a byte buffer, failing once the buffer is full.
+pub(crate) struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    #[inline]
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter { buf, len: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn into_str(self) -> &'a str {
+        // Only whole characters are ever copied into the buffer
+        str::from_utf8(&self.buf[..self.len]).expect("buffer contains valid UTF-8")
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let remaining = self.buf.len() - self.len;
+
+        if s.len() <= remaining {
+            self.buf[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            return Ok(());
+        }
+
+        // Copy the longest prefix which fits without splitting a character
+        let mut end = remaining;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        Err(fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_to_slice;
+
+    #[test]
+    fn truncates_on_char_boundary() {
+        let mut buf = [0u8; 5];
+        assert_eq!(Ok("héll"), write_to_slice(&mut buf, "héll"));
+        assert_eq!(
+            "héll",
+            write_to_slice(&mut buf, "héllo€").unwrap_err().as_str()
+        );
+        assert_eq!(
+            "ab€",
+            write_to_slice(&mut buf, format_args!("{}{}", "ab", "€€"))
+                .unwrap_err()
+                .as_str()
+        );
+        assert_eq!(Ok(""), write_to_slice(&mut [], ""));
+    }
+}
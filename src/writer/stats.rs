@@ -0,0 +1,112 @@
+use core::fmt;
+use core::fmt::Write;
+
+/// Statistics about formatted output collected by [FmtStats].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of lines written. A trailing line break does not start a new line.
+    pub lines: usize,
+    /// The number of characters written, including line breaks.
+    pub chars: usize,
+    /// The number of characters in the longest line, excluding the line break.
+    pub max_line_width: usize,
+}
+
+/// A writer which forwards output unchanged to another writer while collecting [Stats] about it.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::writer::FmtStats;
+///
+/// let mut writer = FmtStats::new(String::new());
+/// write!(writer, "first\nsecond line\n").unwrap();
+///
+/// let stats = writer.stats();
+/// assert_eq!(2, stats.lines);
+/// assert_eq!(18, stats.chars);
+/// assert_eq!(11, stats.max_line_width);
+/// assert_eq!("first\nsecond line\n", writer.into_inner());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FmtStats<W> {
+    dst: W,
+    stats: Stats,
+    /// The number of characters written to the current line
+    line_width: usize,
+}
+
+impl<W> FmtStats<W> {
+    #[inline]
+    pub fn new(dst: W) -> Self {
+        FmtStats {
+            dst,
+            stats: Stats::default(),
+            line_width: 0,
+        }
+    }
+
+    /// Get the statistics for the output written so far.
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        Stats {
+            lines: self.stats.lines + usize::from(self.line_width > 0),
+            ..self.stats
+        }
+    }
+
+    /// Get a reference to the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.dst
+    }
+
+    /// Unwrap this writer, returning the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.dst
+    }
+}
+
+impl<W: Write> Write for FmtStats<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.dst.write_str(s)?;
+
+        for c in s.chars() {
+            self.stats.chars += 1;
+            if c == '\n' {
+                self.stats.lines += 1;
+                self.line_width = 0;
+            } else {
+                self.line_width += 1;
+                self.stats.max_line_width = self.stats.max_line_width.max(self.line_width);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FmtStats, Stats};
+    use std::fmt::Write;
+
+    #[test]
+    fn empty_output() {
+        let writer = FmtStats::new(String::new());
+        assert_eq!(writer.stats(), Stats::default());
+    }
+
+    #[test]
+    fn unterminated_last_line() {
+        let mut writer = FmtStats::new(String::new());
+        writer.write_str("ab\n\nc").unwrap();
+        writer.write_str("dé").unwrap();
+
+        let expected = Stats {
+            lines: 3,
+            chars: 7,
+            max_line_width: 3,
+        };
+        assert_eq!(writer.stats(), expected);
+    }
+}
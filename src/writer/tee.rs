@@ -0,0 +1,110 @@
+use core::fmt;
+use core::fmt::Write;
+
+/// A writer which duplicates all output to two other writers. Each chunk is written to the first
+/// writer before the second, and the first error encountered is returned.
+/// ```rust
+/// use std::fmt::Write;
+/// use fmttools::writer::{CountingWriter, TeeWriter};
+///
+/// let mut writer = TeeWriter::new(String::new(), CountingWriter::new());
+/// write!(writer, "{} + {}", 1, 2).unwrap();
+///
+/// let (text, counter) = writer.into_inner();
+/// assert_eq!("1 + 2", text);
+/// assert_eq!(5, counter.bytes());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TeeWriter<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> TeeWriter<A, B> {
+    #[inline]
+    pub fn new(a: A, b: B) -> Self {
+        TeeWriter { a, b }
+    }
+
+    /// Get references to both underlying writers.
+    #[inline]
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.a, &self.b)
+    }
+
+    /// Unwrap this writer, returning both underlying writers.
+    #[inline]
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: Write, B: Write> Write for TeeWriter<A, B> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.a.write_str(s)?;
+        self.b.write_str(s)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.a.write_char(c)?;
+        self.b.write_char(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TeeWriter;
+    use std::fmt;
+    use std::fmt::Write;
+
+    /// A writer which fails if its output would exceed the given number of bytes.
+    struct FailAfter(usize, String);
+
+    impl Write for FailAfter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if self.1.len() + s.len() > self.0 {
+                return Err(fmt::Error);
+            }
+            self.1.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn both_writers_receive_output() {
+        let mut writer = TeeWriter::new(String::new(), String::new());
+        let accent = 'é';
+        write!(writer, "{}-{}", 12, accent).unwrap();
+        writer.write_char('!').unwrap();
+
+        let (a, b) = writer.into_inner();
+        assert_eq!("12-é!", a);
+        assert_eq!("12-é!", b);
+    }
+
+    #[test]
+    fn first_writer_error() {
+        let mut writer = TeeWriter::new(FailAfter(2, String::new()), String::new());
+        writer.write_str("ab").unwrap();
+        assert!(writer.write_str("cd").is_err());
+        assert!(writer.write_char('e').is_err());
+
+        // The second writer is skipped once the first fails
+        let (a, b) = writer.into_inner();
+        assert_eq!("ab", a.1);
+        assert_eq!("ab", b);
+    }
+
+    #[test]
+    fn second_writer_error() {
+        let mut writer = TeeWriter::new(String::new(), FailAfter(2, String::new()));
+        writer.write_str("ab").unwrap();
+        assert!(writer.write_str("cd").is_err());
+
+        let (a, b) = writer.into_inner();
+        assert_eq!("abcd", a);
+        assert_eq!("ab", b.1);
+    }
+}